@@ -1,8 +1,9 @@
 use std::{collections::HashMap, rc::Rc};
 
 use rust_graph_lib::{
-    algorithms::{Algorithm, Dfs, Dijkstra},
-    graph::Graph,
+    algorithms::registry,
+    format::{FormatStyle, GraphFormatter},
+    graph::{Graph, GraphRead},
     impls::adj_list::AdjListGraph,
 };
 
@@ -29,36 +30,28 @@ fn main() {
     println!("Created graph!");
     println!("Node count: {}", g.node_count());
     println!("Edge count: {}", g.edge_count());
-    println!("{}", g);
+    println!(
+        "{}",
+        GraphFormatter::new(&g)
+            .style(FormatStyle::AdjacencyList)
+            .sorted(true)
+    );
 
     let from = *indexes.get(&1).unwrap();
     let to = *indexes.get(&max).unwrap();
 
-    let ps = {
-        let dfs = Dfs::new(g.as_ref());
-        match dfs.run(from, to) {
-            None => "No path found with DFS".to_string(),
-            Some(ps) => ps
-                .iter()
-                .map(|&p| p.to_string())
-                .collect::<Vec<_>>()
-                .join(", "),
-        }
-    };
-
-    let pd = {
-        let dijkstra = Dijkstra::new(g.as_ref());
-        match dijkstra.run(from, to) {
-            None => "No path found with Dijkstra".to_string(),
-            Some(pd) => pd
+    println!("Paths from {} to {}", from, to);
+    let algorithms = registry();
+    for name in algorithms.names() {
+        let search = algorithms.get(name).unwrap()(&g);
+        let path = match search.run(from, to) {
+            None => format!("No path found with {}", name),
+            Some(p) => p
                 .iter()
                 .map(|&p| p.to_string())
                 .collect::<Vec<_>>()
                 .join(", "),
-        }
-    };
-
-    println!("Paths from {} to {}", from, to);
-    println!("DFS: {}", ps);
-    println!("DIJKSTRA: {}", pd);
+        };
+        println!("{}: {}", name.to_uppercase(), path);
+    }
 }
@@ -0,0 +1,74 @@
+use rust_graph_lib::{
+    algorithms::{Algorithm, Dijkstra},
+    graph::{Graph, GraphRead},
+    impls::adj_list::AdjListGraph,
+};
+use std::time::Instant;
+
+fn dense_graph(n: usize) -> (AdjListGraph, Vec<<AdjListGraph as GraphRead>::Index>) {
+    let mut g = AdjListGraph::new();
+    let nodes: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+    for (i, &f) in nodes.iter().enumerate() {
+        for (j, &t) in nodes.iter().enumerate() {
+            // skip the direct shortcuts so the search has to expand many nodes
+            if i != j && j != i + 1 && (j > i + 1 || i % 2 == 0) {
+                g.add_edge(f, t);
+            }
+        }
+    }
+    (g, nodes)
+}
+
+fn main() {
+    let runs = 20;
+
+    {
+        // long chain queried between neighbouring nodes: only a few nodes are ever touched
+        let n = 100_000;
+        let mut g = AdjListGraph::new();
+        let nodes: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+        for w in nodes.windows(2) {
+            g.add_edge(w[0], w[1]);
+        }
+        let dijkstra = Dijkstra::new(&g);
+
+        let start = Instant::now();
+        for i in 0..runs {
+            std::hint::black_box(dijkstra.run(nodes[i * 100], nodes[i * 100 + 5]));
+        }
+        println!(
+            "dijkstra chain n={:<6} local query: {:>10.3?}",
+            n,
+            start.elapsed() / runs as u32
+        );
+    }
+
+    for n in [100, 300, 600] {
+        let (mut g, nodes) = dense_graph(n);
+        // never reached, forcing the search to settle every node
+        let unreachable = g.add_node();
+        let dijkstra = Dijkstra::new(&g);
+
+        let start = Instant::now();
+        for i in 0..runs {
+            let from = nodes[i % n];
+            let to = nodes[(i * 7 + n / 2) % n];
+            std::hint::black_box(dijkstra.run(from, to));
+        }
+        let reachable = start.elapsed() / runs as u32;
+
+        let start = Instant::now();
+        for i in 0..runs {
+            std::hint::black_box(dijkstra.run(nodes[i % n], unreachable));
+        }
+        let exhaustive = start.elapsed() / runs as u32;
+
+        println!(
+            "dijkstra dense n={:<4} edges={:<7} reachable: {:>10.3?}  exhaustive: {:>10.3?}",
+            n,
+            g.edge_count(),
+            reachable,
+            exhaustive
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use rust_graph_lib::{
+    algorithms::reachable_from,
+    graph::Graph,
+    impls::{adj_list::AdjListGraph, adj_vec::AdjVecGraph},
+};
+use std::time::Instant;
+
+// sparse graph with average out-degree `degree`, wired so that everything is reachable
+fn sparse_graph<G: Graph + Default>(n: usize, degree: usize) -> (G, Vec<G::Index>)
+where
+    G::Index: Copy,
+{
+    let mut g = G::default();
+    let nodes: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+    for (i, &f) in nodes.iter().enumerate() {
+        for k in 1..=degree {
+            g.add_edge(f, nodes[(i * 31 + k * 7919) % n]);
+        }
+        g.add_edge(f, nodes[(i + 1) % n]);
+    }
+    (g, nodes)
+}
+
+fn bench<G: Graph + Default>(name: &str, n: usize, degree: usize, runs: u32)
+where
+    G::Index: Copy + std::hash::Hash,
+{
+    let (g, nodes) = sparse_graph::<G>(n, degree);
+
+    let start = Instant::now();
+    for i in 0..runs as usize {
+        std::hint::black_box(reachable_from(&g, nodes[i % n]));
+    }
+    println!(
+        "reachable_from {:<12} n={:<7} degree={:<2} full traversal: {:>10.3?}",
+        name,
+        n,
+        degree,
+        start.elapsed() / runs
+    );
+}
+
+fn main() {
+    let runs = 10;
+    if cfg!(feature = "smallvec") {
+        println!("(AdjVecGraph with inline neighbors)");
+    }
+
+    for degree in [2, 4, 7] {
+        bench::<AdjListGraph>("AdjListGraph", 100_000, degree, runs);
+        bench::<AdjVecGraph>("AdjVecGraph", 100_000, degree, runs);
+    }
+}
@@ -0,0 +1,279 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_graph_lib::{
+    graph::{Graph, GraphRead},
+    impls::{
+        adj_list::AdjListGraph, adj_vec::AdjVecGraph, concurrent::ConcurrentAdjListGraph,
+        inc_matrix::IncMatrixGraph, keyed::KeyedGraph, multi::MultiGraph,
+        persistent::PersistentGraph, typed::TypedGraph,
+        undirected_weighted::UndirectedWeightedGraph,
+    },
+    invariant::InvariantViolation,
+    views::Reversed,
+};
+use std::{collections::HashSet, fmt::Debug, hash::Hash};
+
+/**
+ * Mutation applied to the graphs; nodes are picked among the ones added so far,
+ * including removed ones, by position.
+ */
+#[derive(Arbitrary, Debug)]
+enum Op {
+    AddNode,
+    AddEdge(u8, u8),
+    RemoveNode(u8),
+    RemoveEdge(u8, u8),
+}
+
+/**
+ * Mutations of a fuzzed graph, so that graphs without a [`Graph`] impl can be driven
+ * by the same ops. `serial` counts the nodes added so far, for graphs keying them.
+ */
+trait Target: GraphRead {
+    fn insert(&mut self, serial: usize) -> Self::Index;
+    fn link(&mut self, f: Self::Index, t: Self::Index);
+    fn unlink(&mut self, f: Self::Index, t: Self::Index);
+    fn delete(&mut self, n: Self::Index);
+    fn invariants(&self) -> Result<(), InvariantViolation>;
+}
+
+macro_rules! graph_target {
+    ($($t:ty),*) => {
+        $(
+            impl Target for $t {
+                fn insert(&mut self, _: usize) -> Self::Index {
+                    self.add_node()
+                }
+                fn link(&mut self, f: Self::Index, t: Self::Index) {
+                    self.add_edge(f, t)
+                }
+                fn unlink(&mut self, f: Self::Index, t: Self::Index) {
+                    self.remove_edge(f, t)
+                }
+                fn delete(&mut self, n: Self::Index) {
+                    self.remove_node(n)
+                }
+                fn invariants(&self) -> Result<(), InvariantViolation> {
+                    self.validate()
+                }
+            }
+        )*
+    };
+}
+
+graph_target!(
+    AdjListGraph,
+    AdjVecGraph,
+    ConcurrentAdjListGraph,
+    IncMatrixGraph,
+    MultiGraph,
+    UndirectedWeightedGraph<u32>
+);
+
+impl Target for TypedGraph<AdjListGraph, u8> {
+    fn insert(&mut self, serial: usize) -> Self::Index {
+        // every third node is untyped, to mix both kinds
+        match serial % 3 {
+            0 => self.add_node(),
+            tag => self.add_typed_node(tag as u8),
+        }
+    }
+    fn link(&mut self, f: Self::Index, t: Self::Index) {
+        self.add_edge(f, t)
+    }
+    fn unlink(&mut self, f: Self::Index, t: Self::Index) {
+        self.remove_edge(f, t)
+    }
+    fn delete(&mut self, n: Self::Index) {
+        self.remove_node(n)
+    }
+    fn invariants(&self) -> Result<(), InvariantViolation> {
+        self.validate().and_then(|_| self.inner().validate())
+    }
+}
+
+impl Target for KeyedGraph<usize, ()> {
+    fn insert(&mut self, serial: usize) -> Self::Index {
+        self.insert_node(serial, ())
+    }
+    fn link(&mut self, f: Self::Index, t: Self::Index) {
+        self.add_edge(f, t)
+    }
+    fn unlink(&mut self, f: Self::Index, t: Self::Index) {
+        self.remove_edge(f, t)
+    }
+    fn delete(&mut self, n: Self::Index) {
+        self.remove_node(n);
+    }
+    fn invariants(&self) -> Result<(), InvariantViolation> {
+        self.validate()
+    }
+}
+
+/**
+ * Replaces the graph with the version returned by `op`, checking that the
+ * previous version is left untouched.
+ */
+fn advance(graph: &mut PersistentGraph, op: impl FnOnce(&PersistentGraph) -> PersistentGraph) {
+    let before: Vec<_> = graph.iter_edges().collect();
+    let next = op(graph);
+    let after: Vec<_> = graph.iter_edges().collect();
+    assert_eq!(before, after, "an older version was changed");
+    *graph = next;
+}
+
+impl Target for PersistentGraph {
+    fn insert(&mut self, _: usize) -> Self::Index {
+        let (next, n) = self.add_node();
+        *self = next;
+        n
+    }
+    fn link(&mut self, f: Self::Index, t: Self::Index) {
+        advance(self, |g| g.add_edge(f, t))
+    }
+    fn unlink(&mut self, f: Self::Index, t: Self::Index) {
+        advance(self, |g| g.remove_edge(f, t))
+    }
+    fn delete(&mut self, n: Self::Index) {
+        advance(self, |g| g.remove_node(n))
+    }
+    fn invariants(&self) -> Result<(), InvariantViolation> {
+        self.validate()
+    }
+}
+
+fn apply<G: Target>(graph: &mut G, nodes: &mut Vec<G::Index>, op: &Op)
+where
+    G::Index: Copy,
+{
+    let pick = |nodes: &[G::Index], i: u8| nodes.get(i as usize % nodes.len().max(1)).copied();
+    match *op {
+        Op::AddNode => nodes.push(graph.insert(nodes.len())),
+        Op::AddEdge(f, t) => {
+            if let (Some(f), Some(t)) = (pick(nodes, f), pick(nodes, t)) {
+                // edges to removed nodes are not part of the contract
+                if graph.iter_adj(f).is_some() && graph.iter_adj(t).is_some() {
+                    graph.link(f, t);
+                }
+            }
+        }
+        Op::RemoveNode(n) => {
+            if let Some(n) = pick(nodes, n) {
+                graph.delete(n);
+            }
+        }
+        Op::RemoveEdge(f, t) => {
+            if let (Some(f), Some(t)) = (pick(nodes, f), pick(nodes, t)) {
+                graph.unlink(f, t);
+            }
+        }
+    }
+}
+
+/**
+ * Shape of the edges of a fuzzed graph.
+ */
+#[derive(Clone, Copy, PartialEq)]
+enum Edges {
+    Directed,
+    Undirected,
+    /**
+     * Directed, with parallel edges listed once per edge.
+     */
+    Parallel,
+}
+
+fn check<G: GraphRead>(graph: &G, shape: Edges)
+where
+    G::Index: Copy + Hash + Eq + Debug,
+{
+    let nodes: HashSet<_> = graph.iter_nodes().collect();
+    assert_eq!(nodes.len(), graph.node_count(), "node_count is stale");
+
+    let edges: Vec<_> = graph.iter_edges().collect();
+    assert_eq!(edges.len(), graph.edge_count(), "edge_count is stale");
+
+    let mut adjacency = HashSet::new();
+    let mut listed = 0;
+    for &n in &nodes {
+        for a in graph.iter_adj(n).expect("live nodes have an adjacency") {
+            assert!(
+                nodes.contains(&a),
+                "{:?} is adjacent to removed node {:?}",
+                n,
+                a
+            );
+            assert!(
+                graph.has_edge(n, a),
+                "has_edge({:?}, {:?}) disagrees with iter_adj",
+                n,
+                a
+            );
+            assert!(
+                adjacency.insert((n, a)) || shape == Edges::Parallel,
+                "{:?} is adjacent to {:?} twice",
+                n,
+                a
+            );
+            listed += 1;
+            if shape == Edges::Undirected {
+                assert!(
+                    graph.has_edge(a, n),
+                    "edge {:?} - {:?} is not symmetric",
+                    n,
+                    a
+                );
+            }
+        }
+    }
+    if shape != Edges::Undirected {
+        assert_eq!(listed, edges.len(), "iter_edges disagrees with iter_adj");
+    }
+    for &(f, t) in &edges {
+        assert!(
+            adjacency.contains(&(f, t)),
+            "edge {:?} -> {:?} is not adjacent",
+            f,
+            t
+        );
+    }
+
+    let reversed = Reversed::new(graph);
+    for &(f, t) in &edges {
+        assert!(reversed.has_edge(t, f));
+        assert!(
+            reversed.iter_adj(t).unwrap().any(|a| a == f),
+            "reverse index misses {:?} -> {:?}",
+            f,
+            t
+        );
+    }
+}
+
+fn run<G: Target>(mut graph: G, ops: &[Op], shape: Edges)
+where
+    G::Index: Copy + Hash + Eq + Debug,
+{
+    let mut nodes = Vec::new();
+    for op in ops {
+        apply(&mut graph, &mut nodes, op);
+        check(&graph, shape);
+        if let Err(e) = graph.invariants() {
+            panic!("{} after {:?}", e, op);
+        }
+    }
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    run(AdjListGraph::new(), &ops, Edges::Directed);
+    run(AdjVecGraph::new(), &ops, Edges::Directed);
+    run(ConcurrentAdjListGraph::new(), &ops, Edges::Directed);
+    run(IncMatrixGraph::new(), &ops, Edges::Directed);
+    run(UndirectedWeightedGraph::<u32>::new(), &ops, Edges::Undirected);
+    run(MultiGraph::new(), &ops, Edges::Parallel);
+    run(TypedGraph::new(AdjListGraph::new()), &ops, Edges::Directed);
+    run(KeyedGraph::new(), &ops, Edges::Directed);
+    run(PersistentGraph::new(), &ops, Edges::Directed);
+});
@@ -1,5 +1,190 @@
-use crate::graph::Graph;
+use crate::graph::ImplicitGraph;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::{ControlFlow, Deref},
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
-pub trait Algorithm<G: Graph> {
-    fn run(&self, from: G::Index, to: G::Index) -> Option<Vec<G::Index>>;
+/**
+ * Hook invoked by an algorithm for each node it settles.
+ *
+ * Returning [`ControlFlow::Break`] aborts the search.
+ */
+pub type Control<'c, I> = dyn FnMut(&I) -> ControlFlow<()> + 'c;
+
+/**
+ * Graph searched by an algorithm, either borrowed or shared with the algorithm.
+ */
+pub(crate) enum GraphHandle<'a, G> {
+    Borrowed(&'a G),
+    Shared(Rc<G>),
+}
+
+impl<G> Deref for GraphHandle<'_, G> {
+    type Target = G;
+
+    fn deref(&self) -> &G {
+        match self {
+            GraphHandle::Borrowed(g) => g,
+            GraphHandle::Shared(g) => g,
+        }
+    }
+}
+
+/**
+ * Error returned when a search is aborted by its [`Control`] hook.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "search cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/**
+ * Limits on the work a search may perform, see [`Algorithm::run_with_budget`].
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Budget {
+    /**
+     * Maximum number of nodes settled before giving up.
+     */
+    pub max_expansions: Option<usize>,
+    /**
+     * Maximum wall-clock time before giving up.
+     */
+    pub max_duration: Option<Duration>,
+}
+
+impl Budget {
+    /**
+     * Creates a budget limited to `max_expansions` settled nodes.
+     */
+    pub fn expansions(max_expansions: usize) -> Self {
+        Budget {
+            max_expansions: Some(max_expansions),
+            max_duration: None,
+        }
+    }
+
+    /**
+     * Creates a budget limited to `max_duration` of wall-clock time.
+     */
+    pub fn duration(max_duration: Duration) -> Self {
+        Budget {
+            max_expansions: None,
+            max_duration: Some(max_duration),
+        }
+    }
+
+    /**
+     * Returns a [`Control`] hook breaking once the budget is exhausted, starting the clock now.
+     */
+    pub fn start<I>(self) -> impl FnMut(&I) -> ControlFlow<()> {
+        // the clock is only read for time limits, since wasm32-unknown-unknown has none
+        let start = self.max_duration.map(|d| (Instant::now(), d));
+        let mut expansions = 0;
+
+        move |_| {
+            expansions += 1;
+            let over_expansions = self.max_expansions.is_some_and(|m| expansions > m);
+            let over_time = start.is_some_and(|(start, d)| start.elapsed() > d);
+            if over_expansions || over_time {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+}
+
+/**
+ * Result of [`Algorithm::run_with_budget`].
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct BudgetedPath<I> {
+    /**
+     * The path to the target if found, otherwise the best partial path
+     * found before the budget ran out (if the algorithm can provide one).
+     */
+    pub path: Option<Vec<I>>,
+    /**
+     * `true` if the search stopped because the budget ran out.
+     */
+    pub exhausted: bool,
+}
+
+/**
+ * A path found by a search, along with its cost.
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CostedPath<I, W> {
+    /**
+     * The nodes of the path, from the source to the target.
+     */
+    pub path: Vec<I>,
+    /**
+     * The cost of the path, as minimized by the search.
+     */
+    pub cost: W,
+}
+
+impl<I, W> CostedPath<I, W> {
+    /**
+     * Returns the path, discarding its cost.
+     */
+    pub fn path_only(self) -> Vec<I> {
+        self.path
+    }
+}
+
+pub trait Algorithm<G: ImplicitGraph> {
+    /**
+     * Searches a path between `from` and `to`.
+     */
+    fn run(&self, from: G::Index, to: G::Index) -> Option<Vec<G::Index>> {
+        self.run_with_control(from, to, &mut |_| ControlFlow::Continue(()))
+            .unwrap_or_default()
+    }
+
+    /**
+     * Searches a path between `from` and `to`, calling `control` for each settled node
+     * so that the caller can track progress or cancel the search.
+     */
+    fn run_with_control(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        control: &mut Control<G::Index>,
+    ) -> Result<Option<Vec<G::Index>>, Cancelled>;
+
+    /**
+     * Searches a path between `from` and `to` within the given budget.
+     *
+     * By default no partial path is returned when the budget runs out;
+     * algorithms able to provide one override this method.
+     */
+    fn run_with_budget(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        budget: Budget,
+    ) -> BudgetedPath<G::Index> {
+        match self.run_with_control(from, to, &mut budget.start()) {
+            Ok(path) => BudgetedPath {
+                path,
+                exhausted: false,
+            },
+            Err(Cancelled) => BudgetedPath {
+                path: None,
+                exhausted: true,
+            },
+        }
+    }
 }
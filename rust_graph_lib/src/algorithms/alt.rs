@@ -0,0 +1,242 @@
+use super::{DenseIndex, Measure};
+use crate::{
+    graph::{GraphRead, WeightedGraph},
+    views::Reversed,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Sub,
+};
+
+fn distances_from<G, W, F>(graph: &G, source: G::Index, weight: F) -> HashMap<G::Index, W>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    W: Measure,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let mut dist = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    dist.insert(source, W::zero());
+    heap.push(Reverse((W::zero(), source)));
+
+    while let Some(Reverse((d, n))) = heap.pop() {
+        if dist[&n] < d {
+            continue;
+        }
+        for adj in graph.iter_adj(n).into_iter().flatten() {
+            let alt = d.combine(weight(n, adj));
+            if dist.get(&adj).is_none_or(|&old| alt < old) {
+                dist.insert(adj, alt);
+                heap.push(Reverse((alt, adj)));
+            }
+        }
+    }
+    dist
+}
+
+fn difference<W: Measure + Sub<Output = W>>(a: W, b: W) -> W {
+    if a > b {
+        a - b
+    } else {
+        W::zero()
+    }
+}
+
+/**
+ * Landmark distances for the ALT (A*, landmarks, triangle inequality) heuristic.
+ *
+ * For each landmark `l` the distances from and to every node are precomputed, so that by
+ * the triangle inequality `d(n, t) >= d(l, t) - d(l, n)` and `d(n, t) >= d(n, l) - d(t, l)`.
+ * [`Landmarks::heuristic`] is an admissible and consistent heuristic for
+ * [`AStar`](super::AStar), usually much tighter than geometric estimates on road-like graphs.
+ */
+pub struct Landmarks<I, W = u32> {
+    landmarks: Vec<I>,
+    from_landmark: Vec<HashMap<I, W>>,
+    to_landmark: Vec<HashMap<I, W>>,
+}
+
+impl<I: Ord + Hash + Copy> Landmarks<I> {
+    /**
+     * Selects `count` landmarks of a graph where each edge costs 1.
+     */
+    pub fn select<G: GraphRead<Index = I>>(graph: &G, count: usize) -> Self {
+        Self::select_with_weights(graph, count, |_, _| 1)
+    }
+}
+
+impl<I: Ord + Hash + Copy, W: Measure + Sub<Output = W>> Landmarks<I, W> {
+    /**
+     * Selects `count` landmarks of a graph, using the weights stored in the graph.
+     */
+    pub fn select_weighted<G>(graph: &G, count: usize) -> Self
+    where
+        G: WeightedGraph<Index = I, Weight = W>,
+    {
+        Self::select_with_weights(graph, count, |f, t| {
+            graph
+                .edge_weight(f, t)
+                .expect("adjacent nodes are joined by an edge")
+        })
+    }
+
+    /**
+     * Selects `count` landmarks of a graph where the edge `(f, t)` costs `weight(f, t)`.
+     *
+     * Landmarks are picked greedily, each being the node farthest from the ones already
+     * picked (unreachable nodes first), starting from the smallest node.
+     * Preprocessing runs two full Dijkstra searches per landmark.
+     */
+    pub fn select_with_weights<G, F>(graph: &G, count: usize, weight: F) -> Self
+    where
+        G: GraphRead<Index = I>,
+        F: Fn(I, I) -> W,
+    {
+        let reversed = Reversed::new(graph);
+        let mut result = Landmarks {
+            landmarks: Vec::new(),
+            from_landmark: Vec::new(),
+            to_landmark: Vec::new(),
+        };
+
+        let index = DenseIndex::new(graph);
+        let nodes = index.nodes();
+        let mut next = nodes.first().copied();
+
+        while let Some(l) = next {
+            if result.landmarks.len() == count {
+                break;
+            }
+            result.landmarks.push(l);
+            result.from_landmark.push(distances_from(graph, l, &weight));
+            result
+                .to_landmark
+                .push(distances_from(&reversed, l, |f, t| weight(t, f)));
+
+            // nodes unreachable from all the landmarks sort before any distance
+            let closest = |n: &I| {
+                result
+                    .from_landmark
+                    .iter()
+                    .filter_map(|d| d.get(n).copied())
+                    .min()
+            };
+            next = nodes
+                .iter()
+                .filter(|n| !result.landmarks.contains(n))
+                .min_by_key(|n| closest(n).map(Reverse))
+                .copied();
+        }
+        result
+    }
+
+    /**
+     * Returns the selected landmarks.
+     */
+    pub fn landmarks(&self) -> &[I] {
+        &self.landmarks
+    }
+
+    /**
+     * Returns a lower bound of the cost of the shortest path from `n` to `target`.
+     */
+    pub fn heuristic(&self, n: I, target: I) -> W {
+        let mut best = W::zero();
+        for (from, to) in self.from_landmark.iter().zip(&self.to_landmark) {
+            if let (Some(&lt), Some(&ln)) = (from.get(&target), from.get(&n)) {
+                best = best.max(difference(lt, ln));
+            }
+            if let (Some(&nl), Some(&tl)) = (to.get(&n), to.get(&target)) {
+                best = best.max(difference(nl, tl));
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{AStar, Algorithm, Dijkstra},
+        graph::Graph,
+        impls::adj_list::AdjListGraph,
+    };
+    use std::ops::ControlFlow;
+
+    fn grid(side: usize) -> (AdjListGraph, Vec<<AdjListGraph as GraphRead>::Index>) {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..side * side).map(|_| g.add_node()).collect();
+        for r in 0..side {
+            for c in 0..side {
+                let n = ids[r * side + c];
+                if c + 1 < side {
+                    g.add_edge(n, ids[r * side + c + 1]);
+                    g.add_edge(ids[r * side + c + 1], n);
+                }
+                if r + 1 < side {
+                    g.add_edge(n, ids[(r + 1) * side + c]);
+                    g.add_edge(ids[(r + 1) * side + c], n);
+                }
+            }
+        }
+        (g, ids)
+    }
+
+    #[test]
+    fn test_landmark_selection() {
+        let (g, ids) = grid(5);
+        let landmarks = Landmarks::select(&g, 2);
+        // the second landmark is the corner opposite to the first one
+        assert_eq!(landmarks.landmarks(), &[ids[0], ids[24]]);
+        assert_eq!(landmarks.heuristic(ids[0], ids[24]), 8);
+        assert_eq!(landmarks.heuristic(ids[12], ids[12]), 0);
+    }
+
+    #[test]
+    fn test_farthest_point_selection() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..11).map(|_| g.add_node()).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1]);
+            g.add_edge(w[1], w[0]);
+        }
+        // each landmark is the node whose nearest landmark is the farthest
+        let landmarks = Landmarks::select(&g, 4);
+        assert_eq!(landmarks.landmarks(), &[ids[0], ids[10], ids[5], ids[2]]);
+
+        // nodes unreachable from every landmark come first
+        let isolated = g.add_node();
+        let landmarks = Landmarks::select(&g, 3);
+        assert_eq!(landmarks.landmarks(), &[ids[0], isolated, ids[10]]);
+    }
+
+    #[test]
+    fn test_alt_matches_dijkstra_with_fewer_expansions() {
+        let (g, ids) = grid(8);
+        let landmarks = Landmarks::select(&g, 4);
+        let alt = AStar::new(&g, |n, t| landmarks.heuristic(n, t));
+        let dijkstra = Dijkstra::new(&g);
+
+        let count = |a: &dyn Algorithm<AdjListGraph>, f, t| {
+            let mut expanded = 0;
+            let p = a
+                .run_with_control(f, t, &mut |_| {
+                    expanded += 1;
+                    ControlFlow::Continue(())
+                })
+                .unwrap();
+            (p.map(|p| p.len()), expanded)
+        };
+
+        for (f, t) in [(ids[0], ids[63]), (ids[9], ids[46]), (ids[60], ids[3])] {
+            let (alt_len, alt_expanded) = count(&alt, f, t);
+            let (len, expanded) = count(&dijkstra, f, t);
+            assert_eq!(alt_len, len);
+            assert!(alt_expanded < expanded);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::ControlFlow,
+    rc::Rc,
+};
+
+use crate::graph::{ImplicitGraph, WeightedGraph};
+
+use super::{trace::SearchStats, Algorithm, Cancelled, Control, CostedPath, GraphHandle, Measure};
+
+struct Candidate<I, W> {
+    node: I,
+    cost: W,
+    estimate: W,
+}
+
+impl<I, W: Ord> PartialEq for Candidate<I, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<I, W: Ord> Eq for Candidate<I, W> {}
+
+impl<I, W: Ord> Ord for Candidate<I, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed because BinaryHeap returns the max; ties prefer the candidate
+        // closer to the target, i.e. the one with the larger cost so far
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<I, W: Ord> PartialOrd for Candidate<I, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+type WeightFn<'a, I, W> = Box<dyn Fn(I, I) -> W + 'a>;
+type HeuristicFn<'a, I, W> = Box<dyn Fn(I, I) -> W + 'a>;
+
+/**
+ * A* shortest path search, guided by a heuristic estimating the cost from a node to the target.
+ *
+ * The heuristic is called as `heuristic(node, target)` and must never overestimate the
+ * actual cost for the returned path to be the shortest. With a zero heuristic the search
+ * behaves like [`Dijkstra`](super::Dijkstra).
+ */
+pub struct AStar<'a, G: ImplicitGraph, W = u32> {
+    graph: GraphHandle<'a, G>,
+    weight: WeightFn<'a, G::Index, W>,
+    heuristic: HeuristicFn<'a, G::Index, W>,
+}
+
+impl<'a, G: ImplicitGraph> AStar<'a, G> {
+    /**
+     * Creates a search where each edge costs 1.
+     */
+    pub fn new<H: Fn(G::Index, G::Index) -> u32 + 'a>(graph: &'a G, heuristic: H) -> Self {
+        Self::with_weights(graph, |_, _| 1, heuristic)
+    }
+
+    /**
+     * Creates a search where each edge costs 1, owning a shared reference to the graph
+     * so that it can outlive the scope it was created in.
+     */
+    pub fn new_shared<H: Fn(G::Index, G::Index) -> u32 + 'a>(graph: Rc<G>, heuristic: H) -> Self {
+        Self::with_weights_shared(graph, |_, _| 1, heuristic)
+    }
+}
+
+impl<'a, G: WeightedGraph> AStar<'a, G, G::Weight>
+where
+    G::Weight: Measure,
+{
+    /**
+     * Creates a search minimizing the weights stored in the graph.
+     */
+    pub fn weighted<H: Fn(G::Index, G::Index) -> G::Weight + 'a>(
+        graph: &'a G,
+        heuristic: H,
+    ) -> Self {
+        Self::with_weights(
+            graph,
+            |f, t| {
+                graph
+                    .edge_weight(f, t)
+                    .expect("adjacent nodes are joined by an edge")
+            },
+            heuristic,
+        )
+    }
+}
+
+impl<'a, G: ImplicitGraph, W: Measure> AStar<'a, G, W> {
+    /**
+     * Creates a search where the edge `(f, t)` costs `weight(f, t)`.
+     */
+    pub fn with_weights<F, H>(graph: &'a G, weight: F, heuristic: H) -> Self
+    where
+        F: Fn(G::Index, G::Index) -> W + 'a,
+        H: Fn(G::Index, G::Index) -> W + 'a,
+    {
+        AStar {
+            graph: GraphHandle::Borrowed(graph),
+            weight: Box::new(weight),
+            heuristic: Box::new(heuristic),
+        }
+    }
+
+    /**
+     * Like [`AStar::with_weights`], owning a shared reference to the graph.
+     */
+    pub fn with_weights_shared<F, H>(graph: Rc<G>, weight: F, heuristic: H) -> Self
+    where
+        F: Fn(G::Index, G::Index) -> W + 'a,
+        H: Fn(G::Index, G::Index) -> W + 'a,
+    {
+        AStar {
+            graph: GraphHandle::Shared(graph),
+            weight: Box::new(weight),
+            heuristic: Box::new(heuristic),
+        }
+    }
+}
+
+impl<'a, I: Hash + Eq + Copy, G: ImplicitGraph<Index = I>, W: Measure> AStar<'a, G, W> {
+    /**
+     * Searches a path between `from` and `to`, returning it along with its cost.
+     */
+    pub fn run_with_cost(&self, from: I, to: I) -> Option<CostedPath<I, W>> {
+        self.search(from, to, &mut |_| ControlFlow::Continue(()))
+            .unwrap_or_default()
+    }
+
+    fn search(
+        &self,
+        from: I,
+        to: I,
+        control: &mut Control<I>,
+    ) -> Result<Option<CostedPath<I, W>>, Cancelled> {
+        // a node is expanded again if reached later with a lower cost,
+        // which only happens with inconsistent heuristics
+        let mut costs = HashMap::new();
+        let mut preds = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        let mut stats = SearchStats::new("astar");
+
+        costs.insert(from, W::zero());
+        heap.push(Candidate {
+            node: from,
+            cost: W::zero(),
+            estimate: (self.heuristic)(from, to),
+        });
+
+        while let Some(Candidate { node, cost, .. }) = heap.pop() {
+            if costs[&node] < cost {
+                continue;
+            }
+            if control(&node).is_break() {
+                return Err(Cancelled);
+            }
+            if node == to {
+                break;
+            }
+            stats.expand(heap.len());
+
+            for adj in self.graph.successors(node) {
+                let alt = cost.combine((self.weight)(node, adj));
+                if costs.get(&adj).is_none_or(|&c| alt < c) {
+                    stats.relax();
+                    costs.insert(adj, alt);
+                    preds.insert(adj, node);
+                    heap.push(Candidate {
+                        node: adj,
+                        cost: alt,
+                        estimate: alt.combine((self.heuristic)(adj, to)),
+                    });
+                }
+            }
+        }
+
+        // the source is reached at no cost, so the path from a node to itself is that node
+        if !costs.contains_key(&to) {
+            return Ok(None);
+        }
+        let mut path = vec![to];
+        let mut cur = to;
+        while cur != from {
+            cur = preds[&cur];
+            path.push(cur);
+        }
+        path.reverse();
+        Ok(Some(CostedPath {
+            path,
+            cost: costs[&to],
+        }))
+    }
+}
+
+impl<'a, I: Hash + Eq + Copy, G: ImplicitGraph<Index = I>, W: Measure> Algorithm<G>
+    for AStar<'a, G, W>
+{
+    fn run_with_control(
+        &self,
+        from: I,
+        to: I,
+        control: &mut Control<I>,
+    ) -> Result<Option<Vec<I>>, Cancelled> {
+        Ok(self.search(from, to, control)?.map(CostedPath::path_only))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::test_utils::slice_equal, graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn guides_search_towards_target() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..6).map(|_| g.add_node()).collect();
+        // a chain 0 -> 1 -> 2 -> 3 with a dead-end branch 0 -> 4 -> 5
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[2], ids[3]);
+        g.add_edge(ids[0], ids[4]);
+        g.add_edge(ids[4], ids[5]);
+
+        let pos = |n| ids.iter().position(|&i| i == n).unwrap() as u32;
+        // the branch cannot reach the target, so any estimate for it is admissible
+        let remaining = |n, t| match (pos(n), pos(t)) {
+            (n, t) if n <= t => t - n,
+            _ => 10,
+        };
+
+        let mut expanded = Vec::new();
+        let p = AStar::new(&g, remaining).run_with_control(ids[0], ids[3], &mut |n| {
+            expanded.push(*n);
+            ControlFlow::Continue(())
+        });
+        assert!(slice_equal(&p.unwrap().unwrap(), &ids[..4]));
+        assert_eq!(expanded, ids[..4]);
+    }
+
+    #[test]
+    fn zero_heuristic_finds_shortest_path() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[2], ids[3]);
+        g.add_edge(ids[0], ids[2]);
+
+        let p = AStar::new(&g, |_, _| 0).run(ids[0], ids[3]);
+        assert!(slice_equal(&p.unwrap(), &[ids[0], ids[2], ids[3]]));
+        assert_eq!(
+            AStar::new(&g, |_, _| 0).run(ids[3], ids[3]),
+            Some(vec![ids[3]])
+        );
+        assert!(AStar::new(&g, |_, _| 0).run(ids[3], ids[0]).is_none());
+
+        let p = AStar::new(&g, |_, _| 0)
+            .run_with_cost(ids[0], ids[3])
+            .unwrap();
+        assert_eq!(p.cost, 2);
+    }
+}
@@ -0,0 +1,751 @@
+use super::DenseIndex;
+use crate::{graph::GraphRead, utils::Rng};
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Shortest paths from one source by position, from the breadth-first phase of
+ * Brandes' algorithm.
+ */
+struct PathCounts {
+    sigma: Vec<f64>,
+    dist: Vec<usize>,
+    preds: Vec<Vec<usize>>,
+    order: Vec<usize>,
+}
+
+impl PathCounts {
+    fn new(adj: &[Vec<usize>], s: usize) -> Self {
+        let n = adj.len();
+        let mut sigma = vec![0.0; n];
+        let mut dist = vec![usize::MAX; n];
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut order = Vec::with_capacity(n);
+
+        sigma[s] = 1.0;
+        dist[s] = 0;
+        order.push(s);
+        let mut head = 0;
+        while head < order.len() {
+            let v = order[head];
+            head += 1;
+            for &w in &adj[v] {
+                if dist[w] == usize::MAX {
+                    dist[w] = dist[v] + 1;
+                    order.push(w);
+                }
+                if dist[w] == dist[v] + 1 {
+                    sigma[w] += sigma[v];
+                    preds[w].push(v);
+                }
+            }
+        }
+        PathCounts {
+            sigma,
+            dist,
+            preds,
+            order,
+        }
+    }
+
+    /**
+     * Returns the dependency of the source on each node, accumulated from the
+     * farthest nodes back.
+     */
+    fn dependencies(&self) -> Vec<f64> {
+        let mut dep = vec![0.0; self.sigma.len()];
+        for &w in self.order.iter().rev() {
+            for &v in &self.preds[w] {
+                dep[v] += self.sigma[v] / self.sigma[w] * (1.0 + dep[w]);
+            }
+        }
+        dep
+    }
+
+    /**
+     * Returns the dependency of the source on each edge `(v, w)` of the shortest
+     * paths, given the node `dependencies`.
+     */
+    fn edge_dependencies<'a>(
+        &'a self,
+        dep: &'a [f64],
+    ) -> impl Iterator<Item = (usize, usize, f64)> + 'a {
+        self.order.iter().flat_map(move |&w| {
+            self.preds[w]
+                .iter()
+                .map(move |&v| (v, w, self.sigma[v] / self.sigma[w] * (1.0 + dep[w])))
+        })
+    }
+}
+
+/**
+ * Adds to `delta` the dependency of `s` on every other node, with the breadth-first
+ * accumulation of Brandes' algorithm.
+ */
+fn accumulate(adj: &[Vec<usize>], s: usize, delta: &mut [f64]) {
+    let counts = PathCounts::new(adj, s);
+    let dep = counts.dependencies();
+    for &w in &counts.order[1..] {
+        delta[w] += dep[w];
+    }
+}
+
+/**
+ * Counts of the shortest paths leaving a source node, returned by
+ * [`shortest_path_counts`], from which custom betweenness-like measures can be built.
+ *
+ * Edges are directed and unweighted. Counts are floating point, since they grow
+ * exponentially with the length of the paths in some graphs.
+ */
+pub struct ShortestPathCounts<I> {
+    index: DenseIndex<I>,
+    counts: PathCounts,
+}
+
+impl<I: Hash + Eq + Copy> ShortestPathCounts<I> {
+    /**
+     * Returns the source of the paths.
+     */
+    pub fn source(&self) -> I {
+        self.index.node(self.counts.order[0])
+    }
+
+    /**
+     * Returns the number of edges of the shortest paths from the source to `n`, if
+     * `n` is reachable.
+     */
+    pub fn distance(&self, n: I) -> Option<usize> {
+        let i = self.index.position(n)?;
+        Some(self.counts.dist[i]).filter(|&d| d != usize::MAX)
+    }
+
+    /**
+     * Returns the number of shortest paths from the source to `n`, called sigma in
+     * Brandes' algorithm; it is zero if `n` is not reachable.
+     */
+    pub fn sigma(&self, n: I) -> f64 {
+        self.index.position(n).map_or(0.0, |i| self.counts.sigma[i])
+    }
+
+    /**
+     * Returns the nodes preceding `n` on its shortest paths from the source.
+     */
+    pub fn predecessors(&self, n: I) -> Vec<I> {
+        self.index.position(n).map_or_else(Vec::new, |i| {
+            self.counts.preds[i]
+                .iter()
+                .map(|&p| self.index.node(p))
+                .collect()
+        })
+    }
+
+    /**
+     * Returns the reachable nodes by increasing distance from the source, starting
+     * with the source itself.
+     */
+    pub fn order(&self) -> impl Iterator<Item = I> + '_ {
+        self.counts.order.iter().map(|&i| self.index.node(i))
+    }
+
+    /**
+     * Returns the dependency of the source on each other reachable node `v`, called
+     * delta in Brandes' algorithm: the sum over the targets `t` of the fraction of
+     * the shortest paths to `t` passing through `v`.
+     *
+     * Summing the dependencies of every source gives the [`betweenness`].
+     */
+    pub fn dependencies(&self) -> HashMap<I, f64> {
+        let dep = self.counts.dependencies();
+        self.counts.order[1..]
+            .iter()
+            .map(|&v| (self.index.node(v), dep[v]))
+            .collect()
+    }
+
+    /**
+     * Returns the dependency of the source on each edge lying on a shortest path:
+     * the sum over the targets `t` of the fraction of the shortest paths to `t`
+     * using the edge.
+     */
+    pub fn edge_dependencies(&self) -> HashMap<(I, I), f64> {
+        let dep = self.counts.dependencies();
+        self.counts
+            .edge_dependencies(&dep)
+            .map(|(v, w, share)| ((self.index.node(v), self.index.node(w)), share))
+            .collect()
+    }
+}
+
+/**
+ * Counts the shortest paths from `source` to every node, following the edges in
+ * their direction, in `O(m)` time. Returns `None` if `source` is not in the graph.
+ */
+pub fn shortest_path_counts<G>(graph: &G, source: G::Index) -> Option<ShortestPathCounts<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let s = index.position(source)?;
+    Some(ShortestPathCounts {
+        counts: PathCounts::new(&index.successors(graph), s),
+        index,
+    })
+}
+
+/**
+ * Returns the betweenness centrality of each node: the number of shortest paths
+ * between other pairs of nodes passing through it, with paths of equal length
+ * sharing one unit.
+ *
+ * Edges are directed and unweighted and scores are not normalized. Takes `O(nm)` time;
+ * see [`approximate_betweenness`] for large graphs.
+ */
+pub fn betweenness<G>(graph: &G) -> HashMap<G::Index, f64>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let adj = index.successors(graph);
+    let mut scores = vec![0.0; index.len()];
+    for s in 0..index.len() {
+        accumulate(&adj, s, &mut scores);
+    }
+    index.into_map(scores)
+}
+
+/**
+ * Returns the betweenness of each edge `(v, w)` of the adjacency lists by position.
+ */
+pub(super) fn edge_betweenness_by_position(adj: &[Vec<usize>]) -> HashMap<(usize, usize), f64> {
+    let mut scores: HashMap<_, _> = adj
+        .iter()
+        .enumerate()
+        .flat_map(|(v, adj)| adj.iter().map(move |&w| ((v, w), 0.0)))
+        .collect();
+    for s in 0..adj.len() {
+        let counts = PathCounts::new(adj, s);
+        let dep = counts.dependencies();
+        for (v, w, share) in counts.edge_dependencies(&dep) {
+            *scores.get_mut(&(v, w)).unwrap() += share;
+        }
+    }
+    scores
+}
+
+/**
+ * Returns the betweenness centrality of each edge: the number of shortest paths
+ * between pairs of nodes using it, with paths of equal length sharing one unit.
+ *
+ * Like [`betweenness`], edges are directed and unweighted and the scores are not
+ * normalized; it takes `O(nm)` time.
+ */
+pub fn edge_betweenness<G>(graph: &G) -> HashMap<(G::Index, G::Index), f64>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    edge_betweenness_by_position(&index.successors(graph))
+        .into_iter()
+        .map(|((v, w), score)| ((index.node(v), index.node(w)), score))
+        .collect()
+}
+
+/**
+ * Sampled estimate of the betweenness centrality, returned by [`approximate_betweenness`].
+ */
+pub struct ApproxBetweenness<I> {
+    /**
+     * Unbiased estimate of the score of each node.
+     */
+    pub scores: HashMap<I, f64>,
+    /**
+     * Standard error of each estimate; the exact score is within two standard
+     * errors of the estimate about 95% of the time.
+     */
+    pub std_error: HashMap<I, f64>,
+    /**
+     * Number of source pivots actually used.
+     */
+    pub pivots: usize,
+}
+
+/**
+ * Estimates [`betweenness`] from the shortest paths leaving `pivots` source nodes
+ * sampled uniformly without replacement, taking `O(pivots * m)` time.
+ *
+ * The dependencies of the pivots are scaled up to the whole graph; the sampling
+ * is reproducible for a given `seed`. With `pivots` at least the number of nodes,
+ * the result is exact and all the errors are 0.
+ */
+pub fn approximate_betweenness<G>(
+    graph: &G,
+    pivots: usize,
+    seed: u64,
+) -> ApproxBetweenness<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let adj = index.successors(graph);
+    let n = index.len();
+    let k = pivots.min(n);
+
+    let mut sources: Vec<_> = (0..n).collect();
+    Rng::new(seed).partial_shuffle(&mut sources, k);
+
+    let mut sum = vec![0.0; n];
+    let mut sum_sq = vec![0.0; n];
+    let mut delta = vec![0.0; n];
+    for &s in &sources[..k] {
+        delta.fill(0.0);
+        accumulate(&adj, s, &mut delta);
+        for v in 0..n {
+            sum[v] += delta[v];
+            sum_sq[v] += delta[v] * delta[v];
+        }
+    }
+
+    let scale = if k == 0 { 0.0 } else { n as f64 / k as f64 };
+    let scores = sum.iter().map(|s| s * scale).collect();
+    let errors: Vec<_> = sum
+        .iter()
+        .zip(&sum_sq)
+        .map(|(s, sq)| {
+            if k < 2 || k == n {
+                return 0.0;
+            }
+            // sample variance of the per-pivot contributions, with the finite population correction
+            let k = k as f64;
+            let variance = (sq - s * s / k) / (k - 1.0);
+            let correction = 1.0 - k / n as f64;
+            n as f64 * (variance.max(0.0) / k * correction).sqrt()
+        })
+        .collect();
+
+    ApproxBetweenness {
+        std_error: index.nodes().iter().copied().zip(errors).collect(),
+        scores: index.into_map(scores),
+        pivots: k,
+    }
+}
+
+type EdgeWeightFn<'a, I> = Box<dyn Fn(I, I) -> f64 + 'a>;
+
+/**
+ * PageRank of the nodes of a graph, configured with a builder.
+ *
+ * A random surfer follows an edge leaving the current node with probability `damping`,
+ * choosing it in proportion to its weight, and otherwise restarts from a node drawn
+ * from the personalization distribution, which is also used when the current node has
+ * no edges leaving it. The scores are the probabilities of being at each node in the
+ * long run, and they sum to 1.
+ *
+ * ```
+ * use rust_graph_lib::{algorithms::centrality::PageRank, graph::Graph, impls::adj_list::AdjListGraph};
+ * use std::collections::HashMap;
+ *
+ * let mut g = AdjListGraph::new();
+ * let [a, b, c] = [(); 3].map(|_| g.add_node());
+ * g.add_edge(a, b);
+ * g.add_edge(b, c);
+ * g.add_edge(c, a);
+ *
+ * let scores = PageRank::new()
+ *     .personalization(HashMap::from([(a, 1.0)]))
+ *     .run(&g);
+ * assert!(scores[&a] > scores[&b] && scores[&b] > scores[&c]);
+ * ```
+ */
+pub struct PageRank<'a, I> {
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    weight: Option<EdgeWeightFn<'a, I>>,
+    personalization: Option<HashMap<I, f64>>,
+}
+
+impl<'a, I: Hash + Ord + Copy> PageRank<'a, I> {
+    /**
+     * Creates the computation with a damping of 0.85, a tolerance of `1e-6`, at most
+     * 100 iterations, unit weights and uniform restarts.
+     */
+    pub fn new() -> Self {
+        PageRank {
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+            weight: None,
+            personalization: None,
+        }
+    }
+
+    /**
+     * Sets the probability of following an edge rather than restarting.
+     */
+    pub fn damping(mut self, damping: f64) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /**
+     * Stops iterating once the scores change by less than `tolerance` in total.
+     */
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /**
+     * Stops iterating after `max_iterations` even if the scores have not converged.
+     */
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /**
+     * Gives the edge `(f, t)` the weight `weight(f, t)`; edges weighing zero or less
+     * are never followed.
+     */
+    pub fn weights<F: Fn(I, I) -> f64 + 'a>(mut self, weight: F) -> Self {
+        self.weight = Some(Box::new(weight));
+        self
+    }
+
+    /**
+     * Restarts from each node with a probability proportional to its value in
+     * `personalization`, nodes missing from it having none. Negative values count as
+     * zero, and restarts are uniform if no node has a positive value.
+     */
+    pub fn personalization(mut self, personalization: HashMap<I, f64>) -> Self {
+        self.personalization = Some(personalization);
+        self
+    }
+
+    /**
+     * Computes the scores by power iteration, in `O(m)` time per iteration.
+     */
+    pub fn run<G: GraphRead<Index = I>>(&self, graph: &G) -> HashMap<I, f64> {
+        let index = DenseIndex::new(graph);
+        let adj = index.successors(graph);
+        let nodes = index.nodes();
+        let n = nodes.len();
+        if n == 0 {
+            return HashMap::new();
+        }
+
+        let weights: Vec<Vec<f64>> = adj
+            .iter()
+            .enumerate()
+            .map(|(u, adj)| {
+                adj.iter()
+                    .map(|&v| match &self.weight {
+                        Some(weight) => weight(nodes[u], nodes[v]).max(0.0),
+                        None => 1.0,
+                    })
+                    .collect()
+            })
+            .collect();
+        let out: Vec<f64> = weights.iter().map(|w| w.iter().sum()).collect();
+
+        let mut restart: Vec<f64> = match &self.personalization {
+            Some(p) => nodes
+                .iter()
+                .map(|v| p.get(v).copied().unwrap_or(0.0).max(0.0))
+                .collect(),
+            None => vec![1.0; n],
+        };
+        let total: f64 = restart.iter().sum();
+        if total > 0.0 {
+            restart.iter_mut().for_each(|r| *r /= total);
+        } else {
+            restart.fill(1.0 / n as f64);
+        }
+
+        let mut scores = vec![1.0 / n as f64; n];
+        let mut next = vec![0.0; n];
+        for _ in 0..self.max_iterations {
+            let dangling: f64 = (0..n).filter(|&u| out[u] == 0.0).map(|u| scores[u]).sum();
+            for (v, r) in restart.iter().enumerate() {
+                next[v] = (1.0 - self.damping + self.damping * dangling) * r;
+            }
+            for u in (0..n).filter(|&u| out[u] > 0.0) {
+                let share = self.damping * scores[u] / out[u];
+                for (&v, w) in adj[u].iter().zip(&weights[u]) {
+                    next[v] += share * w;
+                }
+            }
+
+            let change: f64 = scores.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+            std::mem::swap(&mut scores, &mut next);
+            if change < self.tolerance {
+                break;
+            }
+        }
+        index.into_map(scores)
+    }
+}
+
+impl<I: Hash + Ord + Copy> Default for PageRank<'_, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+ * Returns the unweighted [`PageRank`] of each node with the default parameters.
+ */
+pub fn pagerank<G>(graph: &G) -> HashMap<G::Index, f64>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    PageRank::new().run(graph)
+}
+
+/**
+ * Hub and authority scores of the nodes, returned by [`hits`].
+ */
+pub struct HitsScores<I> {
+    /**
+     * How much each node points to good authorities; the scores sum to 1.
+     */
+    pub hubs: HashMap<I, f64>,
+    /**
+     * How much each node is pointed to by good hubs; the scores sum to 1.
+     */
+    pub authorities: HashMap<I, f64>,
+}
+
+/**
+ * Computes the hub and authority scores of Kleinberg's HITS algorithm, where the
+ * authority of a node is the sum of the hub scores of the nodes with an edge to it
+ * and the hub score of a node the sum of the authorities it has edges to.
+ *
+ * Iterates, in `O(m)` time per iteration, until the hub scores change by less than
+ * `tolerance` in total or after `max_iterations`. All the scores are zero in a
+ * graph without edges.
+ */
+pub fn hits<G>(graph: &G, tolerance: f64, max_iterations: usize) -> HitsScores<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let adj = index.successors(graph);
+    let n = index.len();
+    let normalize = |scores: &mut Vec<f64>| {
+        let total: f64 = scores.iter().sum();
+        if total > 0.0 {
+            scores.iter_mut().for_each(|s| *s /= total);
+        }
+    };
+
+    let mut hubs = vec![1.0 / n as f64; n];
+    let mut authorities = vec![0.0; n];
+    for _ in 0..max_iterations {
+        authorities.fill(0.0);
+        for (u, adj) in adj.iter().enumerate() {
+            for &v in adj {
+                authorities[v] += hubs[u];
+            }
+        }
+        normalize(&mut authorities);
+
+        let mut next: Vec<f64> = adj
+            .iter()
+            .map(|adj| adj.iter().map(|&v| authorities[v]).sum())
+            .collect();
+        normalize(&mut next);
+        let change: f64 = hubs.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        hubs = next;
+        if change < tolerance {
+            break;
+        }
+    }
+
+    HitsScores {
+        hubs: index.nodes().iter().copied().zip(hubs).collect(),
+        authorities: index.into_map(authorities),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    fn undirected_path(n: usize) -> (AdjListGraph, Vec<<AdjListGraph as GraphRead>::Index>) {
+        let mut g = AdjListGraph::new();
+        let nodes: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+        for w in nodes.windows(2) {
+            g.add_edge(w[0], w[1]);
+            g.add_edge(w[1], w[0]);
+        }
+        (g, nodes)
+    }
+
+    #[test]
+    fn test_betweenness() {
+        let (g, nodes) = undirected_path(4);
+        let scores = betweenness(&g);
+        // ordered pairs whose path crosses the node
+        assert_eq!(scores[&nodes[0]], 0.0);
+        assert_eq!(scores[&nodes[1]], 4.0);
+        assert_eq!(scores[&nodes[2]], 4.0);
+
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(a, c);
+        g.add_edge(b, d);
+        g.add_edge(c, d);
+        let scores = betweenness(&g);
+        assert_eq!(scores[&b], 0.5);
+        assert_eq!(scores[&c], 0.5);
+    }
+
+    #[test]
+    fn test_pagerank() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(b, c);
+        g.add_edge(c, a);
+        let scores = pagerank(&g);
+        assert!(scores.values().all(|s| (s - 1.0 / 3.0).abs() < 1e-6));
+
+        // b has no edges leaving it, so the surfer always restarts from a
+        g.remove_edge(b, c);
+        g.remove_edge(c, a);
+        let scores = PageRank::new()
+            .personalization(HashMap::from([(a, 2.0), (c, -1.0)]))
+            .tolerance(1e-12)
+            .max_iterations(1000)
+            .run(&g);
+        let expected_a = 0.15 / (1.0 - 0.85 * 0.85);
+        assert!((scores[&a] - expected_a).abs() < 1e-9);
+        assert!((scores[&b] - 0.85 * expected_a).abs() < 1e-9);
+        assert_eq!(scores[&c], 0.0);
+        assert!(PageRank::new().run(&AdjListGraph::new()).is_empty());
+    }
+
+    #[test]
+    fn test_weighted_pagerank() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        for (f, t) in [(a, b), (a, c), (b, a), (c, a)] {
+            g.add_edge(f, t);
+        }
+        let scores = PageRank::new()
+            .weights(|f, t| if (f, t) == (a, b) { 3.0 } else { 1.0 })
+            .damping(0.9)
+            .tolerance(1e-12)
+            .max_iterations(1000)
+            .run(&g);
+        assert!((scores.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        // b and c only differ in the share of the rank of a they get
+        let ratio = (scores[&b] - 0.1 / 3.0) / (scores[&c] - 0.1 / 3.0);
+        assert!((ratio - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hits() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| g.add_node());
+        g.add_edge(a, c);
+        g.add_edge(a, d);
+        g.add_edge(b, c);
+
+        let scores = hits(&g, 1e-12, 1000);
+        let golden = (5f64.sqrt() - 1.0) / 2.0;
+        assert!((scores.authorities[&c] - golden).abs() < 1e-9);
+        assert!((scores.authorities[&d] - (1.0 - golden)).abs() < 1e-9);
+        assert!((scores.hubs[&a] - golden).abs() < 1e-9);
+        assert!((scores.hubs[&b] - (1.0 - golden)).abs() < 1e-9);
+        assert_eq!((scores.hubs[&c], scores.authorities[&a]), (0.0, 0.0));
+
+        let mut g = AdjListGraph::new();
+        let a = g.add_node();
+        let scores = hits(&g, 1e-9, 10);
+        assert_eq!((scores.hubs[&a], scores.authorities[&a]), (0.0, 0.0));
+        assert!(hits(&AdjListGraph::new(), 1e-9, 10).hubs.is_empty());
+    }
+
+    #[test]
+    fn test_shortest_path_counts() {
+        // a diamond a -> {b, c} -> d with a tail d -> e
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| g.add_node());
+        for (f, t) in [(a, b), (a, c), (b, d), (c, d), (d, e)] {
+            g.add_edge(f, t);
+        }
+
+        let counts = shortest_path_counts(&g, a).unwrap();
+        assert_eq!(counts.source(), a);
+        assert_eq!(counts.sigma(e), 2.0);
+        assert_eq!(counts.distance(e), Some(3));
+        assert_eq!(counts.distance(a), Some(0));
+        let mut preds = counts.predecessors(d);
+        preds.sort();
+        assert_eq!(preds, vec![b, c]);
+        assert_eq!(counts.order().next(), Some(a));
+        assert_eq!(counts.order().last(), Some(e));
+
+        let dep = counts.dependencies();
+        assert_eq!((dep[&b], dep[&d], dep[&e]), (1.0, 1.0, 0.0));
+        assert!(!dep.contains_key(&a));
+        let edge_dep = counts.edge_dependencies();
+        assert_eq!(edge_dep[&(a, b)], 2.0);
+        assert_eq!(edge_dep[&(d, e)], 1.0);
+        assert_eq!(edge_dep.len(), 5);
+
+        let counts = shortest_path_counts(&g, e).unwrap();
+        assert_eq!((counts.sigma(a), counts.distance(a)), (0.0, None));
+        assert!(counts.predecessors(a).is_empty());
+        assert!(shortest_path_counts(&g, e.next()).is_none());
+    }
+
+    #[test]
+    fn test_edge_betweenness() {
+        let (g, nodes) = undirected_path(3);
+        let scores = edge_betweenness(&g);
+        assert_eq!(scores.len(), 4);
+        // a -> b carries the paths from a to b and to c
+        assert_eq!(scores[&(nodes[0], nodes[1])], 2.0);
+        assert_eq!(scores[&(nodes[2], nodes[1])], 2.0);
+
+        let mut g = AdjListGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(b, c);
+        g.add_edge(a, c);
+        let scores = edge_betweenness(&g);
+        assert_eq!(
+            (scores[&(a, b)], scores[&(b, c)], scores[&(a, c)]),
+            (1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_approximation() {
+        let (g, nodes) = undirected_path(60);
+        let exact = betweenness(&g);
+
+        let all = approximate_betweenness(&g, 1000, 7);
+        assert_eq!(all.pivots, 60);
+        assert!(nodes
+            .iter()
+            .all(|n| (all.scores[n] - exact[n]).abs() < 1e-9));
+
+        let approx = approximate_betweenness(&g, 30, 7);
+        let within = nodes
+            .iter()
+            .filter(|n| (approx.scores[n] - exact[n]).abs() <= 3.0 * approx.std_error[n] + 1e-9)
+            .count();
+        assert!(within >= 55);
+    }
+}
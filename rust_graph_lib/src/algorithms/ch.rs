@@ -0,0 +1,437 @@
+use super::{CostedPath, DenseIndex, Measure};
+use crate::{
+    graph::{GraphRead, WeightedGraph},
+    utils::IndexedBinaryHeap,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+/**
+ * Maximum number of nodes settled by each witness search during preprocessing.
+ *
+ * Stopping early only adds superfluous shortcuts, never wrong ones.
+ */
+const WITNESS_SETTLE_LIMIT: usize = 64;
+
+#[derive(Clone, Copy)]
+struct ChEdge<W> {
+    node: usize,
+    weight: W,
+    // the contracted node a shortcut bypasses
+    middle: Option<usize>,
+}
+
+type EdgeMap<W> = HashMap<usize, (W, Option<usize>)>;
+type Preds = HashMap<usize, (usize, Option<usize>)>;
+
+fn insert_min<W: Measure>(edges: &mut EdgeMap<W>, n: usize, w: W, middle: Option<usize>) {
+    if edges.get(&n).is_none_or(|&(old, _)| w < old) {
+        edges.insert(n, (w, middle));
+    }
+}
+
+struct Contraction<W> {
+    out: Vec<EdgeMap<W>>,
+    inc: Vec<EdgeMap<W>>,
+    deleted_neighbors: Vec<i64>,
+}
+
+impl<W: Measure> Contraction<W> {
+    /**
+     * Returns the distances from `source` found while looking for paths not
+     * through `avoid` costing at most `max`.
+     */
+    fn witness(&self, source: usize, avoid: usize, max: W) -> HashMap<usize, W> {
+        let mut dist = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        let mut settled = 0;
+        dist.insert(source, W::zero());
+        heap.push(Reverse((W::zero(), source)));
+
+        while let Some(Reverse((d, n))) = heap.pop() {
+            if dist[&n] < d {
+                continue;
+            }
+            if d > max || settled >= WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            settled += 1;
+
+            for (&a, &(w, _)) in &self.out[n] {
+                let alt = d.combine(w);
+                if a != avoid && dist.get(&a).is_none_or(|&old| alt < old) {
+                    dist.insert(a, alt);
+                    heap.push(Reverse((alt, a)));
+                }
+            }
+        }
+        dist
+    }
+
+    /**
+     * Returns the shortcuts `(u, x, weight)` needed to contract `v`.
+     */
+    fn shortcuts(&self, v: usize) -> Vec<(usize, usize, W)> {
+        let mut shortcuts = Vec::new();
+        for (&u, &(w1, _)) in &self.inc[v] {
+            let targets: Vec<_> = self.out[v]
+                .iter()
+                .filter(|(&x, _)| x != u)
+                .map(|(&x, &(w2, _))| (x, w1.combine(w2)))
+                .collect();
+            let max = match targets.iter().map(|&(_, w)| w).max() {
+                Some(max) => max,
+                None => continue,
+            };
+
+            let dist = self.witness(u, v, max);
+            for (x, w) in targets {
+                if dist.get(&x).is_none_or(|&d| d > w) {
+                    shortcuts.push((u, x, w));
+                }
+            }
+        }
+        shortcuts
+    }
+
+    fn priority(&self, v: usize, shortcuts: usize) -> i64 {
+        let degree = self.inc[v].len() + self.out[v].len();
+        shortcuts as i64 - degree as i64 + self.deleted_neighbors[v]
+    }
+}
+
+/**
+ * Contraction hierarchy of a graph, answering shortest path queries much faster
+ * than a plain [`Dijkstra`](super::Dijkstra) search.
+ *
+ * Preprocessing contracts the nodes one at a time, ordered by edge difference, adding
+ * shortcut edges that preserve the distances among the remaining nodes. Queries then
+ * run a bidirectional search that only moves towards nodes contracted later.
+ *
+ * The index is a snapshot: it does not reflect later changes to the graph.
+ */
+pub struct ChIndex<I, W = u32> {
+    index: DenseIndex<I>,
+    // edges towards nodes contracted later, outgoing in `up` and incoming in `down`
+    up: Vec<Vec<ChEdge<W>>>,
+    down: Vec<Vec<ChEdge<W>>>,
+}
+
+impl<I: Ord + Hash + Copy> ChIndex<I> {
+    /**
+     * Builds the index of a graph where each edge costs 1.
+     */
+    pub fn build<G: GraphRead<Index = I>>(graph: &G) -> Self {
+        Self::build_with_weights(graph, |_, _| 1)
+    }
+}
+
+impl<I: Ord + Hash + Copy, W: Measure> ChIndex<I, W> {
+    /**
+     * Builds the index of a graph, minimizing the weights stored in the graph.
+     */
+    pub fn build_weighted<G>(graph: &G) -> Self
+    where
+        G: WeightedGraph<Index = I, Weight = W>,
+    {
+        Self::build_with_weights(graph, |f, t| {
+            graph
+                .edge_weight(f, t)
+                .expect("adjacent nodes are joined by an edge")
+        })
+    }
+
+    /**
+     * Builds the index of a graph where the edge `(f, t)` costs `weight(f, t)`.
+     */
+    pub fn build_with_weights<G, F>(graph: &G, weight: F) -> Self
+    where
+        G: GraphRead<Index = I>,
+        F: Fn(I, I) -> W,
+    {
+        let index = DenseIndex::new(graph);
+        let n = index.len();
+
+        let mut c = Contraction {
+            out: vec![HashMap::new(); n],
+            inc: vec![HashMap::new(); n],
+            deleted_neighbors: vec![0; n],
+        };
+        for (f, successors) in index.successors(graph).into_iter().enumerate() {
+            for t in successors {
+                if f != t {
+                    let w = weight(index.node(f), index.node(t));
+                    insert_min(&mut c.out[f], t, w, None);
+                    insert_min(&mut c.inc[t], f, w, None);
+                }
+            }
+        }
+
+        let mut queue = IndexedBinaryHeap::with_capacity(n);
+        for v in 0..n {
+            let shortcuts = c.shortcuts(v).len();
+            queue.push(v, (c.priority(v, shortcuts), v));
+        }
+
+        let mut up = vec![Vec::new(); n];
+        let mut down = vec![Vec::new(); n];
+        while let Some((v, _)) = queue.pop() {
+            // priorities are updated lazily, when a node reaches the top of the queue
+            let shortcuts = c.shortcuts(v);
+            let priority = (c.priority(v, shortcuts.len()), v);
+            if queue.peek().is_some_and(|(_, &next)| priority > next) {
+                queue.push(v, priority);
+                continue;
+            }
+
+            for (u, x, w) in shortcuts {
+                insert_min(&mut c.out[u], x, w, Some(v));
+                insert_min(&mut c.inc[x], u, w, Some(v));
+            }
+            for (x, (weight, middle)) in std::mem::take(&mut c.out[v]) {
+                c.inc[x].remove(&v);
+                c.deleted_neighbors[x] += 1;
+                up[v].push(ChEdge {
+                    node: x,
+                    weight,
+                    middle,
+                });
+            }
+            for (u, (weight, middle)) in std::mem::take(&mut c.inc[v]) {
+                c.out[u].remove(&v);
+                c.deleted_neighbors[u] += 1;
+                down[v].push(ChEdge {
+                    node: u,
+                    weight,
+                    middle,
+                });
+            }
+        }
+
+        ChIndex { index, up, down }
+    }
+
+    /**
+     * Returns the number of edges of the hierarchy, including shortcuts.
+     */
+    pub fn edge_count(&self) -> usize {
+        self.up.iter().map(Vec::len).sum::<usize>() + self.down.iter().map(Vec::len).sum::<usize>()
+    }
+
+    /**
+     * Returns the cost of the shortest path between `from` and `to`, if any.
+     */
+    pub fn distance(&self, from: I, to: I) -> Option<W> {
+        self.search(from, to).map(|(d, ..)| d)
+    }
+
+    /**
+     * Returns the shortest path between `from` and `to`, if any.
+     *
+     * Like [`ChIndex::distance`], which is zero from a node to itself, the path from a
+     * node to itself is that node alone.
+     */
+    pub fn shortest_path(&self, from: I, to: I) -> Option<Vec<I>> {
+        self.shortest_path_with_cost(from, to)
+            .map(CostedPath::path_only)
+    }
+
+    /**
+     * Returns the shortest path between `from` and `to` along with its cost, if any.
+     */
+    pub fn shortest_path_with_cost(&self, from: I, to: I) -> Option<CostedPath<I, W>> {
+        let (cost, meet, fwd, bwd) = self.search(from, to)?;
+        let (s, t) = (self.index.position(from)?, self.index.position(to)?);
+
+        let mut hops = Vec::new();
+        let mut cur = meet;
+        while cur != s {
+            let (prev, middle) = fwd[&cur];
+            hops.push((prev, cur, middle));
+            cur = prev;
+        }
+        hops.reverse();
+        let mut cur = meet;
+        while cur != t {
+            let (next, middle) = bwd[&cur];
+            hops.push((cur, next, middle));
+            cur = next;
+        }
+
+        let mut path = vec![s];
+        for (a, b, middle) in hops {
+            self.unpack(a, b, middle, &mut path);
+        }
+        Some(CostedPath {
+            path: path.into_iter().map(|p| self.index.node(p)).collect(),
+            cost,
+        })
+    }
+
+    fn unpack(&self, a: usize, b: usize, middle: Option<usize>, path: &mut Vec<usize>) {
+        match middle {
+            None => path.push(b),
+            Some(m) => {
+                let first = self.down[m].iter().find(|e| e.node == a).unwrap();
+                self.unpack(a, m, first.middle, path);
+                let second = self.up[m].iter().find(|e| e.node == b).unwrap();
+                self.unpack(m, b, second.middle, path);
+            }
+        }
+    }
+
+    /**
+     * Runs the bidirectional upward search, returning the distance, the meeting node and
+     * the predecessor maps of both directions.
+     */
+    fn search(&self, from: I, to: I) -> Option<(W, usize, Preds, Preds)> {
+        let s = self.index.position(from)?;
+        let t = self.index.position(to)?;
+
+        let mut dist = [HashMap::new(), HashMap::new()];
+        let mut preds = [HashMap::new(), HashMap::new()];
+        let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+        let mut done = [false, false];
+        let mut best: Option<(W, usize)> = None;
+
+        for (dir, start) in [s, t].into_iter().enumerate() {
+            dist[dir].insert(start, W::zero());
+            heaps[dir].push(Reverse((W::zero(), start)));
+        }
+
+        while !(done[0] && done[1]) {
+            for dir in 0..2 {
+                if done[dir] {
+                    continue;
+                }
+                let Some(Reverse((d, n))) = heaps[dir].pop() else {
+                    done[dir] = true;
+                    continue;
+                };
+                if dist[dir][&n] < d {
+                    continue;
+                }
+                if best.is_some_and(|(b, _)| d >= b) {
+                    done[dir] = true;
+                    continue;
+                }
+
+                if let Some(&other) = dist[1 - dir].get(&n) {
+                    let total = d.combine(other);
+                    if best.is_none_or(|(b, _)| total < b) {
+                        best = Some((total, n));
+                    }
+                }
+
+                let edges = if dir == 0 { &self.up[n] } else { &self.down[n] };
+                for e in edges {
+                    let alt = d.combine(e.weight);
+                    if dist[dir].get(&e.node).is_none_or(|&old| alt < old) {
+                        dist[dir].insert(e.node, alt);
+                        preds[dir].insert(e.node, (n, e.middle));
+                        heaps[dir].push(Reverse((alt, e.node)));
+                    }
+                }
+            }
+        }
+
+        let (d, meet) = best?;
+        let [fwd, bwd] = preds;
+        Some((d, meet, fwd, bwd))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{Algorithm, Dijkstra},
+        graph::Graph,
+        impls::adj_list::AdjListGraph,
+    };
+
+    fn pseudo_random_graph(n: usize, m: usize) -> (AdjListGraph, HashMap<(usize, usize), u32>) {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+        let mut weights = HashMap::new();
+        let mut state: u64 = 42;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as usize
+        };
+        while weights.len() < m {
+            let (f, t) = (ids[next() % n], ids[next() % n]);
+            if f != t && !g.has_edge(f, t) {
+                g.add_edge(f, t);
+                weights.insert((f.0, t.0), 1 + (next() % 20) as u32);
+            }
+        }
+        (g, weights)
+    }
+
+    #[test]
+    fn test_matches_dijkstra() {
+        let (g, weights) = pseudo_random_graph(60, 180);
+        let weight = |f: crate::impls::adj_list::Index, t: crate::impls::adj_list::Index| {
+            weights[&(f.0, t.0)]
+        };
+        let ch = ChIndex::build_with_weights(&g, weight);
+        let dijkstra = Dijkstra::with_weights(&g, weight);
+        let cost = |p: &[_]| p.windows(2).map(|w| weight(w[0], w[1])).sum::<u32>();
+
+        for f in g.iter_nodes() {
+            for t in g.iter_nodes() {
+                let expected = dijkstra.run(f, t);
+                let path = ch.shortest_path(f, t);
+                assert_eq!(path.is_some(), expected.is_some());
+                if let (Some(path), Some(expected)) = (path, expected) {
+                    assert_eq!(path.first(), Some(&f));
+                    assert_eq!(path.last(), Some(&t));
+                    assert!(path.windows(2).all(|w| g.has_edge(w[0], w[1])));
+                    assert_eq!(cost(&path), cost(&expected));
+                    assert_eq!(ch.distance(f, t), Some(cost(&expected)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unit_weights() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1]);
+        }
+        g.add_edge(ids[0], ids[3]);
+
+        let ch = ChIndex::build(&g);
+        assert_eq!(ch.distance(ids[0], ids[4]), Some(2));
+        assert_eq!(
+            ch.shortest_path(ids[0], ids[4]),
+            Some(vec![ids[0], ids[3], ids[4]])
+        );
+        assert_eq!(ch.shortest_path(ids[4], ids[0]), None);
+        assert_eq!(ch.shortest_path_with_cost(ids[1], ids[4]).unwrap().cost, 3);
+    }
+
+    #[test]
+    fn test_trivial_path() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[0]);
+
+        let ch = ChIndex::build(&g);
+        for &n in &ids {
+            assert_eq!(ch.distance(n, n), Some(0));
+            let path = ch.shortest_path_with_cost(n, n).unwrap();
+            assert_eq!((path.path, path.cost), (vec![n], 0));
+        }
+        assert_eq!(ch.shortest_path(ids[1], ids[1]), Some(vec![ids[1]]));
+    }
+}
@@ -0,0 +1,290 @@
+use super::DenseIndex;
+use crate::graph::GraphRead;
+use std::{collections::HashSet, hash::Hash};
+
+/**
+ * Returns the nodes at the positions, sorted.
+ */
+fn sorted_nodes<I: Hash + Eq + Copy>(index: &DenseIndex<I>, positions: &[usize]) -> Vec<I> {
+    let mut positions = positions.to_vec();
+    // positions are numbered in the order of the nodes
+    positions.sort_unstable();
+    positions.into_iter().map(|p| index.node(p)).collect()
+}
+
+/**
+ * Extends `clique` with the candidates, which are adjacent to all its nodes,
+ * recording the largest clique found in `best`.
+ */
+fn expand(
+    adj: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    candidates: Vec<usize>,
+    best: &mut Vec<usize>,
+) {
+    // greedy coloring: a clique has at most one node of each color, which bounds
+    // what the candidates up to each one can still add
+    let mut classes: Vec<Vec<usize>> = Vec::new();
+    for v in candidates {
+        match classes
+            .iter_mut()
+            .find(|class| class.iter().all(|u| !adj[v].contains(u)))
+        {
+            Some(class) => class.push(v),
+            None => classes.push(vec![v]),
+        }
+    }
+    let order: Vec<(usize, usize)> = classes
+        .iter()
+        .enumerate()
+        .flat_map(|(c, class)| class.iter().map(move |&v| (v, c + 1)))
+        .collect();
+
+    for i in (0..order.len()).rev() {
+        let (v, colors) = order[i];
+        if clique.len() + colors <= best.len() {
+            return;
+        }
+        clique.push(v);
+        let next: Vec<_> = order[..i]
+            .iter()
+            .map(|&(u, _)| u)
+            .filter(|u| adj[v].contains(u))
+            .collect();
+        if next.is_empty() {
+            if clique.len() > best.len() {
+                best.clone_from(clique);
+            }
+        } else {
+            expand(adj, clique, next, best);
+        }
+        clique.pop();
+    }
+}
+
+/**
+ * Bron–Kerbosch with pivoting, reporting the maximal cliques with at least
+ * `min_size` nodes.
+ */
+fn bron_kerbosch<I: Hash + Eq + Copy>(
+    index: &DenseIndex<I>,
+    adj: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    min_size: usize,
+    found: &mut Vec<Vec<I>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() && clique.len() >= min_size {
+            found.push(sorted_nodes(index, clique));
+        }
+        return;
+    }
+    if clique.len() + candidates.len() < min_size {
+        return;
+    }
+
+    // only the candidates not adjacent to the pivot can start a new maximal clique
+    let pivot = candidates
+        .iter()
+        .chain(&excluded)
+        .copied()
+        .max_by_key(|&u| (candidates.iter().filter(|v| adj[u].contains(v)).count(), u))
+        .unwrap();
+    let mut branches: Vec<_> = candidates
+        .iter()
+        .copied()
+        .filter(|v| !adj[pivot].contains(v))
+        .collect();
+    branches.sort_unstable();
+
+    for v in branches {
+        let neighbors = &adj[v];
+        clique.push(v);
+        bron_kerbosch(
+            index,
+            adj,
+            clique,
+            candidates
+                .iter()
+                .copied()
+                .filter(|u| neighbors.contains(u))
+                .collect(),
+            excluded
+                .iter()
+                .copied()
+                .filter(|u| neighbors.contains(u))
+                .collect(),
+            min_size,
+            found,
+        );
+        clique.pop();
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+/**
+ * Returns a maximum clique of the graph, with sorted nodes: a largest set of nodes
+ * each pair of which is joined by an edge, in either direction.
+ *
+ * Uses branch and bound with greedy coloring bounds, which is exact but exponential
+ * in the worst case, so it suits graphs of up to a few hundred nodes; see
+ * [`greedy_independent_set`] for a fast heuristic on the complement.
+ */
+pub fn maximum_clique<G>(graph: &G) -> Vec<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let adj = index.neighbor_sets(graph);
+    let mut best = Vec::new();
+    expand(&adj, &mut Vec::new(), (0..index.len()).collect(), &mut best);
+    sorted_nodes(&index, &best)
+}
+
+/**
+ * Returns an independent set of the graph, with sorted nodes: nodes no two of which
+ * are joined by an edge, in either direction.
+ *
+ * The set is maximal but not necessarily maximum: it is built by repeatedly taking the
+ * node with the fewest remaining neighbors and discarding them, in `O(n² + m)` time.
+ */
+pub fn greedy_independent_set<G>(graph: &G) -> Vec<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let adj = index.neighbor_sets(graph);
+    let n = index.len();
+    let mut degree: Vec<_> = adj.iter().map(HashSet::len).collect();
+    let mut removed = vec![false; n];
+    let mut set = Vec::new();
+
+    while let Some(v) = (0..n).filter(|&v| !removed[v]).min_by_key(|&v| degree[v]) {
+        set.push(v);
+        removed[v] = true;
+        for &u in &adj[v] {
+            if !removed[u] {
+                removed[u] = true;
+                for &w in &adj[u] {
+                    degree[w] -= 1;
+                }
+            }
+        }
+    }
+    sorted_nodes(&index, &set)
+}
+
+/**
+ * Returns the maximal cliques of the graph with at least `min_size` nodes, with the
+ * Bron–Kerbosch algorithm, treating edges as undirected.
+ *
+ * Each clique is sorted, and cliques are sorted. A graph with `n` nodes can have up to
+ * `3^(n/3)` maximal cliques, so a larger `min_size` also prunes the search.
+ */
+pub fn enumerate_cliques<G>(graph: &G, min_size: usize) -> Vec<Vec<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let adj = index.neighbor_sets(graph);
+    let mut found = Vec::new();
+    let candidates = (0..index.len()).collect();
+    bron_kerbosch(
+        &index,
+        &adj,
+        &mut Vec::new(),
+        candidates,
+        HashSet::new(),
+        min_size,
+        &mut found,
+    );
+    found.sort();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph, utils::Rng};
+
+    fn graph(
+        n: usize,
+        edges: &[(usize, usize)],
+    ) -> (AdjListGraph, Vec<<AdjListGraph as GraphRead>::Index>) {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+        for &(f, t) in edges {
+            g.add_edge(ids[f], ids[t]);
+        }
+        (g, ids)
+    }
+
+    #[test]
+    fn test_cliques() {
+        // a 4-clique sharing a node with a triangle, plus a pendant edge
+        let (g, ids) = graph(
+            8,
+            &[
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (1, 2),
+                (1, 3),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (5, 6),
+            ],
+        );
+        assert_eq!(maximum_clique(&g), ids[..4].to_vec());
+        assert_eq!(
+            enumerate_cliques(&g, 1),
+            vec![
+                ids[..4].to_vec(),
+                vec![ids[3], ids[4], ids[5]],
+                vec![ids[5], ids[6]],
+                vec![ids[7]],
+            ]
+        );
+        assert_eq!(enumerate_cliques(&g, 3).len(), 2);
+
+        let set = greedy_independent_set(&g);
+        assert!(set.contains(&ids[7]) && set.contains(&ids[6]));
+        for &a in &set {
+            for &b in &set {
+                assert!(!g.has_edge(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_enumeration() {
+        let mut rng = Rng::new(11);
+        for _ in 0..20 {
+            let mut edges = Vec::new();
+            for f in 0..14 {
+                for t in f + 1..14 {
+                    if rng.next_u64().is_multiple_of(2) {
+                        edges.push((f, t));
+                    }
+                }
+            }
+            let (g, _) = graph(14, &edges);
+            let largest = enumerate_cliques(&g, 0).into_iter().map(|c| c.len()).max();
+            let clique = maximum_clique(&g);
+            assert_eq!(Some(clique.len()), largest);
+            for &a in &clique {
+                for &b in &clique {
+                    assert!(a == b || g.has_edge(a, b) || g.has_edge(b, a));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use super::{centrality::edge_betweenness_by_position, DenseIndex};
+use crate::graph::GraphRead;
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Successive partitions of the nodes into communities, from the coarsest to the
+ * finest, returned by [`girvan_newman`].
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Dendrogram<I> {
+    levels: Vec<Vec<Vec<I>>>,
+}
+
+impl<I> Dendrogram<I> {
+    /**
+     * Returns the partitions: the first one has the connected components of the
+     * graph, and each of the others splits one community of the previous one in two.
+     * Communities are sorted by their smallest node, and their nodes in ascending order.
+     */
+    pub fn levels(&self) -> &[Vec<Vec<I>>] {
+        &self.levels
+    }
+
+    /**
+     * Returns the coarsest partition with at least `k` communities, if there is one.
+     */
+    pub fn communities(&self, k: usize) -> Option<&[Vec<I>]> {
+        self.levels
+            .iter()
+            .find(|level| level.len() >= k)
+            .map(Vec::as_slice)
+    }
+}
+
+/**
+ * Labels each position with its connected component, numbered by smallest position.
+ */
+fn components(adj: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let mut labels = vec![usize::MAX; adj.len()];
+    let mut count = 0;
+    for start in 0..adj.len() {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+        labels[start] = count;
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            for &w in &adj[v] {
+                if labels[w] == usize::MAX {
+                    labels[w] = count;
+                    stack.push(w);
+                }
+            }
+        }
+        count += 1;
+    }
+    (labels, count)
+}
+
+/**
+ * Finds communities with the divisive algorithm of Girvan and Newman, which keeps
+ * removing the edge with the highest betweenness and records a new partition each
+ * time a community falls apart.
+ *
+ * Edges are taken as undirected and unweighted, and ties are broken by removing the
+ * edge between the smallest nodes. Since the betweenness is computed again after
+ * every removal, it takes `O(m² n)` time, which limits it to small graphs.
+ */
+pub fn girvan_newman<G>(graph: &G) -> Dendrogram<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let mut adj: Vec<Vec<usize>> = index
+        .neighbor_sets(graph)
+        .into_iter()
+        .map(|neighbors| {
+            let mut a: Vec<_> = neighbors.into_iter().collect();
+            a.sort_unstable();
+            a
+        })
+        .collect();
+
+    let partition = |labels: &[usize], count: usize| {
+        let mut communities = vec![Vec::new(); count];
+        for (v, &l) in labels.iter().enumerate() {
+            communities[l].push(index.node(v));
+        }
+        communities
+    };
+    let (labels, mut count) = components(&adj);
+    let mut levels = vec![partition(&labels, count)];
+
+    while adj.iter().any(|a| !a.is_empty()) {
+        let mut scores: HashMap<(usize, usize), f64> = HashMap::new();
+        for ((v, w), score) in edge_betweenness_by_position(&adj) {
+            *scores.entry((v.min(w), v.max(w))).or_default() += score;
+        }
+        let mut edges: Vec<_> = scores.into_iter().collect();
+        edges.sort_by_key(|&(edge, _)| edge);
+        let ((v, w), _) = edges
+            .into_iter()
+            .reduce(|best, e| if e.1 > best.1 { e } else { best })
+            .unwrap();
+        adj[v].retain(|&a| a != w);
+        adj[w].retain(|&a| a != v);
+
+        let (labels, new_count) = components(&adj);
+        if new_count > count {
+            count = new_count;
+            levels.push(partition(&labels, count));
+        }
+    }
+    Dendrogram { levels }
+}
+
+/**
+ * Returns the modularity of the partition of the nodes given by `labels`, where
+ * nodes with the same label are in the same community; see [`weighted_modularity`].
+ */
+pub fn modularity<G, L>(graph: &G, labels: &HashMap<G::Index, L>) -> f64
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    L: Hash + Eq,
+{
+    weighted_modularity(graph, labels, |_, _| 1.0)
+}
+
+/**
+ * Returns the modularity of the partition of the nodes given by `labels`, where the
+ * edge `(f, t)` weighs `weight(f, t)`: the fraction of the weight within communities,
+ * minus the fraction expected if the edges were rewired keeping the weighted degrees.
+ *
+ * The score is at most 1, and 0 for a single community. Edges follow the ones
+ * listed by [`GraphRead::iter_adj`], so undirected graphs, whose adjacency has each
+ * edge in both directions, get the usual undirected modularity. Nodes without a label
+ * are each in a community of their own, and a graph without edges scores 0.
+ */
+pub fn weighted_modularity<G, L, F>(graph: &G, labels: &HashMap<G::Index, L>, weight: F) -> f64
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    L: Hash + Eq,
+    F: Fn(G::Index, G::Index) -> f64,
+{
+    // communities are keyed by label, or by node for the unlabelled ones
+    let community = |n: G::Index| labels.get(&n).ok_or(n);
+
+    let mut total = 0.0;
+    let mut inside: HashMap<_, f64> = HashMap::new();
+    let mut out_degree: HashMap<_, f64> = HashMap::new();
+    let mut in_degree: HashMap<_, f64> = HashMap::new();
+    for f in graph.iter_nodes() {
+        for t in graph.iter_adj(f).into_iter().flatten() {
+            let w = weight(f, t);
+            let (cf, ct) = (community(f), community(t));
+            total += w;
+            *out_degree.entry(cf).or_default() += w;
+            *in_degree.entry(ct).or_default() += w;
+            if cf == ct {
+                *inside.entry(cf).or_default() += w;
+            }
+        }
+    }
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    let within: f64 = inside.values().sum();
+    let expected: f64 = out_degree
+        .iter()
+        .map(|(c, out)| out * in_degree.get(c).copied().unwrap_or(0.0))
+        .sum();
+    within / total - expected / (total * total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, WeightedGraph},
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_girvan_newman() {
+        // two triangles joined by a bridge, and an isolated node
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..7).map(|_| g.add_node()).collect();
+        for (f, t) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            g.add_edge(ids[f], ids[t]);
+        }
+
+        let dendrogram = girvan_newman(&g);
+        let levels = dendrogram.levels();
+        assert_eq!(levels[0], vec![ids[..6].to_vec(), vec![ids[6]]]);
+        assert_eq!(
+            levels[1],
+            vec![ids[..3].to_vec(), ids[3..6].to_vec(), vec![ids[6]]]
+        );
+        assert_eq!(levels.last().unwrap().len(), 7);
+        assert!(levels.windows(2).all(|w| w[1].len() == w[0].len() + 1));
+
+        assert_eq!(dendrogram.communities(3), Some(&levels[1][..]));
+        assert_eq!(dendrogram.communities(8), None);
+        let empty = girvan_newman(&AdjListGraph::new());
+        assert!(empty.levels().len() == 1 && empty.levels()[0].is_empty());
+    }
+
+    #[test]
+    fn test_modularity() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..6).map(|_| g.add_node()).collect();
+        for (f, t) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            g.add_edge(ids[f], ids[t]);
+            g.add_edge(ids[t], ids[f]);
+        }
+
+        let halves: HashMap<_, _> = ids.iter().enumerate().map(|(i, &n)| (n, i / 3)).collect();
+        assert!((modularity(&g, &halves) - (12.0 / 14.0 - 0.5)).abs() < 1e-12);
+        let single: HashMap<_, _> = ids.iter().map(|&n| (n, ())).collect();
+        assert!(modularity(&g, &single).abs() < 1e-12);
+        // without labels every node is alone, which scores below zero
+        assert!(modularity(&g, &HashMap::<_, ()>::new()) < 0.0);
+        assert_eq!(modularity(&AdjListGraph::new(), &single), 0.0);
+    }
+
+    #[test]
+    fn test_weighted_modularity() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 5.0);
+        g.add_weighted_edge(ids[2], ids[3], 5.0);
+        g.add_weighted_edge(ids[1], ids[2], 1.0);
+
+        let labels: HashMap<_, _> = ids.iter().enumerate().map(|(i, &n)| (n, i / 2)).collect();
+        let weight = |f, t| g.edge_weight(f, t).unwrap();
+        let q = weighted_modularity(&g, &labels, weight);
+        // 10 of the 11 units of weight are inside, and each half has half the strength
+        assert!((q - (10.0 / 11.0 - 0.5)).abs() < 1e-12);
+        assert!(q > modularity(&g, &labels));
+        let doubled = weighted_modularity(&g, &labels, |f, t| 2.0 * weight(f, t));
+        assert!((q - doubled).abs() < 1e-12);
+    }
+}
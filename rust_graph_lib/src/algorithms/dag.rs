@@ -0,0 +1,365 @@
+use super::{CostedPath, DenseIndex, Measure};
+use crate::graph::GraphRead;
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+};
+
+/**
+ * Error returned when an algorithm requiring a directed acyclic graph finds a cycle.
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct NotADag<I> {
+    /**
+     * The nodes of one cycle of the graph, in edge order; the last node has an edge
+     * to the first.
+     */
+    pub cycle: Vec<I>,
+}
+
+impl<I: Debug> Display for NotADag<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the graph has a cycle through {:?}", self.cycle)
+    }
+}
+
+impl<I: Debug> Error for NotADag<I> {}
+
+type Edges<I> = Vec<(I, I)>;
+type LongestPath<I, W> = Option<CostedPath<I, W>>;
+
+/**
+ * Dense numbering of the nodes of a graph along with a topological order of them.
+ */
+struct Dag<I> {
+    index: DenseIndex<I>,
+    adj: Vec<Vec<usize>>,
+    order: Vec<usize>,
+}
+
+impl<I: Hash + Ord + Copy> Dag<I> {
+    fn new<G: GraphRead<Index = I>>(graph: &G) -> Result<Self, NotADag<I>> {
+        let index = DenseIndex::new(graph);
+        let adj = index.successors(graph);
+
+        // Kahn's algorithm, emitting the sources in ascending order of index
+        let mut in_degree = vec![0; index.len()];
+        for &t in adj.iter().flatten() {
+            in_degree[t] += 1;
+        }
+        let mut queue: VecDeque<_> = (0..index.len()).filter(|&n| in_degree[n] == 0).collect();
+        let mut order = Vec::with_capacity(index.len());
+        while let Some(n) = queue.pop_front() {
+            order.push(n);
+            for &t in &adj[n] {
+                in_degree[t] -= 1;
+                if in_degree[t] == 0 {
+                    queue.push_back(t);
+                }
+            }
+        }
+
+        if order.len() < index.len() {
+            let cycle = Self::find_cycle(&adj, &in_degree);
+            return Err(NotADag {
+                cycle: cycle.into_iter().map(|i| index.node(i)).collect(),
+            });
+        }
+        Ok(Dag { index, adj, order })
+    }
+
+    /**
+     * Returns a cycle among the positions left with a positive in-degree by Kahn's
+     * algorithm, each of which has a predecessor among them.
+     */
+    fn find_cycle(adj: &[Vec<usize>], in_degree: &[usize]) -> Vec<usize> {
+        let mut pred = vec![None; adj.len()];
+        for (f, adj) in adj.iter().enumerate() {
+            for &t in adj {
+                if in_degree[f] > 0 && in_degree[t] > 0 {
+                    pred[t].get_or_insert(f);
+                }
+            }
+        }
+
+        // walking predecessors backwards must eventually revisit a position
+        let mut seen = vec![false; adj.len()];
+        let mut cur = in_degree.iter().position(|&d| d > 0).unwrap();
+        while !seen[cur] {
+            seen[cur] = true;
+            cur = pred[cur].unwrap();
+        }
+        let mut cycle = vec![cur];
+        let mut n = pred[cur].unwrap();
+        while n != cur {
+            cycle.push(n);
+            n = pred[n].unwrap();
+        }
+        cycle.reverse();
+        cycle
+    }
+}
+
+/**
+ * Returns the nodes of a directed acyclic graph in topological order, so that every
+ * edge goes from a node to a later one, or a cycle of the graph.
+ */
+pub fn topological_sort<G>(graph: &G) -> Result<Vec<G::Index>, NotADag<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let dag = Dag::new(graph)?;
+    Ok(dag.order.iter().map(|&i| dag.index.node(i)).collect())
+}
+
+/**
+ * Groups the nodes of a directed acyclic graph into generations, so that each node only
+ * has edges from nodes of earlier generations, or returns a cycle of the graph.
+ *
+ * The first generation holds the nodes without incoming edges and every node is in
+ * the earliest generation possible, so the tasks of a generation can run in parallel
+ * once the previous ones are done. Nodes of a generation are sorted.
+ */
+pub fn topological_generations<G>(graph: &G) -> Result<Vec<Vec<G::Index>>, NotADag<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let dag = Dag::new(graph)?;
+    let mut level = vec![0; dag.index.len()];
+    let mut generations: Vec<Vec<G::Index>> = Vec::new();
+    for &u in &dag.order {
+        for &v in &dag.adj[u] {
+            level[v] = level[v].max(level[u] + 1);
+        }
+        if level[u] == generations.len() {
+            generations.push(Vec::new());
+        }
+        generations[level[u]].push(dag.index.node(u));
+    }
+
+    for generation in &mut generations {
+        generation.sort();
+    }
+    Ok(generations)
+}
+
+/**
+ * Returns the edges of the transitive reduction of a directed acyclic graph: the
+ * smallest set of edges with the same reachability between nodes, which is unique.
+ *
+ * The reduction keeps exactly the edges `a -> b` with no other path from `a` to `b`.
+ * Edges are sorted; removing the others from the graph, or adding them to a graph
+ * with the same nodes, gives the reduced graph. Takes `O(nm / 64)` time and
+ * `O(n² / 64)` memory.
+ */
+pub fn transitive_reduction<G>(graph: &G) -> Result<Edges<G::Index>, NotADag<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let dag = Dag::new(graph)?;
+    let n = dag.index.len();
+    let mut rank = vec![0; n];
+    for (r, &i) in dag.order.iter().enumerate() {
+        rank[i] = r;
+    }
+
+    // reach[u] is a bitset of the positions reachable from u through at least one edge
+    let words = n.div_ceil(64);
+    let mut reach = vec![vec![0u64; words]; n];
+    let mut edges = Vec::new();
+    for &u in dag.order.iter().rev() {
+        let mut succ = dag.adj[u].clone();
+        // a successor reachable through another one comes after it in topological order
+        succ.sort_by_key(|&v| rank[v]);
+        let mut reached = vec![0u64; words];
+        for v in succ {
+            if reached[v / 64] & (1 << (v % 64)) != 0 {
+                continue;
+            }
+            edges.push((dag.index.node(u), dag.index.node(v)));
+            reached[v / 64] |= 1 << (v % 64);
+            for (r, w) in reached.iter_mut().zip(&reach[v]) {
+                *r |= w;
+            }
+        }
+        reach[u] = reached;
+    }
+
+    edges.sort();
+    Ok(edges)
+}
+
+/**
+ * Returns a longest path of a directed acyclic graph, for instance the critical path
+ * of a task graph, with edge lengths given by `weight`, or a cycle of the graph.
+ *
+ * The path may start and end at any node; its cost is the sum of its edge lengths,
+ * saturating like [`Measure::combine`], and among paths of equal cost one with the
+ * most edges is returned. Runs in `O(n + m)` time and returns `None`
+ * only if the graph is empty.
+ */
+pub fn longest_path_dag<G, W, F>(
+    graph: &G,
+    weight: F,
+) -> Result<LongestPath<G::Index, W>, NotADag<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    W: Measure,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let dag = Dag::new(graph)?;
+    let n = dag.index.len();
+
+    // the longest path ending at each position, with ties broken by the number of
+    // edges, and its previous position
+    let mut length = vec![(W::zero(), 0); n];
+    let mut pred = vec![None; n];
+    for &u in &dag.order {
+        for &v in &dag.adj[u] {
+            let (w, hops) = length[u];
+            let candidate = (
+                w.combine(weight(dag.index.node(u), dag.index.node(v))),
+                hops + 1,
+            );
+            if candidate > length[v] {
+                length[v] = candidate;
+                pred[v] = Some(u);
+            }
+        }
+    }
+
+    let Some(end) = (0..n).max_by(|&a, &b| length[a].cmp(&length[b]).then(b.cmp(&a))) else {
+        return Ok(None);
+    };
+    let mut path = vec![dag.index.node(end)];
+    let mut cur = end;
+    while let Some(p) = pred[cur] {
+        path.push(dag.index.node(p));
+        cur = p;
+    }
+    path.reverse();
+    Ok(Some(CostedPath {
+        path,
+        cost: length[end].0,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::reachable_from, graph::Graph, impls::adj_list::AdjListGraph};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_topological_sort() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| g.add_node());
+        g.add_edge(c, a);
+        g.add_edge(a, b);
+        g.add_edge(c, b);
+        g.add_edge(d, c);
+        assert_eq!(topological_sort(&g), Ok(vec![d, c, a, b]));
+
+        g.add_edge(b, d);
+        let err = topological_sort(&g).unwrap_err();
+        assert_eq!(err.cycle.len(), 4);
+        let first = err.cycle.iter().position(|&n| n == d).unwrap();
+        let rotated: Vec<_> = err
+            .cycle
+            .iter()
+            .cycle()
+            .skip(first)
+            .take(4)
+            .copied()
+            .collect();
+        assert_eq!(rotated, vec![d, c, a, b]);
+
+        g.remove_edge(b, d);
+        g.add_edge(b, b);
+        assert_eq!(topological_sort(&g).unwrap_err().cycle, vec![b]);
+    }
+
+    #[test]
+    fn test_generations() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d, e, f] = [(); 6].map(|_| g.add_node());
+        for (s, t) in [(a, c), (b, c), (c, d), (a, d), (b, e)] {
+            g.add_edge(s, t);
+        }
+        assert_eq!(
+            topological_generations(&g),
+            Ok(vec![vec![a, b, f], vec![c, e], vec![d]])
+        );
+
+        assert_eq!(topological_generations(&AdjListGraph::new()), Ok(vec![]));
+        g.add_edge(d, b);
+        assert!(topological_generations(&g).is_err());
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        // a diamond with shortcuts from its top to everything below it
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| g.add_node());
+        for (f, t) in [
+            (a, b),
+            (a, c),
+            (b, d),
+            (c, d),
+            (d, e),
+            (a, d),
+            (a, e),
+            (b, e),
+        ] {
+            g.add_edge(f, t);
+        }
+
+        let edges = transitive_reduction(&g).unwrap();
+        assert_eq!(edges, vec![(a, b), (a, c), (b, d), (c, d), (d, e)]);
+
+        let mut reduced = AdjListGraph::new();
+        for _ in 0..5 {
+            reduced.add_node();
+        }
+        for &(f, t) in &edges {
+            reduced.add_edge(f, t);
+        }
+        for n in [a, b, c, d, e] {
+            assert_eq!(reachable_from(&reduced, n), reachable_from(&g, n));
+        }
+
+        g.add_edge(e, a);
+        assert!(transitive_reduction(&g).is_err());
+    }
+
+    #[test]
+    fn test_longest_path() {
+        // tasks with durations, an edge meaning the target waits for the source
+        let mut g = AdjListGraph::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| g.add_node());
+        let duration = HashMap::from([(a, 3u32), (b, 2), (c, 4), (d, 1), (e, 0)]);
+        for (f, t) in [(a, b), (a, c), (b, d), (c, d), (d, e)] {
+            g.add_edge(f, t);
+        }
+
+        let critical = longest_path_dag(&g, |f, _| duration[&f]).unwrap().unwrap();
+        assert_eq!(critical.path, vec![a, c, d, e]);
+        assert_eq!(critical.cost, 8);
+
+        // zero-length edges still extend the path
+        let zero = longest_path_dag(&g, |_, _| 0u32).unwrap().unwrap();
+        assert_eq!((zero.path.len(), zero.cost), (4, 0));
+
+        assert!(longest_path_dag(&AdjListGraph::new(), |_, _| 1u32)
+            .unwrap()
+            .is_none());
+        g.add_edge(e, a);
+        assert!(longest_path_dag(&g, |_, _| 1u32).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::graph::GraphRead;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/**
+ * Dense numbering of the nodes of a graph in ascending order, so that per-node values
+ * and adjacency lists can live in vectors indexed by position.
+ *
+ * Numbering in order keeps the results of randomized and tie-breaking algorithms
+ * independent of the order the graph iterates its nodes in.
+ */
+#[derive(Clone, Debug)]
+pub(crate) struct DenseIndex<I> {
+    nodes: Vec<I>,
+    positions: HashMap<I, usize>,
+}
+
+impl<I: Hash + Ord + Copy> DenseIndex<I> {
+    pub(crate) fn new<G: GraphRead<Index = I>>(graph: &G) -> Self {
+        let mut nodes: Vec<_> = graph.iter_nodes().collect();
+        nodes.sort();
+        let positions = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        DenseIndex { nodes, positions }
+    }
+
+    /**
+     * Returns the successors of each position, in the order of [`GraphRead::iter_adj`].
+     */
+    pub(crate) fn successors<G: GraphRead<Index = I>>(&self, graph: &G) -> Vec<Vec<usize>> {
+        self.nodes
+            .iter()
+            .map(|&n| {
+                let adj = graph.iter_adj(n).into_iter().flatten();
+                adj.filter_map(|a| self.position(a)).collect()
+            })
+            .collect()
+    }
+
+    /**
+     * Returns the edges of the graph between positions, as listed by
+     * [`GraphRead::iter_edges`].
+     */
+    pub(crate) fn edges<'a, G: GraphRead<Index = I>>(
+        &'a self,
+        graph: &'a G,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        graph
+            .iter_edges()
+            .filter_map(|(f, t)| Some((self.position(f)?, self.position(t)?)))
+    }
+
+    /**
+     * Returns the positions joined to each position by an edge in either direction,
+     * self loops excluded.
+     */
+    pub(crate) fn neighbor_sets<G: GraphRead<Index = I>>(&self, graph: &G) -> Vec<HashSet<usize>> {
+        let mut adj = vec![HashSet::new(); self.len()];
+        for (f, t) in self.edges(graph).filter(|(f, t)| f != t) {
+            adj[f].insert(t);
+            adj[t].insert(f);
+        }
+        adj
+    }
+}
+
+impl<I: Hash + Eq + Copy> DenseIndex<I> {
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /**
+     * Returns the nodes, by position.
+     */
+    pub(crate) fn nodes(&self) -> &[I] {
+        &self.nodes
+    }
+
+    pub(crate) fn node(&self, p: usize) -> I {
+        self.nodes[p]
+    }
+
+    pub(crate) fn position(&self, n: I) -> Option<usize> {
+        self.positions.get(&n).copied()
+    }
+
+    /**
+     * Pairs each node with the value at its position.
+     */
+    pub(crate) fn into_map<V>(self, values: Vec<V>) -> HashMap<I, V> {
+        self.nodes.into_iter().zip(values).collect()
+    }
+}
@@ -1,16 +1,68 @@
-use crate::graph::Graph;
-use std::{collections::HashSet, hash::Hash, iter};
+use crate::graph::ImplicitGraph;
+use std::{cmp::Ordering, collections::HashSet, hash::Hash, rc::Rc};
 
-use super::Algorithm;
+use super::{trace::SearchStats, Algorithm, Budget, BudgetedPath, Cancelled, Control, GraphHandle};
 
-pub struct Dfs<'a, G: Graph> {
-    graph: &'a G,
+type NeighborOrder<'a, I> = Box<dyn Fn(&I, &I) -> Ordering + 'a>;
+
+pub struct Dfs<'a, G: ImplicitGraph> {
+    graph: GraphHandle<'a, G>,
+    order: Option<NeighborOrder<'a, G::Index>>,
 }
 
 // TODO remove copy
-impl<'a, I: Hash + Eq + Copy, G: Graph<Index = I>> Dfs<'a, G> {
+impl<'a, I: Hash + Eq + Copy, G: ImplicitGraph<Index = I>> Dfs<'a, G> {
     pub fn new(graph: &'a G) -> Self {
-        Self { graph }
+        Self {
+            graph: GraphHandle::Borrowed(graph),
+            order: None,
+        }
+    }
+
+    /**
+     * Creates a search owning a shared reference to the graph, so that it can outlive
+     * the scope it was created in.
+     */
+    pub fn new_shared(graph: Rc<G>) -> Self {
+        Self {
+            graph: GraphHandle::Shared(graph),
+            order: None,
+        }
+    }
+
+    /**
+     * Expands the neighbors of each node in the order given by `order`,
+     * instead of the order in which the graph yields them.
+     */
+    pub fn with_neighbor_order<F: Fn(&I, &I) -> Ordering + 'a>(mut self, order: F) -> Self {
+        self.order = Some(Box::new(order));
+        self
+    }
+
+    /**
+     * Expands the neighbors of each node in ascending index order, making the result deterministic.
+     */
+    pub fn sorted(self) -> Self
+    where
+        I: Ord,
+    {
+        self.with_neighbor_order(|a, b| a.cmp(b))
+    }
+
+    fn neighbors<'s>(&'s self, n: I) -> Box<dyn Iterator<Item = I> + 's>
+    where
+        I: 's,
+    {
+        let adj = self.graph.successors(n);
+
+        match &self.order {
+            Some(order) => {
+                let mut adj: Vec<_> = adj.collect();
+                adj.sort_by(order);
+                Box::new(adj.into_iter())
+            }
+            None => adj,
+        }
     }
 
     fn inner_dfs(
@@ -18,50 +70,89 @@ impl<'a, I: Hash + Eq + Copy, G: Graph<Index = I>> Dfs<'a, G> {
         visited: &mut HashSet<G::Index>,
         cur: &mut Vec<G::Index>,
         to: G::Index,
-    ) -> bool {
-        let from = cur.last().unwrap();
-        if *from == to {
-            return true;
+        control: &mut Control<G::Index>,
+        stats: &mut SearchStats,
+    ) -> Result<bool, Cancelled> {
+        let from = *cur.last().unwrap();
+        if control(&from).is_break() {
+            return Err(Cancelled);
         }
+        if from == to {
+            return Ok(true);
+        }
+
+        visited.insert(from);
+        stats.expand(cur.len());
 
-        visited.insert(*from);
-
-        self.graph
-            .iter_adj(*from)
-            .unwrap_or_else(|| Box::new(iter::empty()))
-            .any(|a| {
-                if !visited.contains(&a) {
-                    cur.push(a);
-                    if self.inner_dfs(visited, cur, to) {
-                        return true;
-                    }
-                    cur.pop();
+        for a in self.neighbors(from) {
+            if !visited.contains(&a) {
+                cur.push(a);
+                if self.inner_dfs(visited, cur, to, control, stats)? {
+                    return Ok(true);
                 }
-                false
-            })
+                cur.pop();
+            }
+        }
+        Ok(false)
     }
-}
 
-impl<'a, I: Hash + Eq + Copy, G: Graph<Index = I>> Algorithm<G> for Dfs<'a, G> {
-    fn run(&self, from: G::Index, to: G::Index) -> Option<Vec<G::Index>> {
+    fn search(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        control: &mut Control<G::Index>,
+    ) -> (Vec<G::Index>, Result<bool, Cancelled>) {
         let mut cur = Vec::new();
 
         let mut visited = HashSet::new();
         cur.push(from);
-        self.inner_dfs(&mut visited, &mut cur, to);
+        let mut stats = SearchStats::new("dfs");
+        let res = self.inner_dfs(&mut visited, &mut cur, to, control, &mut stats);
+        (cur, res)
+    }
+}
+
+impl<'a, I: Hash + Eq + Copy, G: ImplicitGraph<Index = I>> Algorithm<G> for Dfs<'a, G> {
+    fn run_with_control(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        control: &mut Control<G::Index>,
+    ) -> Result<Option<Vec<G::Index>>, Cancelled> {
+        let (cur, res) = self.search(from, to, control);
+        Ok(res?.then_some(cur))
+    }
 
-        Some(cur).filter(|cur| cur.len() >= 2)
+    /**
+     * When the budget runs out, the partial path is the branch being explored.
+     */
+    fn run_with_budget(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        budget: Budget,
+    ) -> BudgetedPath<G::Index> {
+        let (cur, res) = self.search(from, to, &mut budget.start());
+
+        BudgetedPath {
+            exhausted: res.is_err(),
+            path: res.unwrap_or(true).then_some(cur),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{algorithms::test_utils::slice_equal, graph::Graph, impls::adj_list::AdjListGraph};
+    use crate::{
+        algorithms::test_utils::slice_equal,
+        graph::{Graph, GraphRead},
+        impls::adj_list::AdjListGraph,
+    };
 
-    use super::{Algorithm, Dfs};
-    use std::hash::Hash;
+    use super::{Algorithm, Budget, Cancelled, Dfs};
+    use std::{hash::Hash, ops::ControlFlow, rc::Rc};
 
-    fn dfs<I: Hash + Eq + Copy, G: Graph<Index = I>>(
+    fn dfs<I: Hash + Eq + Copy, G: GraphRead<Index = I>>(
         g: &G,
         from: G::Index,
         to: G::Index,
@@ -86,6 +177,7 @@ mod tests {
         let p = dfs(&g, id1, id5);
         assert!(p.is_some());
         assert!(slice_equal(&p.unwrap(), &[id1, id2, id3, id4, id5]));
+        assert_eq!(dfs(&g, id3, id3), Some(vec![id3]));
     }
 
     #[test]
@@ -104,4 +196,85 @@ mod tests {
         let p = dfs(&g, id1, id5);
         assert!(p.is_none());
     }
+
+    #[test]
+    fn can_be_cancelled() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+
+        let mut visited = Vec::new();
+        let res = Dfs::new(&g).run_with_control(id1, id3, &mut |&n| {
+            visited.push(n);
+            if n == id2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(res, Err(Cancelled));
+        assert!(slice_equal(&visited, &[id1, id2]));
+    }
+
+    #[test]
+    fn returns_partial_path_when_budget_runs_out() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id4);
+
+        let res = Dfs::new(&g).run_with_budget(id1, id4, Budget::expansions(2));
+        assert!(res.exhausted);
+        assert!(slice_equal(&res.path.unwrap(), &[id1, id2, id3]));
+
+        let res = Dfs::new(&g).run_with_budget(id1, id4, Budget::default());
+        assert!(!res.exhausted);
+        assert!(slice_equal(&res.path.unwrap(), &[id1, id2, id3, id4]));
+    }
+
+    #[test]
+    fn follows_neighbor_order() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id3);
+        g.add_edge(id2, id4);
+        g.add_edge(id3, id4);
+
+        for _ in 0..10 {
+            let p = Dfs::new(&g).sorted().run(id1, id4);
+            assert!(slice_equal(&p.unwrap(), &[id1, id2, id4]));
+
+            let p = Dfs::new(&g)
+                .with_neighbor_order(|a, b| b.cmp(a))
+                .run(id1, id4);
+            assert!(slice_equal(&p.unwrap(), &[id1, id3, id4]));
+        }
+    }
+
+    #[test]
+    fn shared_search_keeps_graph_alive() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id1, id2);
+
+        let g = Rc::new(g);
+        let dfs = Dfs::new_shared(Rc::clone(&g));
+        drop(g);
+        assert!(slice_equal(&dfs.run(id1, id2).unwrap(), &[id1, id2]));
+    }
 }
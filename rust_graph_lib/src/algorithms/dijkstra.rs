@@ -1,103 +1,343 @@
 use std::{
     cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
+    collections::{BinaryHeap, HashMap, HashSet},
     hash::Hash,
-    iter,
+    ops::ControlFlow,
+    rc::Rc,
 };
 
-use crate::graph::Graph;
+use crate::graph::{ImplicitGraph, WeightedGraph};
 
-use super::Algorithm;
+use super::{
+    trace::SearchStats, Algorithm, Budget, BudgetedPath, Cancelled, Control, CostedPath,
+    GraphHandle, Measure, ShortestPathTree,
+};
 
-struct NodeWithDist<G: Graph>(G::Index, u32);
+struct NodeWithDist<I, W>(I, W);
 
-impl<G: Graph> PartialEq for NodeWithDist<G> {
+impl<I: Ord, W: Eq> PartialEq for NodeWithDist<I, W> {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0 && self.1 == other.1
+        self.1 == other.1 && self.0 == other.0
     }
 }
 
-impl<G: Graph> Eq for NodeWithDist<G> {}
+impl<I: Ord, W: Eq> Eq for NodeWithDist<I, W> {}
 
-impl<G: Graph> Ord for NodeWithDist<G> {
+impl<I: Ord, W: Ord> Ord for NodeWithDist<I, W> {
     fn cmp(&self, other: &Self) -> Ordering {
-        // order is reversed because BinaryHeap returns the max
-        other.1.cmp(&self.1)
+        // order is reversed because BinaryHeap returns the max; ties go to the
+        // smaller index, so that the settling order does not depend on hashing
+        other.1.cmp(&self.1).then_with(|| other.0.cmp(&self.0))
     }
 }
 
-impl<G: Graph> PartialOrd for NodeWithDist<G> {
+impl<I: Ord, W: Ord> PartialOrd for NodeWithDist<I, W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-pub struct Dijkstra<'a, G: Graph> {
-    graph: &'a G,
+type WeightFn<'a, I, W> = Box<dyn Fn(I, I) -> W + 'a>;
+type EdgeFilter<'a, I> = Box<dyn Fn(I, I) -> bool + 'a>;
+type NodeFilter<'a, I> = Box<dyn Fn(I) -> bool + 'a>;
+type Search<I, W> = (HashMap<I, I>, HashMap<I, W>, Result<Option<I>, I>);
+
+/**
+ * Dijkstra shortest path search, minimizing a [`Measure`] of the path.
+ *
+ * By default each edge costs 1, so the path with fewer edges is found.
+ *
+ * Results are deterministic: nodes at the same distance are settled in ascending
+ * index order, and each node is reached from the first settled node giving it its
+ * distance, so among equally short paths the one found does not depend on the
+ * order in which the graph yields the neighbors.
+ */
+pub struct Dijkstra<'a, G: ImplicitGraph, W = u32> {
+    graph: GraphHandle<'a, G>,
+    weight: WeightFn<'a, G::Index, W>,
+    edge_filter: Option<EdgeFilter<'a, G::Index>>,
+    node_filter: Option<NodeFilter<'a, G::Index>>,
 }
 
-impl<'a, G: Graph> Dijkstra<'a, G> {
+impl<'a, G: ImplicitGraph> Dijkstra<'a, G> {
     pub fn new(graph: &'a G) -> Self {
-        Self { graph }
+        Self::with_weights(graph, |_, _| 1)
+    }
+
+    /**
+     * Creates a search owning a shared reference to the graph, so that it can outlive
+     * the scope it was created in.
+     */
+    pub fn new_shared(graph: Rc<G>) -> Self {
+        Self::with_weights_shared(graph, |_, _| 1)
+    }
+}
+
+impl<'a, G: WeightedGraph> Dijkstra<'a, G, G::Weight>
+where
+    G::Weight: Measure,
+{
+    /**
+     * Creates a search minimizing the weights stored in the graph.
+     */
+    pub fn weighted(graph: &'a G) -> Self {
+        Self::with_weights(graph, |f, t| {
+            graph
+                .edge_weight(f, t)
+                .expect("adjacent nodes are joined by an edge")
+        })
+    }
+}
+
+impl<'a, G: ImplicitGraph, W: Measure> Dijkstra<'a, G, W> {
+    /**
+     * Creates a search where the edge `(f, t)` costs `weight(f, t)`.
+     */
+    pub fn with_weights<F: Fn(G::Index, G::Index) -> W + 'a>(graph: &'a G, weight: F) -> Self {
+        Self::from_handle(GraphHandle::Borrowed(graph), weight)
+    }
+
+    /**
+     * Like [`Dijkstra::with_weights`], owning a shared reference to the graph.
+     */
+    pub fn with_weights_shared<F: Fn(G::Index, G::Index) -> W + 'a>(
+        graph: Rc<G>,
+        weight: F,
+    ) -> Self {
+        Self::from_handle(GraphHandle::Shared(graph), weight)
+    }
+
+    fn from_handle<F: Fn(G::Index, G::Index) -> W + 'a>(
+        graph: GraphHandle<'a, G>,
+        weight: F,
+    ) -> Self {
+        Self {
+            graph,
+            weight: Box::new(weight),
+            edge_filter: None,
+            node_filter: None,
+        }
+    }
+
+    /**
+     * Restricts the search to the edges `(f, t)` for which `filter` returns `true`.
+     */
+    pub fn with_edge_filter<F: Fn(G::Index, G::Index) -> bool + 'a>(mut self, filter: F) -> Self {
+        self.edge_filter = Some(Box::new(filter));
+        self
+    }
+
+    /**
+     * Restricts the search to the nodes for which `filter` returns `true`,
+     * including `from` and `to`.
+     */
+    pub fn with_node_filter<F: Fn(G::Index) -> bool + 'a>(mut self, filter: F) -> Self {
+        self.node_filter = Some(Box::new(filter));
+        self
     }
 }
 
-impl<'a, I: Hash + Eq + Copy, G: Graph<Index = I>> Algorithm<G> for Dijkstra<'a, G> {
-    fn run(&self, from: G::Index, to: G::Index) -> Option<Vec<G::Index>> {
-        let mut preds = HashMap::with_capacity(self.graph.node_count());
-        let mut heap = BinaryHeap::<NodeWithDist<G>>::with_capacity(self.graph.node_count());
+impl<'a, I: Hash + Ord + Copy, G: ImplicitGraph<Index = I>, W: Measure> Dijkstra<'a, G, W> {
+    /**
+     * Searches a path between `from` and `to`, returning it along with its cost.
+     */
+    pub fn run_with_cost(&self, from: I, to: I) -> Option<CostedPath<I, W>> {
+        let (preds, dists, _) =
+            self.search(&[from], |n, _| n == to, &mut |_| ControlFlow::Continue(()));
+        let cost = *dists.get(&to)?;
+        Some(CostedPath {
+            path: Self::path_to(&preds, from, to),
+            cost,
+        })
+    }
+
+    /**
+     * Searches the path from `from` to the nearest of `targets`, returning the target
+     * reached along with the path and its cost.
+     *
+     * If `from` is itself a target, it is returned with a path made of `from` only.
+     * With the unit weights of [`Dijkstra::new`] the nearest target is the one with
+     * the fewest edges away, as in a breadth-first search.
+     */
+    pub fn run_to_any(&self, from: I, targets: &HashSet<I>) -> Option<(I, CostedPath<I, W>)> {
+        let (preds, dists, res) = self.search(&[from], |n, _| targets.contains(&n), &mut |_| {
+            ControlFlow::Continue(())
+        });
+        let target = res.ok().flatten()?;
+        Some((
+            target,
+            CostedPath {
+                path: Self::path_to(&preds, from, target),
+                cost: dists[&target],
+            },
+        ))
+    }
+
+    /**
+     * Computes the shortest paths from `from` to every node it can reach.
+     */
+    pub fn run_all(&self, from: I) -> ShortestPathTree<I, W> {
+        self.run_from_many([from])
+    }
+
+    /**
+     * Computes the shortest path to every node from the nearest of `sources`, which
+     * form the roots of the (possibly disconnected) tree.
+     *
+     * [`ShortestPathTree::nearest_source`] tells which source each node is assigned to,
+     * partitioning the reachable nodes into Voronoi-like regions.
+     */
+    pub fn run_from_many<S: IntoIterator<Item = I>>(&self, sources: S) -> ShortestPathTree<I, W> {
+        let sources: Vec<_> = sources.into_iter().collect();
+        let (preds, dists, _) =
+            self.search(&sources, |_, _| false, &mut |_| ControlFlow::Continue(()));
+        ShortestPathTree::new(sources, preds, dists)
+    }
+
+    /**
+     * Computes the shortest paths from `from` to every node at distance at most
+     * `radius`, settling only those nodes.
+     */
+    pub fn run_within(&self, from: I, radius: W) -> ShortestPathTree<I, W> {
+        let (mut preds, mut dists, _) = self.search(&[from], |_, d| d > radius, &mut |_| {
+            ControlFlow::Continue(())
+        });
+        // nodes are settled by increasing distance, so the ones within the radius have
+        // their final distance, and the others were only discovered
+        dists.retain(|_, d| *d <= radius);
+        preds.retain(|n, _| dists.contains_key(n));
+        ShortestPathTree::new(vec![from], preds, dists)
+    }
 
-        for node in self.graph.iter_nodes() {
-            let cost = if node == from { 0 } else { std::u32::MAX };
-            heap.push(NodeWithDist(node, cost));
-            preds.insert(node, (None, cost));
+    /**
+     * Settles nodes until one satisfying `stop` with its distance is reached, returning the predecessor
+     * and distance maps along with the node stopping the search, if any, or, if the
+     * search was cancelled, the last settled node.
+     */
+    fn search<S: Fn(I, W) -> bool>(
+        &self,
+        sources: &[I],
+        stop: S,
+        control: &mut Control<I>,
+    ) -> Search<I, W> {
+        // nodes are pushed lazily when first discovered or improved; entries for
+        // nodes already settled are stale and skipped when popped
+        let mut dists = HashMap::new();
+        let mut preds = HashMap::new();
+        let mut settled = HashSet::new();
+        let mut heap = BinaryHeap::new();
+        let mut last = None;
+        let mut stats = SearchStats::new("dijkstra");
+
+        let allowed_node = |n| self.node_filter.as_ref().is_none_or(|f| f(n));
+        let allowed_edge = |f, t| self.edge_filter.as_ref().is_none_or(|e| e(f, t));
+        for &source in sources {
+            if allowed_node(source) {
+                dists.insert(source, W::zero());
+                heap.push(NodeWithDist(source, W::zero()));
+            }
         }
 
         while let Some(NodeWithDist(node, cost)) = heap.pop() {
-            if cost == std::u32::MAX || node == to {
-                break;
+            if !settled.insert(node) {
+                continue;
+            }
+            if control(&node).is_break() {
+                return (preds, dists, Err(last.unwrap_or(node)));
             }
+            last = Some(node);
+            if stop(node, cost) {
+                return (preds, dists, Ok(Some(node)));
+            }
+            stats.expand(heap.len());
 
-            self.graph
-                .iter_adj(node)
-                .unwrap_or_else(|| Box::new(iter::empty()))
-                .for_each(|adj| {
-                    let (_, node_dist) = preds[&node];
-                    let (_, adj_dist) = preds[&adj];
-                    let alt = node_dist + 1;
-                    if alt < adj_dist {
-                        *preds.get_mut(&adj).unwrap() = (Some(node), alt);
-                        heap.push(NodeWithDist(adj, alt));
-                    }
-                });
-        }
+            for adj in self.graph.successors(node) {
+                if !allowed_edge(node, adj) || !allowed_node(adj) {
+                    continue;
+                }
 
-        let not_found_path = matches!(preds.get(&to), None | Some((None, _)));
-        if not_found_path {
-            return None;
+                // settled nodes never improve, so no need to check them explicitly
+                let alt = cost.combine((self.weight)(node, adj));
+                if dists.get(&adj).is_none_or(|&d| alt < d) {
+                    stats.relax();
+                    dists.insert(adj, alt);
+                    preds.insert(adj, node);
+                    heap.push(NodeWithDist(adj, alt));
+                }
+            }
         }
 
+        (preds, dists, Ok(None))
+    }
+
+    /**
+     * Returns the path from `from` to `to`, which must have been reached by the search;
+     * the path from a node to itself is that node alone.
+     */
+    fn path_to(preds: &HashMap<I, I>, from: I, to: I) -> Vec<I> {
         let mut ret = Vec::new();
         let mut cur = to;
 
         while cur != from {
             ret.push(cur);
-            cur = preds[&cur].0.unwrap();
+            cur = preds[&cur];
         }
 
         ret.push(from);
         ret.reverse();
-        Some(ret)
+        ret
+    }
+}
+
+impl<'a, I: Hash + Ord + Copy, G: ImplicitGraph<Index = I>, W: Measure> Algorithm<G>
+    for Dijkstra<'a, G, W>
+{
+    fn run_with_control(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        control: &mut Control<G::Index>,
+    ) -> Result<Option<Vec<G::Index>>, Cancelled> {
+        let (preds, _, res) = self.search(&[from], |n, _| n == to, control);
+        let found = res.map_err(|_| Cancelled)?;
+        Ok(found.map(|to| Self::path_to(&preds, from, to)))
+    }
+
+    /**
+     * When the budget runs out, the partial path leads to the last settled node,
+     * which is the costliest to reach from `from` among the settled ones.
+     */
+    fn run_with_budget(
+        &self,
+        from: G::Index,
+        to: G::Index,
+        budget: Budget,
+    ) -> BudgetedPath<G::Index> {
+        let (preds, _, res) = self.search(&[from], |n, _| n == to, &mut budget.start());
+        match res {
+            Ok(found) => BudgetedPath {
+                path: found.map(|to| Self::path_to(&preds, from, to)),
+                exhausted: false,
+            },
+            Err(last) => BudgetedPath {
+                path: Some(Self::path_to(&preds, from, last)),
+                exhausted: true,
+            },
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Algorithm, Dijkstra};
-    use crate::{algorithms::test_utils::slice_equal, graph::Graph, impls::adj_list::AdjListGraph};
-    use std::hash::Hash;
+    use super::{Algorithm, Budget, Cancelled, Dijkstra};
+    use crate::{
+        algorithms::{test_utils::slice_equal, Distance},
+        graph::{Graph, GraphRead, WeightedGraph},
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+    use std::{hash::Hash, ops::ControlFlow, rc::Rc};
 
-    fn dijkstra<I: Hash + Eq + Copy, G: Graph<Index = I>>(
+    fn dijkstra<I: Hash + Ord + Copy, G: GraphRead<Index = I>>(
         g: &G,
         from: I,
         to: I,
@@ -161,4 +401,311 @@ mod tests {
         let p = dijkstra(&g, id1, id5);
         assert!(p.is_none());
     }
+
+    #[test]
+    fn reports_settled_nodes_and_can_be_cancelled() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+
+        let mut settled = 0;
+        let p = Dijkstra::new(&g).run_with_control(id1, id3, &mut |_| {
+            settled += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(p.map(|p| p.map(|p| p.len())), Ok(Some(3)));
+        assert_eq!(settled, 3);
+
+        let p = Dijkstra::new(&g).run_with_control(id1, id3, &mut |_| ControlFlow::Break(()));
+        assert_eq!(p, Err(Cancelled));
+    }
+
+    #[test]
+    fn returns_partial_path_when_budget_runs_out() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id4);
+
+        let res = Dijkstra::new(&g).run_with_budget(id1, id4, Budget::expansions(3));
+        assert!(res.exhausted);
+        assert!(slice_equal(&res.path.unwrap(), &[id1, id2, id3]));
+
+        let res = Dijkstra::new(&g).run_with_budget(id1, id4, Budget::expansions(4));
+        assert!(!res.exhausted);
+        assert!(slice_equal(&res.path.unwrap(), &[id1, id2, id3, id4]));
+    }
+
+    #[test]
+    fn works_after_compaction() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_edge(id1, id3);
+        g.add_edge(id3, id4);
+        g.remove_node(id2);
+
+        let mapping = g.compact();
+        let p = dijkstra(&g, mapping[&id1], mapping[&id4]);
+        assert!(slice_equal(
+            &p.unwrap(),
+            &[mapping[&id1], mapping[&id3], mapping[&id4]]
+        ));
+    }
+
+    #[test]
+    fn respects_filters() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id4);
+        g.add_edge(id1, id3);
+        g.add_edge(id3, id4);
+        g.add_edge(id1, id4);
+
+        let p = Dijkstra::new(&g)
+            .with_edge_filter(|f, t| (f, t) != (id1, id4))
+            .with_node_filter(|n| n != id2)
+            .run(id1, id4);
+        assert!(slice_equal(&p.unwrap(), &[id1, id3, id4]));
+
+        let p = Dijkstra::new(&g)
+            .with_edge_filter(|f, _| f != id1)
+            .run(id1, id4);
+        assert!(p.is_none());
+
+        let p = Dijkstra::new(&g)
+            .with_node_filter(|n| n != id1)
+            .run(id1, id4);
+        assert!(p.is_none());
+    }
+
+    #[test]
+    fn minimizes_graph_weights() {
+        let mut g = UndirectedWeightedGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_weighted_edge(id1, id4, 10u32);
+        g.add_weighted_edge(id1, id2, 2);
+        g.add_weighted_edge(id2, id3, 3);
+        g.add_weighted_edge(id3, id4, 4);
+
+        let p = Dijkstra::weighted(&g).run(id4, id1);
+        assert!(slice_equal(&p.unwrap(), &[id4, id3, id2, id1]));
+
+        let p = Dijkstra::weighted(&g).run_with_cost(id1, id4).unwrap();
+        assert_eq!(p.cost, 9);
+        assert_eq!(p.path_only(), vec![id1, id2, id3, id4]);
+        let p = Dijkstra::weighted(&g).run_with_cost(id1, id1).unwrap();
+        assert_eq!((p.path, p.cost), (vec![id1], 0));
+    }
+
+    #[test]
+    fn builds_shortest_path_tree() {
+        let mut g = UndirectedWeightedGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+        g.add_weighted_edge(id1, id2, 4u32);
+        g.add_weighted_edge(id1, id3, 1);
+        g.add_weighted_edge(id3, id2, 2);
+
+        let tree = Dijkstra::weighted(&g).run_all(id1);
+        assert_eq!(tree.sources(), [id1]);
+        assert_eq!(tree.distance_to(id2), Some(3));
+        assert_eq!(tree.distance_to(id4), None);
+        assert_eq!(tree.path_to(id2), Some(vec![id1, id3, id2]));
+        assert_eq!(tree.path_to(id1), Some(vec![id1]));
+        assert_eq!(tree.path_to(id4), None);
+
+        let mut reachable: Vec<_> = tree.iter_reachable().collect();
+        reachable.sort();
+        assert_eq!(reachable, vec![(id1, 0), (id2, 3), (id3, 1)]);
+    }
+
+    #[test]
+    fn stops_at_radius() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 2u32);
+        g.add_weighted_edge(ids[1], ids[2], 2);
+        g.add_weighted_edge(ids[0], ids[3], 5);
+
+        let tree = Dijkstra::weighted(&g).run_within(ids[0], 4);
+        let mut reachable: Vec<_> = tree.iter_reachable().collect();
+        reachable.sort();
+        assert_eq!(reachable, vec![(ids[0], 0), (ids[1], 2), (ids[2], 4)]);
+        assert_eq!(tree.path_to(ids[3]), None);
+        assert_eq!(Dijkstra::weighted(&g).run_within(ids[0], 0).len(), 1);
+    }
+
+    #[test]
+    fn finds_nearest_target() {
+        let mut g = UndirectedWeightedGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+        g.add_weighted_edge(id1, id2, 5u32);
+        g.add_weighted_edge(id1, id3, 2);
+        g.add_weighted_edge(id3, id4, 2);
+
+        let search = Dijkstra::weighted(&g);
+        let (target, p) = search.run_to_any(id1, &[id2, id4].into()).unwrap();
+        assert_eq!((target, p.cost), (id4, 4));
+        assert_eq!(p.path, vec![id1, id3, id4]);
+
+        let (target, p) = search.run_to_any(id2, &[id2, id4].into()).unwrap();
+        assert_eq!((target, p.cost, p.path), (id2, 0, vec![id2]));
+
+        let search = Dijkstra::weighted(&g).with_node_filter(|n| n != id3);
+        assert_eq!(search.run_to_any(id1, &[id4].into()), None);
+        let search = Dijkstra::weighted(&g).with_node_filter(|n| n != id4);
+        assert_eq!(search.run_to_any(id4, &[id4].into()), None);
+    }
+
+    #[test]
+    fn assigns_nodes_to_nearest_source() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..6).map(|_| g.add_node()).collect();
+        for (i, w) in ids.windows(2).enumerate() {
+            g.add_weighted_edge(w[0], w[1], 1 + i as u32);
+        }
+
+        let tree = Dijkstra::weighted(&g).run_from_many([ids[0], ids[5], ids[0]]);
+        let nearest: Vec<_> = ids.iter().map(|&n| tree.nearest_source(n)).collect();
+        let expected = [0, 0, 0, 0, 5, 5].map(|i| Some(ids[i]));
+        assert_eq!(nearest, expected);
+        assert_eq!(tree.distance_to(ids[3]), Some(6));
+        assert_eq!(tree.distance_to(ids[4]), Some(5));
+        assert_eq!(tree.path_to(ids[4]), Some(vec![ids[5], ids[4]]));
+        assert_eq!(tree.regions()[&ids[2]], ids[0]);
+
+        let tree = Dijkstra::weighted(&g).run_from_many([]);
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn minimizes_lexicographic_measures() {
+        let mut g = UndirectedWeightedGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+        let id5 = g.add_node();
+        let id6 = g.add_node();
+
+        // two 2-hop paths with different weights, and a lighter 3-hop one
+        g.add_weighted_edge(id1, id2, 5u32);
+        g.add_weighted_edge(id2, id6, 5);
+        g.add_weighted_edge(id1, id3, 1);
+        g.add_weighted_edge(id3, id6, 6);
+        g.add_weighted_edge(id1, id4, 1);
+        g.add_weighted_edge(id4, id5, 1);
+        g.add_weighted_edge(id5, id6, 1);
+
+        let hops_then_weight =
+            Dijkstra::with_weights(&g, |f, t| (1u32, g.edge_weight(f, t).unwrap()));
+        let p = hops_then_weight.run(id1, id6);
+        assert!(slice_equal(&p.unwrap(), &[id1, id3, id6]));
+
+        let p = Dijkstra::weighted(&g).run(id1, id6);
+        assert!(slice_equal(&p.unwrap(), &[id1, id4, id5, id6]));
+    }
+
+    #[test]
+    fn handles_near_max_weights() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id4);
+        g.add_edge(id1, id3);
+        g.add_edge(id3, id4);
+
+        // the path through id3 overflows, and must not wrap around to a small cost
+        let weight = move |f, t| match (f, t) {
+            (f, _) if f == id1 => u32::MAX - 10,
+            (f, _) if f == id2 => 5,
+            _ => 20,
+        };
+        let p = Dijkstra::with_weights(&g, weight).run(id1, id4);
+        assert!(slice_equal(&p.unwrap(), &[id1, id2, id4]));
+
+        let p =
+            Dijkstra::with_weights(&g, move |f, t| Distance::Finite(weight(f, t))).run(id1, id4);
+        assert!(slice_equal(&p.unwrap(), &[id1, id2, id4]));
+
+        // a path whose only route overflows is still found
+        g.remove_edge(id2, id4);
+        let p = Dijkstra::with_weights(&g, weight).run(id1, id4);
+        assert!(slice_equal(&p.unwrap(), &[id1, id3, id4]));
+    }
+
+    #[test]
+    fn shared_search_outlives_graph_scope() {
+        struct Router {
+            search: Dijkstra<'static, AdjListGraph>,
+        }
+
+        let (router, id1, id3) = {
+            let mut g = AdjListGraph::new();
+            let id1 = g.add_node();
+            let id2 = g.add_node();
+            let id3 = g.add_node();
+            g.add_edge(id1, id2);
+            g.add_edge(id2, id3);
+            let router = Router {
+                search: Dijkstra::new_shared(Rc::new(g)),
+            };
+            (router, id1, id3)
+        };
+
+        assert_eq!(router.search.run(id1, id3).map(|p| p.len()), Some(3));
+    }
+
+    #[test]
+    fn breaks_ties_by_index() {
+        // many equally short routes through the middle layer, added in shuffled order
+        for rotation in 0..5 {
+            let mut g = AdjListGraph::new();
+            let from = g.add_node();
+            let middle: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+            let to = g.add_node();
+            for i in 0..5 {
+                let m = middle[(i + rotation) % 5];
+                g.add_edge(from, m);
+                g.add_edge(m, to);
+            }
+
+            let p = Dijkstra::new(&g).run(from, to);
+            assert_eq!(p, Some(vec![from, middle[0], to]));
+            let targets = middle[2..].iter().copied().collect();
+            let (nearest, _) = Dijkstra::new(&g).run_to_any(from, &targets).unwrap();
+            assert_eq!(nearest, middle[2]);
+        }
+    }
 }
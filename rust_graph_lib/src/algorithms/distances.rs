@@ -0,0 +1,112 @@
+use super::{Dijkstra, Measure};
+use crate::graph::ImplicitGraph;
+use std::hash::Hash;
+
+/**
+ * Shortest path distances between the nodes of a list, returned by [`pairwise_distances`].
+ *
+ * The distances are stored row by row in a single vector, with the nodes referred to
+ * by their position in the list.
+ */
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DistanceMatrix<W> {
+    size: usize,
+    dists: Vec<Option<W>>,
+}
+
+impl<W: Copy> DistanceMatrix<W> {
+    /**
+     * Returns the number of nodes, which is the number of rows and of columns.
+     */
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /**
+     * Returns the distance from the `i`-th node to the `j`-th, or `None` if there is
+     * no path between them.
+     *
+     * # Panics
+     *
+     * Panics if `i` or `j` is not less than [`size`](DistanceMatrix::size).
+     */
+    pub fn get(&self, i: usize, j: usize) -> Option<W> {
+        assert!(i < self.size && j < self.size, "position out of bounds");
+        self.dists[i * self.size + j]
+    }
+
+    /**
+     * Returns the distances from the `i`-th node to all the others.
+     */
+    pub fn row(&self, i: usize) -> &[Option<W>] {
+        &self.dists[i * self.size..(i + 1) * self.size]
+    }
+}
+
+/**
+ * Computes the shortest path distances between every pair of `nodes`, where the edge
+ * `(f, t)` costs `weight(f, t)`.
+ *
+ * Runs one [`Dijkstra`] search from each of the `k` nodes, in `O(k (m + n log n))`
+ * time and `O(k²)` memory for the result, which is much cheaper than computing all the
+ * pairs of the graph when only a few nodes matter. Nodes not in the graph are only at
+ * distance zero from themselves.
+ */
+pub fn pairwise_distances<G, W, F>(graph: &G, nodes: &[G::Index], weight: F) -> DistanceMatrix<W>
+where
+    G: ImplicitGraph,
+    G::Index: Hash + Copy,
+    W: Measure,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let search = Dijkstra::with_weights(graph, weight);
+    let mut dists = Vec::with_capacity(nodes.len() * nodes.len());
+    for &from in nodes {
+        let tree = search.run_all(from);
+        dists.extend(nodes.iter().map(|&to| tree.distance_to(to)));
+    }
+    DistanceMatrix {
+        size: nodes.len(),
+        dists,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, WeightedGraph},
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_pairwise_distances() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 4u32);
+        g.add_weighted_edge(ids[1], ids[2], 1);
+        g.add_weighted_edge(ids[0], ids[2], 6);
+        g.add_weighted_edge(ids[2], ids[3], 2);
+
+        let selected = [ids[0], ids[3], ids[4]];
+        let m = pairwise_distances(&g, &selected, |f, t| g.edge_weight(f, t).unwrap());
+        assert_eq!(m.size(), 3);
+        assert_eq!(m.get(0, 1), Some(7));
+        assert_eq!(m.get(1, 0), Some(7));
+        assert_eq!(m.get(2, 2), Some(0));
+        assert_eq!(m.row(0), [Some(0), Some(7), None]);
+    }
+
+    #[test]
+    fn test_directed_hops() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+
+        let m = pairwise_distances(&g, &[ids[2], ids[0]], |_, _| 1u32);
+        assert_eq!(m.row(0), [Some(0), None]);
+        assert_eq!(m.row(1), [Some(2), Some(0)]);
+        assert_eq!(pairwise_distances(&g, &[], |_, _| 1u32).size(), 0);
+    }
+}
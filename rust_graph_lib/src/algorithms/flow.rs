@@ -0,0 +1,667 @@
+use super::{DenseIndex, Measure};
+use crate::graph::GraphRead;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Sub,
+};
+
+/**
+ * Residual network over dense node positions, with each edge stored next to its reverse.
+ */
+struct Network<W> {
+    adj: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    cap: Vec<W>,
+}
+
+impl<W: Measure + Sub<Output = W>> Network<W> {
+    fn new(n: usize) -> Self {
+        Network {
+            adj: vec![Vec::new(); n],
+            to: Vec::new(),
+            cap: Vec::new(),
+        }
+    }
+
+    /**
+     * Adds an edge from `u` to `v` with capacity `forward` and a reverse one with
+     * capacity `backward`, returning the id of the forward edge.
+     */
+    fn add_edge(&mut self, u: usize, v: usize, forward: W, backward: W) -> usize {
+        let id = self.to.len();
+        self.adj[u].push(id);
+        self.to.push(v);
+        self.cap.push(forward);
+        self.adj[v].push(id + 1);
+        self.to.push(u);
+        self.cap.push(backward);
+        id
+    }
+
+    fn levels(&self, s: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.adj.len()];
+        level[s] = Some(0);
+        let mut queue = vec![s];
+        let mut head = 0;
+        while head < queue.len() {
+            let u = queue[head];
+            head += 1;
+            for &e in &self.adj[u] {
+                let v = self.to[e];
+                if level[v].is_none() && self.cap[e] > W::zero() {
+                    level[v] = level[u].map(|l| l + 1);
+                    queue.push(v);
+                }
+            }
+        }
+        level
+    }
+
+    /**
+     * Finds a path from `s` to `t` along the level graph and pushes as much flow as
+     * it takes, up to `limit`, returning the amount pushed.
+     *
+     * The search keeps the edges of the current path on an explicit stack rather than
+     * recursing, so long paths cannot overflow the call stack.
+     */
+    fn augment(
+        &mut self,
+        s: usize,
+        t: usize,
+        limit: W,
+        level: &[Option<usize>],
+        next: &mut [usize],
+    ) -> W {
+        let mut path: Vec<usize> = Vec::new();
+        let mut u = s;
+        loop {
+            if u == t {
+                let pushed = path.iter().fold(limit, |m, &e| m.min(self.cap[e]));
+                for &e in &path {
+                    self.cap[e] = self.cap[e] - pushed;
+                    self.cap[e ^ 1] = self.cap[e ^ 1].combine(pushed);
+                }
+                return pushed;
+            }
+
+            let step = self.adj[u][next[u]..].iter().position(|&e| {
+                self.cap[e] > W::zero() && level[self.to[e]] == level[u].map(|l| l + 1)
+            });
+            match step {
+                Some(skipped) => {
+                    next[u] += skipped;
+                    let e = self.adj[u][next[u]];
+                    path.push(e);
+                    u = self.to[e];
+                }
+                None => {
+                    next[u] = self.adj[u].len();
+                    // dead end: retreat, skipping the edge that led here
+                    let Some(e) = path.pop() else {
+                        return W::zero();
+                    };
+                    u = self.to[e ^ 1];
+                    next[u] += 1;
+                }
+            }
+        }
+    }
+
+    /**
+     * Pushes as much flow as possible from `s` to `t` with Dinic's algorithm,
+     * returning the amount pushed.
+     */
+    fn max_flow(&mut self, s: usize, t: usize, unbounded: W) -> W {
+        let mut total = W::zero();
+        if s == t {
+            return total;
+        }
+        loop {
+            let level = self.levels(s);
+            if level[t].is_none() {
+                return total;
+            }
+            let mut next = vec![0; self.adj.len()];
+            loop {
+                let pushed = self.augment(s, t, unbounded, &level, &mut next);
+                if pushed == W::zero() {
+                    break;
+                }
+                total = total.combine(pushed);
+            }
+        }
+    }
+
+    /**
+     * Returns which positions are reachable from `s` in the residual network.
+     */
+    fn source_side(&self, s: usize) -> Vec<bool> {
+        self.levels(s).iter().map(Option::is_some).collect()
+    }
+}
+
+/**
+ * Maximum flow between two nodes, returned by [`max_flow`].
+ */
+#[derive(Clone, Debug)]
+pub struct MaxFlow<I, W> {
+    /**
+     * The amount of flow from the source to the sink.
+     */
+    pub value: W,
+    /**
+     * The flow on each edge carrying some.
+     */
+    pub flows: HashMap<(I, I), W>,
+    /**
+     * The nodes on the source side of a minimum cut: the edges leaving this set are
+     * saturated and their capacities sum to `value`.
+     */
+    pub source_side: HashSet<I>,
+}
+
+/**
+ * Computes a maximum flow from `source` to `sink`, where the edge `(f, t)` has
+ * capacity `capacity(f, t)`, with Dinic's algorithm in `O(n^2 m)` time.
+ *
+ * If either node is not in the graph, or they are the same, the flow is empty.
+ * The capacities must sum to a representable value, which is used as unbounded.
+ */
+pub fn max_flow<G, W, F>(
+    graph: &G,
+    source: G::Index,
+    sink: G::Index,
+    capacity: F,
+) -> MaxFlow<G::Index, W>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    W: Measure + Sub<Output = W>,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let index = DenseIndex::new(graph);
+    let mut network = Network::new(index.len());
+    let mut total = W::zero();
+    let mut edges = Vec::new();
+    for (u, v) in index.edges(graph) {
+        let (f, t) = (index.node(u), index.node(v));
+        let c = capacity(f, t);
+        total = total.combine(c);
+        edges.push((f, t, c, network.add_edge(u, v, c, W::zero())));
+    }
+
+    let (Some(s), Some(t)) = (index.position(source), index.position(sink)) else {
+        return MaxFlow {
+            value: W::zero(),
+            flows: HashMap::new(),
+            source_side: HashSet::new(),
+        };
+    };
+    let value = network.max_flow(s, t, total);
+
+    let mut flows = HashMap::new();
+    for (f, t, c, id) in edges {
+        let flow = c - network.cap[id];
+        if flow > W::zero() {
+            let e = flows.entry((f, t)).or_insert_with(W::zero);
+            *e = e.combine(flow);
+        }
+    }
+    let side = network.source_side(s);
+    MaxFlow {
+        value,
+        flows,
+        source_side: index
+            .nodes()
+            .iter()
+            .zip(side)
+            .filter(|(_, s)| *s)
+            .map(|(&n, _)| n)
+            .collect(),
+    }
+}
+
+/**
+ * Returns a minimum set of nodes, other than `source` and `sink`, whose removal leaves
+ * no path from `source` to `sink`.
+ *
+ * Each node is split into an entry and an exit joined by an edge of capacity 1, so that
+ * a minimum cut of the split graph only crosses such edges. Returns `None` if the nodes
+ * are the same, not in the graph, or joined by an edge, since no set of other nodes can
+ * separate them; the set is empty if `sink` is not reachable at all.
+ */
+pub fn min_vertex_cut<G>(graph: &G, source: G::Index, sink: G::Index) -> Option<HashSet<G::Index>>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let (s, t) = (index.position(source)?, index.position(sink)?);
+    if s == t || graph.has_edge(source, sink) {
+        return None;
+    }
+
+    // more than any cut, which takes at most all the nodes
+    let unbounded = index.len() + 1;
+    let mut network = Network::new(2 * index.len());
+    for (i, &n) in index.nodes().iter().enumerate() {
+        let inner = if n == source || n == sink {
+            unbounded
+        } else {
+            1
+        };
+        network.add_edge(2 * i, 2 * i + 1, inner, 0);
+    }
+    for (u, v) in index.edges(graph) {
+        network.add_edge(2 * u + 1, 2 * v, unbounded, 0);
+    }
+
+    network.max_flow(2 * s + 1, 2 * t, unbounded);
+    let side = network.source_side(2 * s + 1);
+    Some(
+        index
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| side[2 * i] && !side[2 * i + 1])
+            .map(|(_, &n)| n)
+            .collect(),
+    )
+}
+
+/**
+ * Returns the undirected edges of a graph, once per unordered pair of adjacent
+ * positions, with the capacity of the first direction found.
+ */
+fn undirected_edges<G, W, F>(
+    graph: &G,
+    index: &DenseIndex<G::Index>,
+    capacity: F,
+) -> Vec<(usize, usize, W)>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let mut seen = HashSet::new();
+    index
+        .edges(graph)
+        .filter(|&(u, v)| u != v && seen.insert((u.min(v), u.max(v))))
+        .map(|(u, v)| (u, v, capacity(index.node(u), index.node(v))))
+        .collect()
+}
+
+/**
+ * Gomory–Hu tree of an undirected graph, returned by [`gomory_hu_tree`].
+ *
+ * The minimum cut between any two nodes of the graph equals the smallest weight on the
+ * path joining them in the tree, and removing that tree edge splits the nodes into the
+ * two sides of such a cut.
+ */
+#[derive(Clone, Debug)]
+pub struct GomoryHuTree<I, W> {
+    index: DenseIndex<I>,
+    parent: Vec<usize>,
+    weight: Vec<W>,
+}
+
+impl<I: Hash + Eq + Copy, W: Measure> GomoryHuTree<I, W> {
+    /**
+     * Iterates over the `n - 1` edges of the tree as `(node, parent, weight)`.
+     */
+    pub fn edges(&self) -> impl Iterator<Item = (I, I, W)> + '_ {
+        (1..self.index.len()).map(|i| {
+            let parent = self.index.node(self.parent[i]);
+            (self.index.node(i), parent, self.weight[i])
+        })
+    }
+
+    /**
+     * Returns the weight of a minimum cut between `a` and `b`, or `None` if they are
+     * the same node or not in the graph.
+     */
+    pub fn min_cut(&self, a: I, b: I) -> Option<W> {
+        let (a, b) = (self.index.position(a)?, self.index.position(b)?);
+        if a == b {
+            return None;
+        }
+
+        // the weights on the way from `a` to the root, then up from `b` to the first shared node
+        let mut up = HashMap::new();
+        let mut best = None::<W>;
+        let mut cur = a;
+        up.insert(cur, best);
+        while cur != 0 {
+            best = Some(best.map_or(self.weight[cur], |w| w.min(self.weight[cur])));
+            cur = self.parent[cur];
+            up.insert(cur, best);
+        }
+
+        let mut best_b = None::<W>;
+        let mut cur = b;
+        while !up.contains_key(&cur) {
+            best_b = Some(best_b.map_or(self.weight[cur], |w| w.min(self.weight[cur])));
+            cur = self.parent[cur];
+        }
+        match (up[&cur], best_b) {
+            (Some(x), Some(y)) => Some(x.min(y)),
+            (x, y) => x.or(y),
+        }
+    }
+}
+
+/**
+ * Builds a Gomory–Hu tree of an undirected graph with `n - 1` maximum flow
+ * computations, following Gusfield's algorithm.
+ *
+ * Each unordered pair of adjacent nodes is an undirected edge of capacity
+ * `capacity(f, t)`, read once, so graphs storing both directions of each edge and
+ * graphs listing it once give the same tree. Self loops are ignored.
+ */
+pub fn gomory_hu_tree<G, W, F>(graph: &G, capacity: F) -> GomoryHuTree<G::Index, W>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    W: Measure + Sub<Output = W>,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let index = DenseIndex::new(graph);
+    let edges = undirected_edges(graph, &index, capacity);
+    let unbounded = edges.iter().fold(W::zero(), |acc, e| acc.combine(e.2));
+
+    let n = index.len();
+    let mut parent = vec![0; n];
+    let mut weight = vec![W::zero(); n];
+    for s in 1..n {
+        let t = parent[s];
+        let mut network = Network::new(n);
+        for &(u, v, c) in &edges {
+            network.add_edge(u, v, c, c);
+        }
+        let value = network.max_flow(s, t, unbounded);
+        let side = network.source_side(s);
+
+        weight[s] = value;
+        for i in 0..n {
+            if i != s && side[i] && parent[i] == t {
+                parent[i] = s;
+            }
+        }
+        // keeps the tree a cut tree when the parent of `t` ends up on the side of `s`
+        if side[parent[t]] {
+            parent[s] = parent[t];
+            parent[t] = s;
+            weight[s] = weight[t];
+            weight[t] = value;
+        }
+    }
+
+    GomoryHuTree {
+        index,
+        parent,
+        weight,
+    }
+}
+
+/**
+ * Computes a global minimum cut of an undirected graph with the Stoer–Wagner algorithm,
+ * in `O(n^3)` time, returning its weight and the nodes on one side of it.
+ *
+ * Edges are read as in [`gomory_hu_tree`]. Returns `None` if the graph has fewer than
+ * two nodes; a disconnected graph has a cut of weight zero around one of its components.
+ */
+pub fn stoer_wagner<G, W, F>(graph: &G, capacity: F) -> Option<(W, HashSet<G::Index>)>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    W: Measure,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    let index = DenseIndex::new(graph);
+    let n = index.len();
+    if n < 2 {
+        return None;
+    }
+
+    let mut weights = vec![vec![W::zero(); n]; n];
+    for (u, v, c) in undirected_edges(graph, &index, capacity) {
+        weights[u][v] = weights[u][v].combine(c);
+        weights[v][u] = weights[v][u].combine(c);
+    }
+    // the original positions merged into each remaining one
+    let mut merged: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut alive: Vec<usize> = (0..n).collect();
+    let mut best: Option<(W, Vec<usize>)> = None;
+
+    while alive.len() > 1 {
+        // maximum adjacency ordering: repeatedly adds the node most connected to the added ones
+        let mut added = vec![false; n];
+        let mut connection = vec![W::zero(); n];
+        let (mut prev, mut last) = (alive[0], alive[0]);
+        for _ in 0..alive.len() {
+            let next = alive
+                .iter()
+                .copied()
+                .filter(|&v| !added[v])
+                .max_by(|&a, &b| connection[a].cmp(&connection[b]).then(b.cmp(&a)))
+                .unwrap();
+            added[next] = true;
+            prev = last;
+            last = next;
+            for &v in &alive {
+                if !added[v] {
+                    connection[v] = connection[v].combine(weights[next][v]);
+                }
+            }
+        }
+
+        // the last node alone is a minimum cut between it and the one added before it
+        let cut = connection[last];
+        if best.as_ref().is_none_or(|(w, _)| cut < *w) {
+            best = Some((cut, merged[last].clone()));
+        }
+
+        let absorbed = std::mem::take(&mut merged[last]);
+        merged[prev].extend(absorbed);
+
+        let absorbed = weights[last].clone();
+        for (v, w) in absorbed.into_iter().enumerate() {
+            weights[prev][v] = weights[prev][v].combine(w);
+            weights[v][prev] = weights[prev][v];
+        }
+        weights[prev][prev] = W::zero();
+        alive.retain(|&v| v != last);
+    }
+
+    best.map(|(w, side)| (w, side.into_iter().map(|i| index.node(i)).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::reachable_from,
+        graph::{EdgeIterator, Graph, NodeIterator, WeightedGraph},
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_max_flow() {
+        let mut g = AdjListGraph::new();
+        let [s, a, b, c, d, t] = [(); 6].map(|_| g.add_node());
+        let caps = HashMap::from([
+            ((s, a), 10u32),
+            ((s, c), 10),
+            ((a, b), 4),
+            ((a, c), 2),
+            ((a, d), 8),
+            ((c, d), 9),
+            ((b, t), 10),
+            ((d, b), 6),
+            ((d, t), 10),
+        ]);
+        for &(f, to) in caps.keys() {
+            g.add_edge(f, to);
+        }
+
+        let res = max_flow(&g, s, t, |f, to| caps[&(f, to)]);
+        assert_eq!(res.value, 19);
+        assert!(res.flows.iter().all(|(e, &f)| f <= caps[e]));
+        let out: u32 = res
+            .flows
+            .iter()
+            .filter(|((f, _), _)| *f == s)
+            .map(|(_, &f)| f)
+            .sum();
+        assert_eq!(out, 19);
+        let cut: u32 = caps
+            .iter()
+            .filter(|((f, to), _)| res.source_side.contains(f) && !res.source_side.contains(to))
+            .map(|(_, &c)| c)
+            .sum();
+        assert_eq!(cut, 19);
+        assert_eq!(max_flow(&g, t, s, |f, to| caps[&(f, to)]).value, 0);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..100_000).map(|_| g.add_node()).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1]);
+        }
+        let flow = max_flow(&g, ids[0], ids[ids.len() - 1], |f, _| {
+            if f == ids[500] {
+                2u32
+            } else {
+                3
+            }
+        });
+        assert_eq!(flow.value, 2);
+        assert_eq!(flow.source_side.len(), 501);
+    }
+
+    #[test]
+    fn test_gomory_hu_tree() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..7).map(|_| g.add_node()).collect();
+        let mut state = 3u64;
+        for i in 0..ids.len() {
+            for j in i + 1..ids.len() {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                if !(state >> 33).is_multiple_of(3) {
+                    g.add_weighted_edge(ids[i], ids[j], 1 + (state >> 40) as u32 % 9);
+                }
+            }
+        }
+        let isolated = g.add_node();
+
+        let capacity = |f, t| g.edge_weight(f, t).unwrap();
+        let tree = gomory_hu_tree(&g, capacity);
+        assert_eq!(tree.edges().count(), 7);
+        for &a in &ids {
+            for &b in &ids {
+                let expected = (a != b).then(|| max_flow(&Symmetric(&g), a, b, capacity).value);
+                assert_eq!(tree.min_cut(a, b), expected);
+            }
+            assert_eq!(tree.min_cut(a, isolated), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_stoer_wagner() {
+        // two triangles of heavy edges joined by two light ones
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..6).map(|_| g.add_node()).collect();
+        for (f, t, w) in [
+            (0, 1, 5),
+            (1, 2, 4),
+            (0, 2, 6),
+            (3, 4, 5),
+            (4, 5, 7),
+            (3, 5, 4),
+        ] {
+            g.add_weighted_edge(ids[f], ids[t], w);
+        }
+        g.add_weighted_edge(ids[2], ids[3], 2u32);
+        g.add_weighted_edge(ids[0], ids[5], 1);
+
+        let capacity = |f, t| g.edge_weight(f, t).unwrap();
+        let (weight, side) = stoer_wagner(&g, capacity).unwrap();
+        assert_eq!(weight, 3);
+        let left: HashSet<_> = ids[..3].iter().copied().collect();
+        let right: HashSet<_> = ids[3..].iter().copied().collect();
+        assert!(side == left || side == right);
+
+        let tree = gomory_hu_tree(&g, capacity);
+        assert_eq!(tree.edges().map(|e| e.2).min(), Some(weight));
+
+        let lonely = g.add_node();
+        let capacity = |f, t| g.edge_weight(f, t).unwrap();
+        assert_eq!(
+            stoer_wagner(&g, capacity),
+            Some((0, HashSet::from([lonely])))
+        );
+
+        let mut single = UndirectedWeightedGraph::<u32>::new();
+        single.add_node();
+        assert_eq!(stoer_wagner(&single, |_, _| 1u32), None);
+    }
+
+    /**
+     * Lists both directions of each edge of an undirected graph, for the directed flow.
+     */
+    struct Symmetric<'a, G>(&'a G);
+
+    impl<G: GraphRead> GraphRead for Symmetric<'_, G>
+    where
+        G::Index: Copy + 'static,
+    {
+        type Index = G::Index;
+
+        fn has_edge(&self, f: G::Index, t: G::Index) -> bool {
+            self.0.has_edge(f, t)
+        }
+
+        fn node_count(&self) -> usize {
+            self.0.node_count()
+        }
+
+        fn edge_count(&self) -> usize {
+            2 * self.0.edge_count()
+        }
+
+        fn iter_nodes(&self) -> Box<NodeIterator<'_, G::Index>> {
+            self.0.iter_nodes()
+        }
+
+        fn iter_adj(&self, n: G::Index) -> Option<Box<NodeIterator<'_, G::Index>>> {
+            self.0.iter_adj(n)
+        }
+
+        fn iter_edges(&self) -> Box<EdgeIterator<'_, G::Index>> {
+            Box::new(self.0.iter_edges().flat_map(|(f, t)| [(f, t), (t, f)]))
+        }
+    }
+
+    #[test]
+    fn test_min_vertex_cut() {
+        let mut g = AdjListGraph::new();
+        let [s, a, b, c, d, t] = [(); 6].map(|_| g.add_node());
+        // two routes through a shared bottleneck `c`, and a third through `d`
+        for (f, to) in [(s, a), (s, b), (a, c), (b, c), (c, t), (s, d), (d, t)] {
+            g.add_edge(f, to);
+        }
+
+        let cut = min_vertex_cut(&g, s, t).unwrap();
+        assert_eq!(cut, HashSet::from([c, d]));
+        assert_eq!(min_vertex_cut(&g, s, d), None);
+        assert_eq!(min_vertex_cut(&g, t, s), Some(HashSet::new()));
+
+        for n in cut {
+            g.remove_node(n);
+        }
+        assert!(!reachable_from(&g, s).contains(&t));
+    }
+}
@@ -0,0 +1,429 @@
+use super::DenseIndex;
+use crate::graph::GraphRead;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+fn hash_of<T: Hash>(value: T) -> u64 {
+    // the default hasher has fixed keys, so hashes are stable between runs
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/**
+ * Returns the Weisfeiler–Lehman hash of a graph, which does not depend on how the nodes
+ * are numbered, for deduplicating graphs or using them as cache keys.
+ *
+ * Each node starts with the hash of `label(n)` and, for `iterations` rounds, combines
+ * it with the sorted labels of its successors and of its predecessors. Isomorphic
+ * graphs with matching labels always have the same hash; different graphs almost
+ * always have different ones, except for some regular graphs that this refinement
+ * cannot tell apart. Use `|_| ()` for unlabeled graphs.
+ */
+pub fn weisfeiler_lehman_hash<G, L, F>(graph: &G, iterations: usize, label: F) -> u64
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    L: Hash,
+    F: Fn(G::Index) -> L,
+{
+    let index = DenseIndex::new(graph);
+    let nodes = index.nodes();
+    let mut succ = vec![Vec::new(); nodes.len()];
+    let mut pred = vec![Vec::new(); nodes.len()];
+    for (f, t) in index.edges(graph) {
+        succ[f].push(t);
+        pred[t].push(f);
+    }
+
+    let mut labels: Vec<u64> = nodes.iter().map(|&n| hash_of(label(n))).collect();
+    let mut sorted = labels.clone();
+    sorted.sort_unstable();
+    let mut histograms = vec![sorted];
+
+    for _ in 0..iterations {
+        let neighborhood = |adj: &[usize], labels: &[u64]| {
+            let mut v: Vec<_> = adj.iter().map(|&a| labels[a]).collect();
+            v.sort_unstable();
+            v
+        };
+        labels = (0..nodes.len())
+            .map(|i| {
+                hash_of((
+                    labels[i],
+                    neighborhood(&succ[i], &labels),
+                    neighborhood(&pred[i], &labels),
+                ))
+            })
+            .collect();
+
+        let mut sorted = labels.clone();
+        sorted.sort_unstable();
+        histograms.push(sorted);
+    }
+
+    hash_of((nodes.len(), graph.edge_count(), histograms))
+}
+
+/**
+ * Successors and predecessors of each position of a [`DenseIndex`], as sets so that
+ * the matcher can test adjacency in constant time.
+ */
+struct Neighborhoods<I> {
+    index: DenseIndex<I>,
+    succ: Vec<HashSet<usize>>,
+    pred: Vec<HashSet<usize>>,
+}
+
+impl<I: Hash + Ord + Copy> Neighborhoods<I> {
+    fn new<G: GraphRead<Index = I>>(graph: &G) -> Self {
+        let index = DenseIndex::new(graph);
+        let mut succ = vec![HashSet::new(); index.len()];
+        let mut pred = vec![HashSet::new(); index.len()];
+        for (f, t) in index.edges(graph) {
+            succ[f].insert(t);
+            pred[t].insert(f);
+        }
+        Neighborhoods { index, succ, pred }
+    }
+
+    fn degree(&self, p: usize) -> usize {
+        self.succ[p].len() + self.pred[p].len()
+    }
+}
+
+/**
+ * Iterator over the induced subgraph isomorphisms from a pattern graph into a target
+ * graph, returned by [`find_subgraph_isomorphisms`].
+ */
+pub struct SubgraphIsomorphisms<P, T, F> {
+    pattern: Neighborhoods<P>,
+    target: Neighborhoods<T>,
+    node_match: F,
+    // pattern positions in matching order, each after as many of its neighbors as possible
+    order: Vec<usize>,
+    core_p: Vec<Option<usize>>,
+    core_t: Vec<Option<usize>>,
+    // candidate target positions for each matched depth, with the next one to try
+    stack: Vec<(Vec<usize>, usize)>,
+    empty_pending: bool,
+}
+
+/**
+ * Iterator returned by [`find_subgraph_isomorphisms`], matching any pair of nodes.
+ */
+pub type UnlabeledIsomorphisms<P, T> = SubgraphIsomorphisms<P, T, fn(P, T) -> bool>;
+
+/**
+ * Returns an iterator over the ways to map the nodes of `pattern` to distinct nodes of
+ * `target` so that two pattern nodes are joined by an edge if and only if their images
+ * are, with the VF2 algorithm.
+ *
+ * Each mapping is yielded once, as a map from pattern nodes to target nodes, so
+ * symmetric patterns are found once per automorphism. An empty pattern has a single
+ * empty mapping. See [`find_subgraph_isomorphisms_by`] for labeled graphs.
+ *
+ * ```
+ * use rust_graph_lib::{algorithms::isomorphism::find_subgraph_isomorphisms, graph::Graph, impls::adj_list::AdjListGraph};
+ *
+ * let mut pattern = AdjListGraph::new();
+ * let [a, b] = [(); 2].map(|_| pattern.add_node());
+ * pattern.add_edge(a, b);
+ *
+ * let mut target = AdjListGraph::new();
+ * let [x, y, z] = [(); 3].map(|_| target.add_node());
+ * target.add_edge(x, y);
+ * target.add_edge(y, z);
+ * target.add_edge(z, y);
+ *
+ * // the edge between y and z goes both ways, so it does not match
+ * let found: Vec<_> = find_subgraph_isomorphisms(&pattern, &target).collect();
+ * assert_eq!(found.len(), 1);
+ * assert_eq!((found[0][&a], found[0][&b]), (x, y));
+ * ```
+ */
+pub fn find_subgraph_isomorphisms<P, T>(
+    pattern: &P,
+    target: &T,
+) -> UnlabeledIsomorphisms<P::Index, T::Index>
+where
+    P: GraphRead,
+    T: GraphRead,
+    P::Index: Hash + Copy,
+    T::Index: Hash + Copy,
+{
+    find_subgraph_isomorphisms_by(pattern, target, |_, _| true)
+}
+
+/**
+ * Like [`find_subgraph_isomorphisms`], only mapping a pattern node to a target node
+ * if `node_match` accepts them, for instance when their labels are equal.
+ */
+pub fn find_subgraph_isomorphisms_by<P, T, F>(
+    pattern: &P,
+    target: &T,
+    node_match: F,
+) -> SubgraphIsomorphisms<P::Index, T::Index, F>
+where
+    P: GraphRead,
+    T: GraphRead,
+    P::Index: Hash + Copy,
+    T::Index: Hash + Copy,
+    F: Fn(P::Index, T::Index) -> bool,
+{
+    let pattern = Neighborhoods::new(pattern);
+    let target = Neighborhoods::new(target);
+    let n = pattern.index.len();
+
+    let mut order = Vec::with_capacity(n);
+    let mut ordered = vec![false; n];
+    let mut links = vec![0; n];
+    for _ in 0..n {
+        let next = (0..n)
+            .filter(|&p| !ordered[p])
+            .max_by_key(|&p| (links[p], pattern.degree(p), usize::MAX - p))
+            .unwrap();
+        ordered[next] = true;
+        order.push(next);
+        for &q in pattern.succ[next].iter().chain(&pattern.pred[next]) {
+            links[q] += 1;
+        }
+    }
+
+    let mut it = SubgraphIsomorphisms {
+        core_p: vec![None; n],
+        core_t: vec![None; target.index.len()],
+        pattern,
+        target,
+        node_match,
+        order,
+        stack: Vec::new(),
+        empty_pending: n == 0,
+    };
+    if n > 0 && n <= it.target.index.len() {
+        let candidates = it.candidates(0);
+        it.stack.push((candidates, 0));
+    }
+    it
+}
+
+impl<P, T, F> SubgraphIsomorphisms<P, T, F>
+where
+    P: Hash + Ord + Copy,
+    T: Hash + Ord + Copy,
+    F: Fn(P, T) -> bool,
+{
+    /**
+     * Returns the target positions worth trying for the pattern position at `depth`:
+     * the unmapped neighbors of the image of an already mapped neighbor, if any.
+     */
+    fn candidates(&self, depth: usize) -> Vec<usize> {
+        let p = self.order[depth];
+        let mapped = |q: &usize| self.core_p[*q];
+        let mut candidates: Vec<_> = if let Some(t) = self.pattern.succ[p].iter().find_map(mapped) {
+            self.target.pred[t].iter().copied().collect()
+        } else if let Some(t) = self.pattern.pred[p].iter().find_map(mapped) {
+            self.target.succ[t].iter().copied().collect()
+        } else {
+            (0..self.target.index.len()).collect()
+        };
+        candidates.retain(|&t| self.core_t[t].is_none());
+        candidates.sort_unstable();
+        candidates
+    }
+
+    /**
+     * Checks whether mapping `p` to `t` keeps the partial mapping an isomorphism
+     * between the induced subgraphs, and whether it can still be completed.
+     */
+    fn feasible(&self, p: usize, t: usize) -> bool {
+        let (pattern, target) = (&self.pattern, &self.target);
+        if pattern.succ[p].len() > target.succ[t].len()
+            || pattern.pred[p].len() > target.pred[t].len()
+            || pattern.succ[p].contains(&p) != target.succ[t].contains(&t)
+            || !(self.node_match)(pattern.index.node(p), target.index.node(t))
+        {
+            return false;
+        }
+
+        // edges with the mapped nodes, checked from both sides to also match non-edges
+        for (p_adj, t_adj) in [(&pattern.succ, &target.succ), (&pattern.pred, &target.pred)] {
+            let forward = p_adj[p]
+                .iter()
+                .filter_map(|&q| self.core_p[q])
+                .all(|u| t_adj[t].contains(&u));
+            let backward = t_adj[t]
+                .iter()
+                .filter_map(|&u| self.core_t[u])
+                .all(|q| p_adj[p].contains(&q));
+            if !forward || !backward {
+                return false;
+            }
+        }
+
+        // lookahead: the unmapped neighbors of `p` need distinct unmapped neighbors of `t`
+        let unmapped_p = pattern.succ[p]
+            .union(&pattern.pred[p])
+            .filter(|&&q| q != p && self.core_p[q].is_none())
+            .count();
+        let unmapped_t = target.succ[t]
+            .union(&target.pred[t])
+            .filter(|&&u| u != t && self.core_t[u].is_none())
+            .count();
+        unmapped_p <= unmapped_t
+    }
+}
+
+impl<P, T, F> Iterator for SubgraphIsomorphisms<P, T, F>
+where
+    P: Hash + Ord + Copy,
+    T: Hash + Ord + Copy,
+    F: Fn(P, T) -> bool,
+{
+    type Item = HashMap<P, T>;
+
+    fn next(&mut self) -> Option<HashMap<P, T>> {
+        if self.empty_pending {
+            self.empty_pending = false;
+            return Some(HashMap::new());
+        }
+
+        while let Some(depth) = self.stack.len().checked_sub(1) {
+            let p = self.order[depth];
+            // undoes the mapping tried last at this depth
+            if let Some(t) = self.core_p[p].take() {
+                self.core_t[t] = None;
+            }
+
+            let mut found = None;
+            while let Some(&t) = self.stack[depth].0.get(self.stack[depth].1) {
+                self.stack[depth].1 += 1;
+                if self.core_t[t].is_none() && self.feasible(p, t) {
+                    found = Some(t);
+                    break;
+                }
+            }
+            let Some(t) = found else {
+                self.stack.pop();
+                continue;
+            };
+
+            self.core_p[p] = Some(t);
+            self.core_t[t] = Some(p);
+            if depth + 1 == self.order.len() {
+                let mapping = self.order.iter().map(|&q| {
+                    let image = self.core_p[q].unwrap();
+                    (self.pattern.index.node(q), self.target.index.node(image))
+                });
+                return Some(mapping.collect());
+            }
+            let candidates = self.candidates(depth + 1);
+            self.stack.push((candidates, 0));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> AdjListGraph {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+        for &(f, t) in edges {
+            g.add_edge(ids[f], ids[t]);
+        }
+        g
+    }
+
+    #[test]
+    fn test_weisfeiler_lehman_hash() {
+        let hash = |g: &AdjListGraph| weisfeiler_lehman_hash(g, 3, |_| ());
+
+        // the same directed path, numbered differently
+        let path = graph(4, &[(0, 1), (1, 2), (2, 3)]);
+        let renumbered = graph(4, &[(3, 0), (2, 3), (1, 2)]);
+        assert_eq!(hash(&path), hash(&renumbered));
+
+        let reversed = graph(4, &[(1, 0), (2, 1), (3, 2)]);
+        let star = graph(4, &[(0, 1), (0, 2), (0, 3)]);
+        let longer = graph(5, &[(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(hash(&path), hash(&reversed));
+        assert_ne!(hash(&path), hash(&star));
+        assert_ne!(hash(&path), hash(&longer));
+
+        // labels distinguish otherwise identical graphs
+        let nodes = |g: &AdjListGraph| {
+            let mut v: Vec<_> = g.iter_nodes().collect();
+            v.sort();
+            v
+        };
+        let marked = |g: &AdjListGraph, m| weisfeiler_lehman_hash(g, 3, |n| n == m);
+        let (start, renumbered_start) = (nodes(&path)[0], nodes(&renumbered)[1]);
+        assert_eq!(marked(&path, start), marked(&renumbered, renumbered_start));
+        assert_ne!(marked(&path, start), marked(&renumbered, start));
+        assert_ne!(marked(&path, start), hash(&path));
+    }
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> AdjListGraph {
+        let both: Vec<_> = edges.iter().flat_map(|&(f, t)| [(f, t), (t, f)]).collect();
+        graph(n, &both)
+    }
+
+    #[test]
+    fn test_subgraph_isomorphisms() {
+        let triangle = undirected(3, &[(0, 1), (1, 2), (0, 2)]);
+        let k4 = undirected(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        // 4 triangles, each found once per permutation of its nodes
+        assert_eq!(find_subgraph_isomorphisms(&triangle, &k4).count(), 24);
+
+        // induced: a path of three nodes does not match inside a triangle
+        let path = undirected(3, &[(0, 1), (1, 2)]);
+        assert_eq!(find_subgraph_isomorphisms(&path, &k4).count(), 0);
+        let square = undirected(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let found: Vec<_> = find_subgraph_isomorphisms(&path, &square).collect();
+        assert_eq!(found.len(), 8);
+        for mapping in &found {
+            let images: HashSet<_> = mapping.values().collect();
+            assert_eq!(images.len(), 3);
+            for (f, t) in path.iter_edges() {
+                assert!(square.has_edge(mapping[&f], mapping[&t]));
+            }
+        }
+
+        let empty = AdjListGraph::new();
+        assert_eq!(find_subgraph_isomorphisms(&empty, &square).count(), 1);
+        assert_eq!(find_subgraph_isomorphisms(&k4, &triangle).count(), 0);
+    }
+
+    #[test]
+    fn test_labeled_isomorphisms() {
+        // a red node pointing to a blue one, looked up in an alternating cycle
+        let pattern = graph(2, &[(0, 1)]);
+        let target = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let color = |g: &AdjListGraph| {
+            let mut nodes: Vec<_> = g.iter_nodes().collect();
+            nodes.sort();
+            nodes
+                .into_iter()
+                .enumerate()
+                .map(|(i, n)| (n, i % 2 == 0))
+                .collect::<HashMap<_, _>>()
+        };
+        let (pattern_red, target_red) = (color(&pattern), color(&target));
+
+        let found: Vec<_> = find_subgraph_isomorphisms_by(&pattern, &target, |p, t| {
+            pattern_red[&p] == target_red[&t]
+        })
+        .collect();
+        assert_eq!(found.len(), 2);
+        assert!(found
+            .iter()
+            .all(|m| m.iter().all(|(p, t)| pattern_red[p] == target_red[t])));
+        assert_eq!(find_subgraph_isomorphisms(&pattern, &target).count(), 4);
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::impls::grid::{Connectivity, GridGraph, STRAIGHT_COST};
+
+use super::{AStar, Algorithm, Cancelled, Control};
+
+type Cell = (usize, usize);
+
+/**
+ * Jump Point Search over a [`GridGraph`] with uniform costs.
+ *
+ * Instead of expanding every neighbor, the search jumps along straight and diagonal
+ * lines until it finds a cell where the optimal path may turn, so open areas are
+ * crossed with very few expansions. Paths are as short as those of [`AStar`] with
+ * [`GridGraph::move_cost`] on a grid whose cells all cost 1, and list every cell visited.
+ *
+ * Cell costs are ignored; use [`AStar`] for weighted grids. On grids with
+ * [`Connectivity::Four`] the search falls back to a plain [`AStar`].
+ */
+pub struct Jps<'a> {
+    grid: &'a GridGraph,
+}
+
+impl<'a> Jps<'a> {
+    /**
+     * Creates a search over the passable cells of the grid.
+     */
+    pub fn new(grid: &'a GridGraph) -> Self {
+        Jps { grid }
+    }
+
+    fn walkable(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && self.grid.is_passable((x as usize, y as usize))
+    }
+
+    /**
+     * Moves from `(x, y)` in the direction `(dx, dy)` until reaching a jump point,
+     * returning it, or `None` if the line ends against a wall first.
+     */
+    fn jump(
+        &self,
+        (mut x, mut y): (i64, i64),
+        (dx, dy): (i64, i64),
+        goal: (i64, i64),
+    ) -> Option<(i64, i64)> {
+        loop {
+            if !self.walkable(x, y) {
+                return None;
+            }
+            if (x, y) == goal {
+                return Some((x, y));
+            }
+
+            let w = |x, y| self.walkable(x, y);
+            let forced = if dx != 0 && dy != 0 {
+                // a diagonal move stops where one of its straight components finds a jump point
+                self.jump((x + dx, y), (dx, 0), goal).is_some()
+                    || self.jump((x, y + dy), (0, dy), goal).is_some()
+            } else if dx != 0 {
+                (w(x, y - 1) && !w(x - dx, y - 1)) || (w(x, y + 1) && !w(x - dx, y + 1))
+            } else {
+                (w(x - 1, y) && !w(x - 1, y - dy)) || (w(x + 1, y) && !w(x + 1, y - dy))
+            };
+            if forced {
+                return Some((x, y));
+            }
+
+            // diagonal moves must not cut a wall corner, as in the grid itself
+            if !(w(x + dx, y) && w(x, y + dy)) {
+                return None;
+            }
+            x += dx;
+            y += dy;
+        }
+    }
+
+    /**
+     * Returns the directions worth exploring from a cell reached moving along `dir`.
+     */
+    fn directions(&self, (x, y): (i64, i64), dir: Option<(i64, i64)>) -> Vec<(i64, i64)> {
+        let w = |dx, dy| self.walkable(x + dx, y + dy);
+        let mut dirs = Vec::new();
+        match dir {
+            None => {
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        let straight = dx == 0 || dy == 0;
+                        if (dx, dy) != (0, 0) && w(dx, dy) && (straight || (w(dx, 0) && w(0, dy))) {
+                            dirs.push((dx, dy));
+                        }
+                    }
+                }
+            }
+            Some((dx, dy)) if dx != 0 && dy != 0 => {
+                dirs.extend([(0, dy), (dx, 0)].into_iter().filter(|&(a, b)| w(a, b)));
+                if w(dx, 0) && w(0, dy) {
+                    dirs.push((dx, dy));
+                }
+            }
+            Some((dx, dy)) => {
+                // the perpendicular directions, and the diagonals towards them
+                let (px, py) = (dy, dx);
+                for side in [1, -1] {
+                    let (sx, sy) = (px * side, py * side);
+                    if w(sx, sy) {
+                        dirs.push((sx, sy));
+                        if w(dx, dy) {
+                            dirs.push((dx + sx, dy + sy));
+                        }
+                    }
+                }
+                if w(dx, dy) {
+                    dirs.push((dx, dy));
+                }
+            }
+        }
+        dirs
+    }
+
+    fn search(
+        &self,
+        from: Cell,
+        to: Cell,
+        control: &mut Control<Cell>,
+    ) -> Result<Option<Vec<Cell>>, Cancelled> {
+        if !self.grid.is_passable(from) || !self.grid.is_passable(to) {
+            return Ok(None);
+        }
+        let cell = |(x, y): (i64, i64)| (x as usize, y as usize);
+        let point = |(x, y): Cell| (x as i64, y as i64);
+        let goal = point(to);
+
+        let mut costs = HashMap::new();
+        let mut parents: HashMap<Cell, Cell> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        costs.insert(from, 0);
+        heap.push(Reverse((self.grid.heuristic(from, to), 0, from)));
+
+        while let Some(Reverse((_, cost, node))) = heap.pop() {
+            if costs[&node] < cost {
+                continue;
+            }
+            if control(&node).is_break() {
+                return Err(Cancelled);
+            }
+            if node == to {
+                return Ok(Some(self.expand(from, to, &parents)));
+            }
+
+            let p = point(node);
+            let dir = parents.get(&node).map(|&parent| {
+                let q = point(parent);
+                ((p.0 - q.0).signum(), (p.1 - q.1).signum())
+            });
+            for d in self.directions(p, dir) {
+                let Some(jp) = self.jump((p.0 + d.0, p.1 + d.1), d, goal).map(cell) else {
+                    continue;
+                };
+                // jump points are reached along straight or diagonal lines, whose cost is the octile distance
+                let alt = cost + self.grid.heuristic(node, jp);
+                if costs.get(&jp).is_none_or(|&c| alt < c) {
+                    costs.insert(jp, alt);
+                    parents.insert(jp, node);
+                    heap.push(Reverse((alt + self.grid.heuristic(jp, to), alt, jp)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /**
+     * Rebuilds the full path of cells from the jump points.
+     */
+    fn expand(&self, from: Cell, to: Cell, parents: &HashMap<Cell, Cell>) -> Vec<Cell> {
+        let mut jumps = vec![to];
+        let mut cur = to;
+        while cur != from {
+            cur = parents[&cur];
+            jumps.push(cur);
+        }
+        jumps.reverse();
+
+        let mut path = vec![from];
+        for w in jumps.windows(2) {
+            let (mut x, mut y) = w[0];
+            let (tx, ty) = w[1];
+            while (x, y) != (tx, ty) {
+                x = if tx > x {
+                    x + 1
+                } else if tx < x {
+                    x - 1
+                } else {
+                    x
+                };
+                y = if ty > y {
+                    y + 1
+                } else if ty < y {
+                    y - 1
+                } else {
+                    y
+                };
+                path.push((x, y));
+            }
+        }
+        path
+    }
+}
+
+impl Algorithm<GridGraph> for Jps<'_> {
+    fn run_with_control(
+        &self,
+        from: Cell,
+        to: Cell,
+        control: &mut Control<Cell>,
+    ) -> Result<Option<Vec<Cell>>, Cancelled> {
+        match self.grid.connectivity() {
+            Connectivity::Eight => self.search(from, to, control),
+            Connectivity::Four => {
+                let grid = self.grid;
+                AStar::with_weights(grid, |_, _| STRAIGHT_COST, |n, t| grid.heuristic(n, t))
+                    .run_with_control(from, to, control)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphRead;
+    use std::ops::ControlFlow;
+
+    fn cost(grid: &GridGraph, path: &[Cell]) -> u32 {
+        path.windows(2).map(|w| grid.move_cost(w[0], w[1])).sum()
+    }
+
+    #[test]
+    fn matches_astar_on_mazes() {
+        let mut state = 12345u64;
+        for _ in 0..20 {
+            let mut grid = GridGraph::new(20, 15, Connectivity::Eight);
+            for y in 0..15 {
+                for x in 0..20 {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    if (state >> 33) % 10 < 3 && (x, y) != (0, 0) && (x, y) != (19, 14) {
+                        grid.set_wall((x, y));
+                    }
+                }
+            }
+
+            let astar = AStar::with_weights(
+                &grid,
+                |f, t| grid.move_cost(f, t),
+                |n, t| grid.heuristic(n, t),
+            );
+            let expected = astar.run((0, 0), (19, 14));
+            let path = Jps::new(&grid).run((0, 0), (19, 14));
+            assert_eq!(path.is_some(), expected.is_some());
+            if let (Some(path), Some(expected)) = (path, expected) {
+                assert!(path.windows(2).all(|w| grid.has_edge(w[0], w[1])));
+                assert_eq!(cost(&grid, &path), cost(&grid, &expected));
+            }
+        }
+    }
+
+    #[test]
+    fn expands_few_nodes_on_open_grids() {
+        let grid = GridGraph::new(50, 50, Connectivity::Eight);
+        let mut expanded = 0;
+        let path = Jps::new(&grid).run_with_control((0, 0), (49, 30), &mut |_| {
+            expanded += 1;
+            ControlFlow::Continue(())
+        });
+        let path = path.unwrap().unwrap();
+        assert_eq!(path.len(), 50);
+        assert!(expanded < 10);
+        assert_eq!(Jps::new(&grid).run((7, 7), (7, 7)), Some(vec![(7, 7)]));
+
+        let grid = GridGraph::new(5, 5, Connectivity::Four);
+        assert_eq!(
+            Jps::new(&grid).run((0, 0), (4, 4)).map(|p| p.len()),
+            Some(9)
+        );
+        assert_eq!(Jps::new(&grid).run((0, 0), (5, 5)), None);
+        assert_eq!(Jps::new(&grid).run((4, 4), (4, 4)), Some(vec![(4, 4)]));
+    }
+}
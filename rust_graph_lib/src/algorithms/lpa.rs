@@ -0,0 +1,290 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use crate::graph::GraphRead;
+
+use super::{CostedPath, Distance, Measure};
+
+struct Entry<I, W> {
+    key: Distance<W>,
+    node: I,
+}
+
+impl<I, W: Ord> PartialEq for Entry<I, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<I, W: Ord> Eq for Entry<I, W> {}
+
+impl<I, W: Ord> Ord for Entry<I, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // order is reversed because BinaryHeap returns the max
+        other.key.cmp(&self.key)
+    }
+}
+
+impl<I, W: Ord> PartialOrd for Entry<I, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/**
+ * Lifelong Planning A* (LPA*), an incremental shortest path search between two fixed
+ * nodes that repairs its previous solution when edge costs change, instead of
+ * searching again from scratch.
+ *
+ * The search works on a copy of the edges and their costs taken at creation, which
+ * is then kept up to date with [`LpaStar::set_edge_cost`]; only the nodes whose
+ * distance is affected by a change are expanded again by the next [`LpaStar::path`].
+ * No heuristic is used, so the search behaves like an incremental
+ * [`Dijkstra`](super::Dijkstra).
+ */
+pub struct LpaStar<I, W = u32> {
+    from: I,
+    to: I,
+    succ: HashMap<I, HashMap<I, W>>,
+    pred: HashMap<I, HashMap<I, W>>,
+    g: HashMap<I, Distance<W>>,
+    rhs: HashMap<I, Distance<W>>,
+    queue: BinaryHeap<Entry<I, W>>,
+    expansions: usize,
+}
+
+impl<I: Hash + Eq + Copy, W: Measure> LpaStar<I, W> {
+    /**
+     * Creates a search from `from` to `to`, where the edge `(f, t)` costs `weight(f, t)`.
+     */
+    pub fn new<G, F>(graph: &G, weight: F, from: I, to: I) -> Self
+    where
+        G: GraphRead<Index = I>,
+        F: Fn(I, I) -> W,
+    {
+        let mut search = LpaStar {
+            from,
+            to,
+            succ: HashMap::new(),
+            pred: HashMap::new(),
+            g: HashMap::new(),
+            rhs: HashMap::new(),
+            queue: BinaryHeap::new(),
+            expansions: 0,
+        };
+        for (f, t) in graph.iter_edges() {
+            let w = weight(f, t);
+            search.succ.entry(f).or_default().insert(t, w);
+            search.pred.entry(t).or_default().insert(f, w);
+        }
+        search.rhs.insert(from, Distance::zero());
+        search.queue.push(Entry {
+            key: Distance::zero(),
+            node: from,
+        });
+        search
+    }
+
+    fn g(&self, n: I) -> Distance<W> {
+        self.g.get(&n).copied().unwrap_or(Distance::Infinity)
+    }
+
+    fn rhs(&self, n: I) -> Distance<W> {
+        self.rhs.get(&n).copied().unwrap_or(Distance::Infinity)
+    }
+
+    fn key(&self, n: I) -> Distance<W> {
+        self.g(n).min(self.rhs(n))
+    }
+
+    /**
+     * Recomputes the best distance of `n` through its predecessors and queues it if
+     * it is no longer consistent.
+     */
+    fn update(&mut self, n: I) {
+        if n != self.from {
+            let best = self
+                .pred
+                .get(&n)
+                .into_iter()
+                .flatten()
+                .fold(Distance::Infinity, |best, (&p, &w)| {
+                    best.min(self.g(p).combine(Distance::Finite(w)))
+                });
+            self.rhs.insert(n, best);
+        }
+        if self.g(n) != self.rhs(n) {
+            self.queue.push(Entry {
+                key: self.key(n),
+                node: n,
+            });
+        }
+    }
+
+    /**
+     * Sets the cost of the edge `(f, t)`, adding it if needed, or removes the edge if
+     * `cost` is `None`.
+     */
+    pub fn set_edge_cost(&mut self, f: I, t: I, cost: Option<W>) {
+        match cost {
+            Some(w) => {
+                self.succ.entry(f).or_default().insert(t, w);
+                self.pred.entry(t).or_default().insert(f, w);
+            }
+            None => {
+                self.succ.get_mut(&f).map(|s| s.remove(&t));
+                self.pred.get_mut(&t).map(|p| p.remove(&f));
+            }
+        }
+        self.update(t);
+    }
+
+    /**
+     * Returns the number of nodes expanded by the last call of [`LpaStar::path`].
+     */
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    fn compute(&mut self) {
+        self.expansions = 0;
+        while let Some(top) = self.queue.peek() {
+            if top.key >= self.key(self.to) && self.g(self.to) == self.rhs(self.to) {
+                break;
+            }
+            let Entry { key, node } = self.queue.pop().unwrap();
+            // entries are pushed again when their key changes, so outdated ones are skipped
+            if self.g(node) == self.rhs(node) || key != self.key(node) {
+                continue;
+            }
+
+            self.expansions += 1;
+            if self.g(node) > self.rhs(node) {
+                self.g.insert(node, self.rhs(node));
+            } else {
+                self.g.insert(node, Distance::Infinity);
+                self.update(node);
+            }
+            let succ: Vec<_> = self
+                .succ
+                .get(&node)
+                .into_iter()
+                .flatten()
+                .map(|(&s, _)| s)
+                .collect();
+            for s in succ {
+                self.update(s);
+            }
+        }
+    }
+
+    /**
+     * Returns the current shortest path and its cost, repairing the previous solution
+     * after any cost change.
+     *
+     * As with [`Dijkstra`](super::Dijkstra), the path from a node to itself is that node
+     * alone, at no cost. No path is returned if the cost overflows.
+     */
+    pub fn path(&mut self) -> Option<CostedPath<I, W>> {
+        self.compute();
+        let cost = self.g(self.to).finite()?;
+
+        let mut path = vec![self.to];
+        let mut cur = self.to;
+        while cur != self.from {
+            // the predecessor on a shortest path is the one the distance of `cur` comes from
+            let target = self.g(cur);
+            cur = self
+                .pred
+                .get(&cur)
+                .into_iter()
+                .flatten()
+                .find_map(|(&p, &w)| {
+                    (self.g(p).combine(Distance::Finite(w)) == target).then_some(p)
+                })?;
+            path.push(cur);
+            if path.len() > self.g.len() + 1 {
+                return None;
+            }
+        }
+        path.reverse();
+        Some(CostedPath { path, cost })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::Dijkstra, graph::Graph, impls::adj_list::AdjListGraph};
+    use std::collections::HashMap;
+
+    #[test]
+    fn repairs_after_cost_changes() {
+        // a 6x6 grid with edges in both directions
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..36).map(|_| g.add_node()).collect();
+        let mut costs = HashMap::new();
+        for i in 0..36 {
+            for j in [i + 1, i + 6] {
+                if j < 36 && (j != i + 1 || j % 6 != 0) {
+                    g.add_edge(ids[i], ids[j]);
+                    g.add_edge(ids[j], ids[i]);
+                    costs.insert((ids[i], ids[j]), 1 + (i * 7 + j) as u32 % 5);
+                    costs.insert((ids[j], ids[i]), 1 + (i * 3 + j) as u32 % 5);
+                }
+            }
+        }
+
+        let (from, to) = (ids[0], ids[35]);
+        let mut lpa = LpaStar::new(&g, |f, t| costs[&(f, t)], from, to);
+        let mut state = 7u64;
+        for round in 0..30 {
+            let p = lpa.path();
+            let expected =
+                Dijkstra::with_weights(&g, |f, t| costs[&(f, t)]).run_with_cost(from, to);
+            assert_eq!(p.as_ref().map(|p| p.cost), expected.map(|p| p.cost));
+            if let Some(p) = p {
+                let cost: u32 = p.path.windows(2).map(|w| costs[&(w[0], w[1])]).sum();
+                assert_eq!(cost, p.cost);
+                assert!(round == 0 || lpa.expansions() < 36);
+            }
+
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            let keys: Vec<_> = {
+                let mut keys: Vec<_> = costs.keys().copied().collect();
+                keys.sort();
+                keys
+            };
+            let (f, t) = keys[(state >> 33) as usize % keys.len()];
+            let w = 1 + (state >> 40) as u32 % 20;
+            costs.insert((f, t), w);
+            lpa.set_edge_cost(f, t, Some(w));
+        }
+    }
+
+    #[test]
+    fn handles_removed_edges() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(b, c);
+
+        let mut lpa = LpaStar::new(&g, |_, _| 1u32, a, c);
+        assert_eq!(lpa.path().map(|p| p.cost), Some(2));
+        lpa.set_edge_cost(b, c, None);
+        assert_eq!(lpa.path(), None);
+        lpa.set_edge_cost(a, c, Some(5));
+        assert_eq!(lpa.path().map(|p| p.path), Some(vec![a, c]));
+
+        let mut same = LpaStar::new(&g, |_, _| 1u32, b, b);
+        assert_eq!(same.path().map(|p| (p.path, p.cost)), Some((vec![b], 0)));
+        lpa.set_edge_cost(b, c, Some(1));
+        assert_eq!(
+            lpa.path().map(|p| (p.path, p.cost)),
+            Some((vec![a, b, c], 2))
+        );
+    }
+}
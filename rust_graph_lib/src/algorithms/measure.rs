@@ -0,0 +1,154 @@
+/**
+ * Cost of a path, as minimized by the shortest path algorithms.
+ *
+ * Measures are compared with [`Ord`], smaller being better, and extended edge by
+ * edge with [`Measure::combine`]. Tuples are compared lexicographically, so for
+ * instance `(hops, weight)` finds the path with fewer edges, breaking ties by weight.
+ */
+pub trait Measure: Copy + Ord {
+    /**
+     * Returns the measure of an empty path.
+     */
+    fn zero() -> Self;
+    /**
+     * Extends a path measure with the measure of another edge or path.
+     *
+     * Must not overflow: integers saturate at their maximum value, so that
+     * overflowing paths compare as the costliest instead of wrapping around.
+     */
+    fn combine(self, other: Self) -> Self;
+    /**
+     * Extends a path measure like [`Measure::combine`], returning `None` if the result
+     * cannot be represented exactly.
+     */
+    fn checked_combine(self, other: Self) -> Option<Self> {
+        Some(self.combine(other))
+    }
+}
+
+macro_rules! impl_measure {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn combine(self, other: Self) -> Self {
+                    self.saturating_add(other)
+                }
+
+                fn checked_combine(self, other: Self) -> Option<Self> {
+                    self.checked_add(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_measure!(u8, u16, u32, u64, u128, usize);
+
+impl<A: Measure, B: Measure> Measure for (A, B) {
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+
+    fn combine(self, other: Self) -> Self {
+        (self.0.combine(other.0), self.1.combine(other.1))
+    }
+
+    fn checked_combine(self, other: Self) -> Option<Self> {
+        Some((
+            self.0.checked_combine(other.0)?,
+            self.1.checked_combine(other.1)?,
+        ))
+    }
+}
+
+impl<A: Measure, B: Measure, C: Measure> Measure for (A, B, C) {
+    fn zero() -> Self {
+        (A::zero(), B::zero(), C::zero())
+    }
+
+    fn combine(self, other: Self) -> Self {
+        (
+            self.0.combine(other.0),
+            self.1.combine(other.1),
+            self.2.combine(other.2),
+        )
+    }
+
+    fn checked_combine(self, other: Self) -> Option<Self> {
+        Some((
+            self.0.checked_combine(other.0)?,
+            self.1.checked_combine(other.1)?,
+            self.2.checked_combine(other.2)?,
+        ))
+    }
+}
+
+/**
+ * Measure extended with an explicit infinity, which every overflowing path becomes.
+ *
+ * Unlike the saturation of plain integers, an infinite distance is never mistaken for a
+ * path costing exactly the maximum value.
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Distance<W> {
+    Finite(W),
+    Infinity,
+}
+
+impl<W> Distance<W> {
+    /**
+     * Returns the finite value, if any.
+     */
+    pub fn finite(self) -> Option<W> {
+        match self {
+            Distance::Finite(w) => Some(w),
+            Distance::Infinity => None,
+        }
+    }
+}
+
+impl<W: Measure> Measure for Distance<W> {
+    fn zero() -> Self {
+        Distance::Finite(W::zero())
+    }
+
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Distance::Finite(a), Distance::Finite(b)) => a
+                .checked_combine(b)
+                .map_or(Distance::Infinity, Distance::Finite),
+            _ => Distance::Infinity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Distance, Measure};
+
+    #[test]
+    fn test_tuples_combine_componentwise() {
+        assert_eq!((1u32, 5u64).combine((1, 7)), (2, 12));
+        assert_eq!(<(u8, u8, u8)>::zero(), (0, 0, 0));
+        assert!((1u32, 100u32) < (2, 1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(250u8.combine(10), u8::MAX);
+        assert_eq!(250u8.checked_combine(10), None);
+        assert_eq!((1u8, 250u8).checked_combine((1, 10)), None);
+
+        let near_max = Distance::Finite(u32::MAX - 1);
+        assert_eq!(
+            near_max.combine(Distance::Finite(1)),
+            Distance::Finite(u32::MAX)
+        );
+        assert_eq!(near_max.combine(Distance::Finite(2)), Distance::Infinity);
+        assert!(Distance::Finite(u32::MAX) < Distance::Infinity);
+    }
+}
@@ -1,9 +1,44 @@
 mod algo;
+mod alt;
+mod astar;
+pub mod centrality;
+pub mod ch;
+pub mod cliques;
+pub mod community;
+pub mod dag;
+mod dense;
 mod dfs;
 mod dijkstra;
+mod distances;
+pub mod flow;
+pub mod isomorphism;
+mod jps;
+mod lpa;
+mod measure;
+mod path_cache;
+mod reachability;
+mod registry;
+mod spt;
 #[cfg(test)]
 mod test_utils;
+mod trace;
+mod weight;
+mod widest;
 
-pub use algo::Algorithm;
+use algo::GraphHandle;
+pub use algo::{Algorithm, Budget, BudgetedPath, Cancelled, Control, CostedPath};
+pub use alt::Landmarks;
+pub use astar::AStar;
+pub(crate) use dense::DenseIndex;
 pub use dfs::Dfs;
 pub use dijkstra::Dijkstra;
+pub use distances::{pairwise_distances, DistanceMatrix};
+pub use jps::Jps;
+pub use lpa::LpaStar;
+pub use measure::{Distance, Measure};
+pub use path_cache::PathCache;
+pub use reachability::{reachable_from, reaching_to};
+pub use registry::{registry, weighted_registry, Factory, Registry};
+pub use spt::ShortestPathTree;
+pub use weight::{Cost, Prob, Width};
+pub use widest::widest_path;
@@ -0,0 +1,324 @@
+use super::{Algorithm, Dijkstra, Measure};
+use crate::{
+    graph::{Graph, GraphRead, WeightedGraph},
+    observer::{GraphEvent, GraphObserver, ObservedGraph},
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+struct CachedPaths<I> {
+    paths: HashMap<(I, I), Option<Vec<I>>>,
+    // queries whose cached path goes through each node
+    by_node: HashMap<I, HashSet<(I, I)>>,
+    weighted: bool,
+}
+
+impl<I: Hash + Eq + Copy> CachedPaths<I> {
+    fn insert(&mut self, key: (I, I), path: Option<Vec<I>>) {
+        for &n in path.iter().flatten() {
+            self.by_node.entry(n).or_default().insert(key);
+        }
+        self.paths.insert(key, path);
+    }
+
+    fn invalidate(&mut self, key: (I, I)) {
+        if let Some(path) = self.paths.remove(&key) {
+            for n in path.iter().flatten() {
+                if let Some(keys) = self.by_node.get_mut(n) {
+                    keys.remove(&key);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.paths.clear();
+        self.by_node.clear();
+    }
+}
+
+impl<I: Hash + Eq + Copy> GraphObserver<I> for CachedPaths<I> {
+    fn notify(&mut self, event: &GraphEvent<I>) {
+        match *event {
+            // a new isolated node cannot change any path
+            GraphEvent::NodeAdded(_) => {}
+            // removals only make the paths not using the removed element longer,
+            // so the cached paths avoiding it stay the shortest
+            GraphEvent::NodeRemoved(n) => {
+                for key in self.by_node.remove(&n).unwrap_or_default() {
+                    self.invalidate(key);
+                }
+            }
+            // on undirected graphs removing (f, t) also removes (t, f), so paths using
+            // either hop are dropped; on directed ones this only costs a recomputation
+            GraphEvent::EdgeRemoved(f, t) => {
+                let keys: Vec<_> = self
+                    .by_node
+                    .get(&f)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .filter(|key| {
+                        self.paths[key]
+                            .iter()
+                            .flat_map(|p| p.windows(2))
+                            .any(|w| (w[0], w[1]) == (f, t) || (w[0], w[1]) == (t, f))
+                    })
+                    .collect();
+                for key in keys {
+                    self.invalidate(key);
+                }
+            }
+            // a new edge may shorten any path, or connect unreachable nodes
+            GraphEvent::EdgeAdded(..) => self.clear(),
+            // unit paths ignore the weights, while a weighted path may be shortened
+            // by any edge getting lighter
+            GraphEvent::EdgeWeightChanged(..) => {
+                if self.weighted {
+                    self.clear();
+                }
+            }
+        }
+    }
+}
+
+type Search<G> = fn(
+    &ObservedGraph<G>,
+    <G as GraphRead>::Index,
+    <G as GraphRead>::Index,
+) -> Option<Vec<<G as GraphRead>::Index>>;
+
+/**
+ * Wrapper around a graph memoizing its shortest path queries, computed with [`Dijkstra`]
+ * either by number of edges or, if created with [`PathCache::weighted`], by weight.
+ *
+ * The graph is mutated through [`graph_mut`](PathCache::graph_mut) and the cached paths
+ * affected by each mutation are dropped: removing a node or an edge only invalidates the
+ * paths using it, while adding an edge, or changing a weight when caching by weight,
+ * invalidates everything.
+ */
+pub struct PathCache<G: Graph> {
+    graph: ObservedGraph<G>,
+    cache: Rc<RefCell<CachedPaths<G::Index>>>,
+    search: Search<G>,
+    hits: Cell<usize>,
+}
+
+impl<G: Graph> PathCache<G>
+where
+    G::Index: Hash + Eq + Copy + 'static,
+{
+    /**
+     * Wraps the graph, with an empty cache of paths with the fewest edges.
+     */
+    pub fn new(graph: G) -> Self {
+        Self::with_search(graph, false, |g, f, t| Dijkstra::new(g).run(f, t))
+    }
+
+    /**
+     * Wraps the graph, with an empty cache of paths minimizing the weights stored in it.
+     */
+    pub fn weighted(graph: G) -> Self
+    where
+        G: WeightedGraph,
+        G::Weight: Measure,
+    {
+        Self::with_search(graph, true, |g, f, t| Dijkstra::weighted(g).run(f, t))
+    }
+
+    fn with_search(graph: G, weighted: bool, search: Search<G>) -> Self {
+        let mut graph = ObservedGraph::new(graph);
+        let cache = Rc::new(RefCell::new(CachedPaths {
+            paths: HashMap::new(),
+            by_node: HashMap::new(),
+            weighted,
+        }));
+        graph.subscribe(&cache);
+        PathCache {
+            graph,
+            cache,
+            search,
+            hits: Cell::new(0),
+        }
+    }
+
+    /**
+     * Returns the shortest path between `from` and `to`, computing it only
+     * if it is not cached.
+     */
+    pub fn shortest_path(&self, from: G::Index, to: G::Index) -> Option<Vec<G::Index>> {
+        if let Some(path) = self.cache.borrow().paths.get(&(from, to)) {
+            self.hits.set(self.hits.get() + 1);
+            return path.clone();
+        }
+
+        let path = (self.search)(&self.graph, from, to);
+        self.cache.borrow_mut().insert((from, to), path.clone());
+        path
+    }
+
+    /**
+     * Returns the number of cached queries.
+     */
+    pub fn len(&self) -> usize {
+        self.cache.borrow().paths.len()
+    }
+
+    /**
+     * Checks if no query is cached.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+     * Returns the number of queries answered from the cache so far.
+     */
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+
+    /**
+     * Drops all the cached queries.
+     */
+    pub fn clear(&mut self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /**
+     * Returns the wrapped graph.
+     */
+    pub fn graph(&self) -> &ObservedGraph<G> {
+        &self.graph
+    }
+
+    /**
+     * Returns the wrapped graph for mutation, keeping the cache in sync.
+     */
+    pub fn graph_mut(&mut self) -> &mut ObservedGraph<G> {
+        &mut self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::WeightedGraph,
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_cache_hits_and_invalidation() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[0], ids[3]);
+        g.add_edge(ids[3], ids[4]);
+        g.add_edge(ids[4], ids[2]);
+
+        let mut cache = PathCache::new(g);
+        let p = vec![ids[0], ids[1], ids[2]];
+        assert_eq!(cache.shortest_path(ids[0], ids[2]), Some(p.clone()));
+        assert_eq!(cache.shortest_path(ids[0], ids[2]), Some(p));
+        assert_eq!(
+            cache.shortest_path(ids[0], ids[3]),
+            Some(vec![ids[0], ids[3]])
+        );
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.len(), 2);
+
+        // removing an edge invalidates only the queries whose path uses it
+        cache.graph_mut().remove_edge(ids[0], ids[1]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.shortest_path(ids[0], ids[2]),
+            Some(vec![ids[0], ids[3], ids[4], ids[2]])
+        );
+
+        cache.graph_mut().add_edge(ids[0], ids[2]);
+        assert!(cache.is_empty());
+        assert_eq!(
+            cache.shortest_path(ids[0], ids[2]),
+            Some(vec![ids[0], ids[2]])
+        );
+    }
+
+    #[test]
+    fn test_node_removal() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+
+        let mut cache = PathCache::new(g);
+        assert!(cache.shortest_path(ids[0], ids[2]).is_some());
+        assert!(cache.shortest_path(ids[2], ids[0]).is_none());
+
+        cache.graph_mut().remove_node(ids[1]);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.shortest_path(ids[0], ids[2]).is_none());
+    }
+
+    #[test]
+    fn test_undirected_edge_removal() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 1u32);
+        g.add_weighted_edge(ids[1], ids[2], 1);
+
+        let mut cache = PathCache::new(g);
+        assert_eq!(
+            cache.shortest_path(ids[0], ids[1]),
+            Some(vec![ids[0], ids[1]])
+        );
+        cache.graph_mut().remove_edge(ids[1], ids[0]);
+        assert!(cache.is_empty());
+        assert_eq!(cache.shortest_path(ids[0], ids[1]), None);
+    }
+
+    #[test]
+    fn test_weighted_paths() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[2], 5u32);
+        g.add_weighted_edge(ids[0], ids[1], 1);
+        g.add_weighted_edge(ids[1], ids[2], 1);
+
+        let mut cache = PathCache::weighted(g);
+        assert_eq!(
+            cache.shortest_path(ids[0], ids[2]),
+            Some(vec![ids[0], ids[1], ids[2]])
+        );
+
+        // making the direct edge lighter flips the best route
+        assert!(cache.graph_mut().set_edge_weight(ids[0], ids[2], 1));
+        assert!(cache.is_empty());
+        assert_eq!(
+            cache.shortest_path(ids[0], ids[2]),
+            Some(vec![ids[0], ids[2]])
+        );
+    }
+
+    #[test]
+    fn test_weight_change_keeps_paths() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 1u32);
+        g.add_weighted_edge(ids[1], ids[2], 1);
+
+        let mut cache = PathCache::new(g);
+        assert!(cache.shortest_path(ids[0], ids[2]).is_some());
+        assert!(cache.graph_mut().set_edge_weight(ids[0], ids[1], 7));
+        assert!(cache
+            .graph_mut()
+            .update_edge_weight(ids[1], ids[2], |w| w + 1));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.graph().edge_weight(ids[1], ids[2]), Some(2));
+    }
+}
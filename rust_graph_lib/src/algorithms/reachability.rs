@@ -0,0 +1,67 @@
+use crate::{graph::GraphRead, views::Reversed};
+use std::{collections::HashSet, hash::Hash};
+
+/**
+ * Returns the set of nodes reachable from `start`, including `start` itself
+ * if it is in the graph.
+ */
+pub fn reachable_from<G>(graph: &G, start: G::Index) -> HashSet<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let mut visited = HashSet::new();
+    if graph.iter_adj(start).is_none() {
+        return visited;
+    }
+
+    let mut stack = vec![start];
+    visited.insert(start);
+
+    while let Some(n) = stack.pop() {
+        for a in graph.iter_adj(n).into_iter().flatten() {
+            if visited.insert(a) {
+                stack.push(a);
+            }
+        }
+    }
+    visited
+}
+
+/**
+ * Returns the set of nodes from which `target` can be reached, including `target` itself
+ * if it is in the graph.
+ */
+pub fn reaching_to<G>(graph: &G, target: G::Index) -> HashSet<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    reachable_from(&Reversed::new(graph), target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_reachable_sets() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        let id4 = g.add_node();
+        let id5 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id1);
+        g.add_edge(id4, id3);
+
+        assert_eq!(reachable_from(&g, id1), HashSet::from([id1, id2, id3]));
+        assert_eq!(reachable_from(&g, id5), HashSet::from([id5]));
+        assert_eq!(reaching_to(&g, id3), HashSet::from([id1, id2, id3, id4]));
+        assert_eq!(reaching_to(&g, id4), HashSet::from([id4]));
+    }
+}
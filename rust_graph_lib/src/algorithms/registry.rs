@@ -0,0 +1,152 @@
+use super::{Algorithm, Dfs, Dijkstra, Measure};
+use crate::graph::{ImplicitGraph, WeightedGraph};
+use std::{collections::BTreeMap, hash::Hash};
+
+/**
+ * Creates an algorithm searching the given graph.
+ */
+pub type Factory<G> = Box<dyn for<'a> Fn(&'a G) -> Box<dyn Algorithm<G> + 'a>>;
+
+/**
+ * Path search algorithms by name, for applications choosing them at runtime, for
+ * instance from a configuration file.
+ *
+ * ```
+ * use rust_graph_lib::{algorithms::registry, graph::Graph, impls::adj_list::AdjListGraph};
+ *
+ * let mut g = AdjListGraph::new();
+ * let a = g.add_node();
+ * let b = g.add_node();
+ * g.add_edge(a, b);
+ *
+ * let algorithms = registry();
+ * let search = algorithms.get("dijkstra").unwrap()(&g);
+ * assert_eq!(search.run(a, b), Some(vec![a, b]));
+ * ```
+ */
+pub struct Registry<G> {
+    factories: BTreeMap<String, Factory<G>>,
+}
+
+impl<G: ImplicitGraph> Registry<G> {
+    /**
+     * Creates a registry without algorithms.
+     */
+    pub fn new() -> Self {
+        Registry {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Registers an algorithm under `name`, replacing the one registered before, if any.
+     */
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F)
+    where
+        F: for<'a> Fn(&'a G) -> Box<dyn Algorithm<G> + 'a> + 'static,
+    {
+        self.factories.insert(name.into(), Box::new(factory));
+    }
+
+    /**
+     * Returns the factory of the algorithm registered under `name`.
+     */
+    pub fn get(&self, name: &str) -> Option<&Factory<G>> {
+        self.factories.get(name)
+    }
+
+    /**
+     * Iterates over the names of the registered algorithms, in alphabetical order.
+     */
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+impl<G: ImplicitGraph> Default for Registry<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+ * Returns a registry of the algorithms working on any graph: `dfs` and `dijkstra`,
+ * where every edge costs 1.
+ */
+pub fn registry<G>() -> Registry<G>
+where
+    G: ImplicitGraph,
+    G::Index: Hash + Copy,
+{
+    let mut registry = Registry::new();
+    registry.register("dfs", |g| Box::new(Dfs::new(g)));
+    registry.register("dijkstra", |g| Box::new(Dijkstra::new(g)));
+    registry
+}
+
+/**
+ * Returns the [`registry`] of a weighted graph, where `dijkstra` minimizes the stored
+ * weights and `bfs` finds the path with the fewest edges.
+ */
+pub fn weighted_registry<G>() -> Registry<G>
+where
+    G: WeightedGraph,
+    G::Index: Hash + Copy,
+    G::Weight: Measure,
+{
+    let mut registry = registry();
+    registry.register("bfs", |g| Box::new(Dijkstra::new(g)));
+    registry.register("dijkstra", |g| Box::new(Dijkstra::weighted(g)));
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::AStar,
+        graph::Graph,
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_registry() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(b, c);
+
+        let mut algorithms = registry();
+        assert_eq!(
+            algorithms.names().collect::<Vec<_>>(),
+            vec!["dfs", "dijkstra"]
+        );
+        assert!(algorithms.get("bellman-ford").is_none());
+        for name in ["dfs", "dijkstra"] {
+            let search = algorithms.get(name).unwrap()(&g);
+            assert_eq!(search.run(a, c), Some(vec![a, b, c]));
+        }
+
+        algorithms.register("astar", |g| Box::new(AStar::new(g, |_, _| 0)));
+        assert_eq!(algorithms.get("astar").unwrap()(&g).run(c, a), None);
+    }
+
+    #[test]
+    fn test_weighted_registry() {
+        let mut g = UndirectedWeightedGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_weighted_edge(a, b, 1u32);
+        g.add_weighted_edge(b, c, 1);
+        g.add_weighted_edge(a, c, 5);
+
+        let algorithms = weighted_registry();
+        assert_eq!(
+            algorithms.get("dijkstra").unwrap()(&g).run(a, c),
+            Some(vec![a, b, c])
+        );
+        assert_eq!(
+            algorithms.get("bfs").unwrap()(&g).run(a, c),
+            Some(vec![a, c])
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Shortest paths from one or more sources to every node they can reach, as computed by
+ * [`Dijkstra::run_all`](super::Dijkstra::run_all) and
+ * [`Dijkstra::run_from_many`](super::Dijkstra::run_from_many).
+ *
+ * With several sources each node is reached from the nearest one.
+ */
+#[derive(Clone, Debug)]
+pub struct ShortestPathTree<I, W> {
+    sources: Vec<I>,
+    preds: HashMap<I, I>,
+    dists: HashMap<I, W>,
+}
+
+impl<I: Hash + Eq + Copy, W: Copy> ShortestPathTree<I, W> {
+    pub(crate) fn new(sources: Vec<I>, preds: HashMap<I, I>, dists: HashMap<I, W>) -> Self {
+        ShortestPathTree {
+            sources,
+            preds,
+            dists,
+        }
+    }
+
+    /**
+     * Returns the sources of the paths, as given to the search.
+     */
+    pub fn sources(&self) -> &[I] {
+        &self.sources
+    }
+
+    /**
+     * Returns the number of reachable nodes, including the sources.
+     */
+    pub fn len(&self) -> usize {
+        self.dists.len()
+    }
+
+    /**
+     * Returns `true` if no node is reachable, i.e. there were no sources or they were all
+     * excluded from the search.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.dists.is_empty()
+    }
+
+    /**
+     * Returns the cost of the shortest path to `n`, if `n` is reachable.
+     */
+    pub fn distance_to(&self, n: I) -> Option<W> {
+        self.dists.get(&n).copied()
+    }
+
+    /**
+     * Returns the predecessor of `n` on its shortest path, if `n` is reachable and not a source.
+     */
+    pub fn predecessor(&self, n: I) -> Option<I> {
+        self.preds.get(&n).copied()
+    }
+
+    /**
+     * Returns the shortest path from the nearest source to `n`, if `n` is reachable.
+     *
+     * The path to a source only contains the source itself.
+     */
+    pub fn path_to(&self, n: I) -> Option<Vec<I>> {
+        if !self.dists.contains_key(&n) {
+            return None;
+        }
+
+        let mut path = vec![n];
+        let mut cur = n;
+        while let Some(&prev) = self.preds.get(&cur) {
+            path.push(prev);
+            cur = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /**
+     * Returns the source nearest to `n`, the root of its path, if `n` is reachable.
+     */
+    pub fn nearest_source(&self, n: I) -> Option<I> {
+        if !self.dists.contains_key(&n) {
+            return None;
+        }
+        let mut cur = n;
+        while let Some(&prev) = self.preds.get(&cur) {
+            cur = prev;
+        }
+        Some(cur)
+    }
+
+    /**
+     * Returns the nearest source of every reachable node.
+     */
+    pub fn regions(&self) -> HashMap<I, I> {
+        let mut regions = HashMap::with_capacity(self.dists.len());
+        for &n in self.dists.keys() {
+            // walk up until a node with a known region, then assign it along the way
+            let mut chain = Vec::new();
+            let mut cur = n;
+            let root = loop {
+                if let Some(&r) = regions.get(&cur) {
+                    break r;
+                }
+                match self.preds.get(&cur) {
+                    Some(&prev) => {
+                        chain.push(cur);
+                        cur = prev;
+                    }
+                    None => {
+                        regions.insert(cur, cur);
+                        break cur;
+                    }
+                }
+            };
+            for c in chain {
+                regions.insert(c, root);
+            }
+        }
+        regions
+    }
+
+    /**
+     * Iterates over the reachable nodes and their distances, in arbitrary order.
+     */
+    pub fn iter_reachable(&self) -> impl Iterator<Item = (I, W)> + '_ {
+        self.dists.iter().map(|(&n, &d)| (n, d))
+    }
+}
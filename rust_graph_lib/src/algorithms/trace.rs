@@ -0,0 +1,190 @@
+/**
+ * Counters of the work done by a search, reported with the `tracing` feature.
+ *
+ * Creating the counters enters a `search` span at the debug level, one per run. Inside
+ * it every expanded node and relaxed edge emits a trace level event with the counts so
+ * far, and dropping the counters records the totals on the span and emits them in a
+ * debug level event, on every way out of the search. Without the feature the counters
+ * are empty and every method does nothing.
+ */
+pub(crate) struct SearchStats {
+    #[cfg(feature = "tracing")]
+    algorithm: &'static str,
+    #[cfg(feature = "tracing")]
+    expanded: usize,
+    #[cfg(feature = "tracing")]
+    relaxed: usize,
+    #[cfg(feature = "tracing")]
+    max_frontier: usize,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl SearchStats {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn new(algorithm: &'static str) -> Self {
+        SearchStats {
+            algorithm,
+            expanded: 0,
+            relaxed: 0,
+            max_frontier: 0,
+            _span: tracing::debug_span!(
+                "search",
+                algorithm,
+                expanded = tracing::field::Empty,
+                relaxations = tracing::field::Empty,
+                max_frontier = tracing::field::Empty,
+            )
+            .entered(),
+        }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn new(_algorithm: &'static str) -> Self {
+        SearchStats {}
+    }
+
+    /**
+     * Counts a node being expanded, with `frontier` nodes still waiting.
+     */
+    #[inline]
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn expand(&mut self, frontier: usize) {
+        #[cfg(feature = "tracing")]
+        {
+            self.expanded += 1;
+            self.max_frontier = self.max_frontier.max(frontier);
+            tracing::trace!(expanded = self.expanded, frontier, "expand");
+        }
+    }
+
+    /**
+     * Counts an edge improving the best known cost of its target.
+     */
+    #[inline]
+    pub(crate) fn relax(&mut self) {
+        #[cfg(feature = "tracing")]
+        {
+            self.relaxed += 1;
+            tracing::trace!(relaxations = self.relaxed, "relax");
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for SearchStats {
+    fn drop(&mut self) {
+        self._span.record("expanded", self.expanded);
+        self._span.record("relaxations", self.relaxed);
+        self._span.record("max_frontier", self.max_frontier);
+        tracing::debug!(
+            algorithm = self.algorithm,
+            expanded = self.expanded,
+            relaxations = self.relaxed,
+            max_frontier = self.max_frontier,
+            "search finished"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{Algorithm, Dijkstra},
+        graph::Graph,
+        impls::adj_list::AdjListGraph,
+    };
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    /**
+     * Subscriber collecting the fields of each event, and those recorded on spans,
+     * as `name=value` strings.
+     */
+    #[derive(Clone, Default)]
+    struct Collector {
+        events: Arc<Mutex<Vec<Vec<String>>>>,
+        records: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            let mut fields = Fields(Vec::new());
+            values.record(&mut fields);
+            self.records.lock().unwrap().extend(fields.0);
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(Vec::new());
+            event.record(&mut fields);
+            self.events.lock().unwrap().push(fields.0);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_search_events() {
+        let mut g = AdjListGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(b, c);
+
+        let collector = Collector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            Dijkstra::new(&g).run(a, c);
+            SearchStats::new("test").relax();
+        });
+
+        let events = collector.events.lock().unwrap();
+        let with = |message: &str| {
+            let message = format!("message={}", message);
+            events.iter().filter(move |e| e.contains(&message))
+        };
+        // recorded during the search, with the counts so far
+        let expanded: Vec<_> = with("expand").map(|e| e[1].clone()).collect();
+        assert_eq!(expanded, vec!["expanded=1", "expanded=2"]);
+        assert_eq!(with("relax").count(), 3);
+
+        let summaries: Vec<_> = with("search finished").collect();
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries[0].contains(&"algorithm=\"dijkstra\"".to_string()));
+        assert!(summaries[0].contains(&"expanded=2".to_string()));
+        assert!(summaries[0].contains(&"relaxations=2".to_string()));
+        assert!(summaries[1].contains(&"relaxations=1".to_string()));
+
+        let records = collector.records.lock().unwrap();
+        assert_eq!(
+            records[..3],
+            ["expanded=2", "relaxations=2", "max_frontier=0"]
+        );
+    }
+}
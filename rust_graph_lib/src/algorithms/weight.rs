@@ -0,0 +1,237 @@
+use super::Measure;
+use std::{cmp::Ordering, fmt::Display};
+
+/**
+ * Additive edge weight, combined by summing: the usual cost of a path.
+ *
+ * It behaves like the wrapped [`Measure`], and exists to spell out the meaning of a
+ * weight next to [`Prob`], e.g. `WeightedGraph<Weight = Cost<u32>>`.
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Cost<W>(pub W);
+
+impl<W: Measure> Measure for Cost<W> {
+    fn zero() -> Self {
+        Cost(W::zero())
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Cost(self.0.combine(other.0))
+    }
+
+    fn checked_combine(self, other: Self) -> Option<Self> {
+        self.0.checked_combine(other.0).map(Cost)
+    }
+}
+
+impl<W: Display> Display for Cost<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/**
+ * Probability of an edge, combined by multiplying, so that the shortest path
+ * algorithms find the most probable path, e.g. the most reliable route in a network
+ * whose links fail independently.
+ *
+ * Higher probabilities compare as smaller, since smaller measures are better, and
+ * the empty path has probability 1. Probabilities are kept between 0 and 1, which
+ * makes paths only get less probable as they grow, as the searches require.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Prob<F>(F);
+
+mod sealed {
+    use std::{cmp::Ordering, ops::Mul};
+
+    /**
+     * Floating point types usable as [`super::Prob`].
+     */
+    pub trait Float: Copy + PartialOrd + Mul<Output = Self> {
+        const ZERO: Self;
+        const ONE: Self;
+
+        fn total_cmp(&self, other: &Self) -> Ordering;
+    }
+
+    macro_rules! impl_float {
+        ($($t:ty),*) => {
+            $(
+                impl Float for $t {
+                    const ZERO: Self = 0.0;
+                    const ONE: Self = 1.0;
+
+                    fn total_cmp(&self, other: &Self) -> Ordering {
+                        <$t>::total_cmp(self, other)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_float!(f32, f64);
+}
+
+impl<F: sealed::Float> Prob<F> {
+    /**
+     * Wraps a probability, returning `None` unless it is between 0 and 1; `-0.0` is
+     * taken as `0.0`.
+     */
+    pub fn new(p: F) -> Option<Self> {
+        // -0.0 == 0.0, but they differ for total_cmp, which orders probabilities
+        let p = if p == F::ZERO { F::ZERO } else { p };
+        (F::ZERO..=F::ONE).contains(&p).then_some(Prob(p))
+    }
+
+    /**
+     * Returns the probability.
+     */
+    pub fn get(self) -> F {
+        self.0
+    }
+}
+
+impl<F: sealed::Float> PartialEq for Prob<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<F: sealed::Float> Eq for Prob<F> {}
+
+impl<F: sealed::Float> Ord for Prob<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0)
+    }
+}
+
+impl<F: sealed::Float> PartialOrd for Prob<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: sealed::Float> Measure for Prob<F> {
+    fn zero() -> Self {
+        Prob(F::ONE)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Prob(self.0 * other.0)
+    }
+}
+
+impl<F: Display> Display for Prob<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/**
+ * Width of a path, the smallest capacity of its edges, combined by taking the
+ * minimum, so that the shortest path algorithms find the widest path; see
+ * [`widest_path`](super::widest_path).
+ *
+ * Wider paths compare as smaller, since smaller measures are better, and the empty
+ * path is unbounded, wider than any other.
+ */
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Width<W>(Option<W>);
+
+impl<W> Width<W> {
+    /**
+     * Returns the width of an edge with the given capacity.
+     */
+    pub fn new(capacity: W) -> Self {
+        Width(Some(capacity))
+    }
+
+    /**
+     * Returns the width, or `None` if it is unbounded.
+     */
+    pub fn get(self) -> Option<W> {
+        self.0
+    }
+}
+
+impl<W: Ord> Ord for Width<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(a), Some(b)) => b.cmp(a),
+        }
+    }
+}
+
+impl<W: Ord> PartialOrd for Width<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Ord + Copy> Measure for Width<W> {
+    fn zero() -> Self {
+        Width(None)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        self.max(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::Dijkstra, graph::Graph, impls::adj_list::AdjListGraph};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_cost() {
+        assert_eq!(Cost(2u32).combine(Cost(3)), Cost(5));
+        assert_eq!(Cost(u8::MAX).checked_combine(Cost(1)), None);
+        assert!(Cost(1u32) < Cost(2));
+    }
+
+    #[test]
+    fn test_prob() {
+        assert_eq!(Prob::new(1.5), None);
+        assert_eq!(Prob::new(f64::NAN), None);
+        let (half, quarter) = (Prob::new(0.5).unwrap(), Prob::new(0.25).unwrap());
+        assert_eq!(half.combine(half), quarter);
+        assert!(half < quarter);
+        assert_eq!(Prob::<f32>::zero().get(), 1.0);
+
+        let (zero, negative) = (Prob::new(0.0).unwrap(), Prob::new(-0.0f64).unwrap());
+        assert_eq!(negative.get().to_bits(), 0.0f64.to_bits());
+        assert!(zero == negative && zero.cmp(&negative) == Ordering::Equal);
+        assert_eq!(zero.combine(half), zero);
+    }
+
+    #[test]
+    fn test_width() {
+        let (narrow, wide) = (Width::new(3u32), Width::new(10));
+        assert_eq!(narrow.combine(wide), narrow);
+        assert!(wide < narrow && Width::zero() < wide);
+        assert_eq!(Width::<u32>::zero().combine(wide), wide);
+        assert_eq!(Width::<u32>::zero().get(), None);
+    }
+
+    #[test]
+    fn test_most_probable_path() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        let mut probs = HashMap::new();
+        for (f, t, p) in [(0, 3, 0.3f64), (0, 1, 0.9), (1, 2, 0.8), (2, 3, 0.7)] {
+            g.add_edge(ids[f], ids[t]);
+            probs.insert((ids[f], ids[t]), Prob::new(p).unwrap());
+        }
+
+        let search = Dijkstra::with_weights(&g, |f, t| probs[&(f, t)]);
+        let path = search.run_with_cost(ids[0], ids[3]).unwrap();
+        assert_eq!(path.path, ids);
+        assert!((path.cost.get() - 0.504).abs() < 1e-12);
+    }
+}
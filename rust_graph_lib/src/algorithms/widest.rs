@@ -0,0 +1,56 @@
+use super::{CostedPath, Dijkstra, Width};
+use crate::graph::ImplicitGraph;
+use std::hash::Hash;
+
+/**
+ * Finds the widest path from `from` to `to`, maximizing the smallest `capacity(f, t)`
+ * of its edges, e.g. the route with the most bandwidth in a network.
+ *
+ * The search is [`Dijkstra`] over the [`Width`] measure, so among paths equally wide
+ * it finds the one [`Dijkstra`] prefers.
+ */
+pub fn widest_path<G, W, F>(
+    graph: &G,
+    from: G::Index,
+    to: G::Index,
+    capacity: F,
+) -> Option<CostedPath<G::Index, Width<W>>>
+where
+    G: ImplicitGraph,
+    G::Index: Hash + Ord + Copy,
+    W: Ord + Copy,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    Dijkstra::with_weights(graph, |f, t| Width::new(capacity(f, t))).run_with_cost(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, WeightedGraph},
+        impls::undirected_weighted::UndirectedWeightedGraph,
+    };
+
+    #[test]
+    fn test_widest_path() {
+        // the direct link is short but narrow, the detour is long but wide
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        for (f, t, w) in [(0, 4, 2u32), (0, 1, 10), (1, 2, 8), (2, 4, 9), (1, 3, 1)] {
+            g.add_weighted_edge(ids[f], ids[t], w);
+        }
+        let capacity = |f, t| g.edge_weight(f, t).unwrap();
+
+        let path = widest_path(&g, ids[0], ids[4], capacity).unwrap();
+        assert_eq!(path.path, vec![ids[0], ids[1], ids[2], ids[4]]);
+        assert_eq!(path.cost.get(), Some(8));
+        assert_eq!(
+            widest_path(&g, ids[0], ids[3], capacity)
+                .unwrap()
+                .cost
+                .get(),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+use std::fmt::{Display, Formatter};
+
+/**
+ * Layout used by [`GraphFormatter`].
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum FormatStyle {
+    /**
+     * One line per node, followed by one line per edge (`a -> b`).
+     */
+    #[default]
+    EdgeList,
+    /**
+     * One line per node listing its adjacent nodes (`a: b, c`).
+     */
+    AdjacencyList,
+}
+
+type LabelFn<'a, I> = Box<dyn Fn(&I) -> String + 'a>;
+
+/**
+ * Configurable [`Display`] adapter for any [`GraphRead`].
+ *
+ * ```
+ * use rust_graph_lib::{format::{FormatStyle, GraphFormatter}, graph::Graph, impls::adj_list::AdjListGraph};
+ *
+ * let mut g = AdjListGraph::new();
+ * let a = g.add_node();
+ * let b = g.add_node();
+ * g.add_edge(a, b);
+ *
+ * let out = GraphFormatter::new(&g)
+ *     .style(FormatStyle::AdjacencyList)
+ *     .sorted(true)
+ *     .to_string();
+ * assert_eq!(out, "1: 2\n2:\n");
+ * ```
+ */
+pub struct GraphFormatter<'a, G: GraphRead> {
+    graph: &'a G,
+    style: FormatStyle,
+    sorted: bool,
+    max_lines: Option<usize>,
+    label: Option<LabelFn<'a, G::Index>>,
+}
+
+impl<'a, G: GraphRead> GraphFormatter<'a, G> {
+    /**
+     * Creates a formatter with the default settings: edge list style,
+     * unsorted, no line limit and nodes printed with their [`Display`] impl.
+     */
+    pub fn new(graph: &'a G) -> Self {
+        GraphFormatter {
+            graph,
+            style: FormatStyle::default(),
+            sorted: false,
+            max_lines: None,
+            label: None,
+        }
+    }
+
+    /**
+     * Sets the output layout.
+     */
+    pub fn style(mut self, style: FormatStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /**
+     * Sorts nodes and edges by index, making the output deterministic.
+     */
+    pub fn sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /**
+     * Limits the output to `max_lines` lines, followed by a summary of the omitted ones,
+     * which are never formatted.
+     */
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /**
+     * Maps each node to the label printed in its place.
+     */
+    pub fn labels<F: Fn(&G::Index) -> String + 'a>(mut self, label: F) -> Self {
+        self.label = Some(Box::new(label));
+        self
+    }
+
+    fn label(&self, n: &G::Index) -> String
+    where
+        G::Index: Display,
+    {
+        match &self.label {
+            Some(f) => f(n),
+            None => n.to_string(),
+        }
+    }
+
+    fn nodes(&self) -> Box<NodeIterator<'_, G::Index>> {
+        if !self.sorted {
+            return self.graph.iter_nodes();
+        }
+        let mut nodes: Vec<_> = self.graph.iter_nodes().collect();
+        nodes.sort();
+        Box::new(nodes.into_iter())
+    }
+
+    /**
+     * Returns the number of lines of the whole output, along with the lines, which
+     * are only formatted as they are iterated.
+     */
+    fn lines(&self) -> (usize, Box<dyn Iterator<Item = String> + '_>)
+    where
+        G::Index: Display + Copy,
+    {
+        let nodes = self.nodes();
+        match self.style {
+            FormatStyle::EdgeList => {
+                let edges: Box<EdgeIterator<'_, G::Index>> = if self.sorted {
+                    let mut edges: Vec<_> = self.graph.iter_edges().collect();
+                    edges.sort();
+                    Box::new(edges.into_iter())
+                } else {
+                    self.graph.iter_edges()
+                };
+
+                let nodes = nodes.map(|n| format!("Node {}", self.label(&n)));
+                let edges = edges.map(|(x, y)| format!("{} -> {}", self.label(&x), self.label(&y)));
+                let total = self.graph.node_count() + self.graph.edge_count();
+                (total, Box::new(nodes.chain(edges)))
+            }
+            FormatStyle::AdjacencyList => {
+                let lines = nodes.map(|n| {
+                    let mut adj: Vec<_> = self.graph.iter_adj(n).into_iter().flatten().collect();
+                    if self.sorted {
+                        adj.sort();
+                    }
+
+                    let adj = adj
+                        .iter()
+                        .map(|a| self.label(a))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if adj.is_empty() {
+                        format!("{}:", self.label(&n))
+                    } else {
+                        format!("{}: {}", self.label(&n), adj)
+                    }
+                });
+                (self.graph.node_count(), Box::new(lines))
+            }
+        }
+    }
+}
+
+impl<G: GraphRead> Display for GraphFormatter<'_, G>
+where
+    G::Index: Display + Copy,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (total, lines) = self.lines();
+        let shown = self.max_lines.unwrap_or(total).min(total);
+
+        // only the lines shown are formatted
+        for line in lines.take(shown) {
+            writeln!(f, "{}", line)?;
+        }
+        if shown < total {
+            writeln!(f, "... ({} more lines)", total - shown)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+    use std::cell::Cell;
+
+    fn path_graph() -> AdjListGraph {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id3);
+        g.add_edge(id2, id3);
+        g
+    }
+
+    #[test]
+    fn test_edge_list_sorted() {
+        let g = path_graph();
+        let out = GraphFormatter::new(&g).sorted(true).to_string();
+        assert_eq!(out, "Node 1\nNode 2\nNode 3\n1 -> 2\n1 -> 3\n2 -> 3\n");
+    }
+
+    #[test]
+    fn test_adjacency_list_with_labels() {
+        let g = path_graph();
+        let out = GraphFormatter::new(&g)
+            .style(FormatStyle::AdjacencyList)
+            .sorted(true)
+            .labels(|n| format!("n{}", n))
+            .to_string();
+        assert_eq!(out, "n1: n2, n3\nn2: n3\nn3:\n");
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let g = path_graph();
+        let out = GraphFormatter::new(&g)
+            .sorted(true)
+            .max_lines(4)
+            .to_string();
+        assert_eq!(out, "Node 1\nNode 2\nNode 3\n1 -> 2\n... (2 more lines)\n");
+    }
+
+    #[test]
+    fn test_max_lines_formats_only_shown_lines() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..1000).map(|_| g.add_node()).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1]);
+        }
+
+        for style in [FormatStyle::EdgeList, FormatStyle::AdjacencyList] {
+            let labelled = Cell::new(0);
+            let out = GraphFormatter::new(&g)
+                .style(style)
+                .max_lines(3)
+                .labels(|n| {
+                    labelled.set(labelled.get() + 1);
+                    n.to_string()
+                })
+                .to_string();
+            assert!(labelled.get() <= 6);
+            let omitted = if style == FormatStyle::EdgeList {
+                1996
+            } else {
+                997
+            };
+            assert!(out.ends_with(&format!("... ({} more lines)\n", omitted)));
+        }
+    }
+}
@@ -1,13 +1,180 @@
+use std::{collections::HashMap, hash::Hash, iter, iter::Sum, rc::Rc, sync::Arc};
+
 pub type Edge<Idx> = (Idx, Idx);
 pub type NodeIterator<'s, Idx> = dyn Iterator<Item = Idx> + 's;
 pub type EdgeIterator<'s, Idx> = dyn Iterator<Item = Edge<Idx>> + 's;
+pub type IncidentIterator<'s, Idx> = dyn Iterator<Item = (Idx, Idx, Direction)> + 's;
 
 /**
- * Graph trait.
+ * Direction of an edge relative to one of its endpoints.
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum Direction {
+    Outgoing,
+    Incoming,
+}
+
+/**
+ * Read-only graph trait, implemented by graphs and by views over them.
+ */
+pub trait GraphRead {
+    type Index: Ord;
+
+    /**
+     * Checks if an edge is in the graph.
+     */
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool;
+    /**
+     * Returns the count of nodes in the graph.
+     */
+    fn node_count(&self) -> usize;
+    /**
+     * Returns the count of edges in the graph.
+     */
+    fn edge_count(&self) -> usize;
+    /**
+     * Returns an iterator over all nodes in the graph.
+     */
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>>;
+    /**
+     * Returns an iterator over nodes adjacent to the specified node in the graph.
+     */
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>>;
+    /**
+     * Returns an iterator over all edges in the graph.
+     */
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>>;
+
+    /**
+     * Checks if the graph has no nodes.
+     */
+    fn is_empty(&self) -> bool {
+        self.node_count() == 0
+    }
+    /**
+     * Returns the number of nodes adjacent to the specified node, if it is in the graph.
+     */
+    fn neighbors_count(&self, n: Self::Index) -> Option<usize> {
+        self.iter_adj(n).map(Iterator::count)
+    }
+    /**
+     * Returns an iterator over the edges leaving the specified node.
+     */
+    fn edges_of(&self, n: Self::Index) -> Box<EdgeIterator<'_, Self::Index>>
+    where
+        Self::Index: Copy,
+    {
+        match self.iter_adj(n) {
+            Some(adj) => Box::new(adj.map(move |a| (n, a))),
+            None => Box::new(iter::empty()),
+        }
+    }
+    /**
+     * Returns an iterator over the edges touching the specified node, as the
+     * `(from, to)` listed by [`GraphRead::iter_edges`] along with their direction
+     * relative to the node. Loops are listed once, as outgoing.
+     *
+     * The default implementation scans all the edges to find the incoming ones;
+     * graphs that index them override it.
+     */
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>>
+    where
+        Self::Index: Copy,
+    {
+        let Some(adj) = self.iter_adj(n) else {
+            return Box::new(iter::empty());
+        };
+        let outgoing = adj.map(move |a| (n, a, Direction::Outgoing));
+        let incoming = self
+            .iter_edges()
+            .filter(move |&(f, t)| t == n && f != n)
+            .map(|(f, t)| (f, t, Direction::Incoming));
+        Box::new(outgoing.chain(incoming))
+    }
+    /**
+     * Checks if any of the `sources` has an edge to `target`.
+     */
+    fn contains_edge_between_any(&self, sources: &[Self::Index], target: Self::Index) -> bool
+    where
+        Self::Index: Copy,
+    {
+        sources.iter().any(|&s| self.has_edge(s, target))
+    }
+}
+
+// shared pointers only give read access, so they implement `GraphRead` but not `Graph`
+macro_rules! impl_graph_read_deref {
+    ($($t:ty),*) => {
+        $(
+            impl<G: GraphRead + ?Sized> GraphRead for $t {
+                type Index = G::Index;
+
+                fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+                    (**self).has_edge(f, t)
+                }
+
+                fn node_count(&self) -> usize {
+                    (**self).node_count()
+                }
+
+                fn edge_count(&self) -> usize {
+                    (**self).edge_count()
+                }
+
+                fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+                    (**self).iter_nodes()
+                }
+
+                fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+                    (**self).iter_adj(n)
+                }
+
+                fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+                    (**self).iter_edges()
+                }
+
+                fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>>
+                where
+                    Self::Index: Copy,
+                {
+                    (**self).iter_incident(n)
+                }
+            }
+        )*
+    };
+}
+
+impl_graph_read_deref!(&G, Rc<G>, Arc<G>);
+
+/**
+ * Graph defined by a successor function, whose nodes can be generated on demand
+ * instead of being stored up front.
+ *
+ * Every [`GraphRead`] is an implicit graph whose successors are the adjacent nodes,
+ * and the search algorithms only require this trait, so they can also explore
+ * state spaces too large to materialize.
  */
-pub trait Graph {
+pub trait ImplicitGraph {
     type Index: Ord;
 
+    /**
+     * Returns an iterator over the nodes reachable from `n` with one edge.
+     */
+    fn successors(&self, n: Self::Index) -> Box<NodeIterator<'_, Self::Index>>;
+}
+
+impl<G: GraphRead> ImplicitGraph for G {
+    type Index = G::Index;
+
+    fn successors(&self, n: Self::Index) -> Box<NodeIterator<'_, Self::Index>> {
+        self.iter_adj(n).unwrap_or_else(|| Box::new(iter::empty()))
+    }
+}
+
+/**
+ * Graph trait.
+ */
+pub trait Graph: GraphRead {
     /**
      * Add a node to the graph.
      */
@@ -16,10 +183,6 @@ pub trait Graph {
      * Add an edge to the graph.
      */
     fn add_edge(&mut self, f: Self::Index, t: Self::Index);
-    /**
-     * Checks if an edge is in the graph.
-     */
-    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool;
     /**
      * Remove a node from the graph.
      */
@@ -29,23 +192,338 @@ pub trait Graph {
      */
     fn remove_edge(&mut self, f: Self::Index, t: Self::Index);
     /**
-     * Returns the count of nodes in the graph.
+     * Removes the nodes for which `remove` returns `true`, with all their edges,
+     * returning how many were removed.
+     *
+     * The default implementation removes the nodes one at a time; implementations
+     * override it to drop the edges in a single pass over the graph.
      */
-    fn node_count(&self) -> usize;
+    fn remove_nodes_where<F>(&mut self, mut remove: F) -> usize
+    where
+        Self: Sized,
+        Self::Index: Copy,
+        F: FnMut(Self::Index) -> bool,
+    {
+        let nodes: Vec<_> = self.iter_nodes().filter(|&n| remove(n)).collect();
+        let count = nodes.len();
+        for n in nodes {
+            self.remove_node(n);
+        }
+        count
+    }
     /**
-     * Returns the count of edges in the graph.
+     * Adds all the edges produced by `edges`, creating nodes for the ids not yet in `ids`.
+     *
+     * `progress` is called after each edge with the number of edges added so far.
+     * Stops at the first error, keeping the edges added before it.
+     * Returns the number of edges added.
      */
-    fn edge_count(&self) -> usize;
+    fn extend_from_stream<K, E, I, P>(
+        &mut self,
+        edges: I,
+        ids: &mut HashMap<K, Self::Index>,
+        mut progress: P,
+    ) -> Result<usize, E>
+    where
+        Self: Sized,
+        Self::Index: Copy,
+        K: Hash + Eq,
+        I: IntoIterator<Item = Result<(K, K), E>>,
+        P: FnMut(usize),
+    {
+        let mut count = 0;
+        for edge in edges {
+            let (f, t) = edge?;
+            let f = *ids.entry(f).or_insert_with(|| self.add_node());
+            let t = *ids.entry(t).or_insert_with(|| self.add_node());
+            self.add_edge(f, t);
+            count += 1;
+            progress(count);
+        }
+        Ok(count)
+    }
+}
+
+/**
+ * Graph whose edges carry a weight.
+ */
+pub trait WeightedGraph: Graph {
+    type Weight: Copy;
+
     /**
-     * Returns an iterator over all nodes in the graph.
+     * Add an edge with the given weight to the graph, replacing the weight if the edge exists.
      */
-    fn iter_nodes(&self) -> Box<NodeIterator<Self::Index>>;
+    fn add_weighted_edge(&mut self, f: Self::Index, t: Self::Index, w: Self::Weight);
     /**
-     * Returns an iterator over nodes adjacent to the specified node in the graph.
+     * Returns the weight of an edge, if the edge is in the graph.
      */
-    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<Self::Index>>>;
+    fn edge_weight(&self, f: Self::Index, t: Self::Index) -> Option<Self::Weight>;
     /**
-     * Returns an iterator over all edges in the graph.
+     * Changes the weight of an edge, returning `false` if the edge is not in the graph.
+     */
+    fn set_edge_weight(&mut self, f: Self::Index, t: Self::Index, w: Self::Weight) -> bool
+    where
+        Self::Index: Copy,
+    {
+        if !self.has_edge(f, t) {
+            return false;
+        }
+        self.add_weighted_edge(f, t, w);
+        true
+    }
+    /**
+     * Replaces the weight of an edge with the result of `update` on it, returning
+     * `false` if the edge is not in the graph.
+     */
+    fn update_edge_weight<F>(&mut self, f: Self::Index, t: Self::Index, update: F) -> bool
+    where
+        Self: Sized,
+        Self::Index: Copy,
+        F: FnOnce(Self::Weight) -> Self::Weight,
+    {
+        match self.edge_weight(f, t) {
+            Some(w) => self.set_edge_weight(f, t, update(w)),
+            None => false,
+        }
+    }
+    /**
+     * Returns the sum of the weights of the edges leaving a node, if it is in the graph.
      */
-    fn iter_edges(&self) -> Box<EdgeIterator<Self::Index>>;
+    fn out_strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Index: Copy,
+        Self::Weight: Sum,
+    {
+        let adj = self.iter_adj(n)?;
+        Some(adj.filter_map(|a| self.edge_weight(n, a)).sum())
+    }
+    /**
+     * Returns the sum of the weights of the edges reaching a node, if it is in the graph.
+     *
+     * The default implementation looks up an edge from every node of the graph.
+     */
+    fn in_strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Index: Copy,
+        Self::Weight: Sum,
+    {
+        let inc = self.iter_nodes().filter_map(|m| self.edge_weight(m, n));
+        self.iter_adj(n).map(|_| inc.sum())
+    }
+    /**
+     * Returns the sum of the weights of the edges incident to a node, if it is in the
+     * graph: the in and out strength of directed graphs, in which loops count twice.
+     */
+    fn strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Index: Copy,
+        Self::Weight: Sum,
+    {
+        let out = self
+            .iter_adj(n)?
+            .filter_map(move |a| self.edge_weight(n, a));
+        let inc = self.iter_nodes().filter_map(|m| self.edge_weight(m, n));
+        Some(out.chain(inc).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::{
+        adj_list::{AdjListGraph, Index},
+        adj_vec::AdjVecGraph,
+        multi::{EdgeKind, MultiGraph},
+        undirected_weighted::UndirectedWeightedGraph,
+    };
+    use crate::views::Reversed;
+
+    /**
+     * Directed graph with weights stored on the side, to test the provided methods.
+     */
+    #[derive(Default)]
+    struct Directed(AdjListGraph, HashMap<(Index, Index), u32>);
+
+    impl GraphRead for Directed {
+        type Index = Index;
+
+        fn has_edge(&self, f: Index, t: Index) -> bool {
+            self.0.has_edge(f, t)
+        }
+        fn node_count(&self) -> usize {
+            self.0.node_count()
+        }
+        fn edge_count(&self) -> usize {
+            self.0.edge_count()
+        }
+        fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+            self.0.iter_nodes()
+        }
+        fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+            self.0.iter_adj(n)
+        }
+        fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+            self.0.iter_edges()
+        }
+    }
+
+    impl Graph for Directed {
+        fn add_node(&mut self) -> Index {
+            self.0.add_node()
+        }
+        fn add_edge(&mut self, f: Index, t: Index) {
+            self.add_weighted_edge(f, t, 1);
+        }
+        fn remove_node(&mut self, n: Index) {
+            self.0.remove_node(n);
+            self.1.retain(|&(f, t), _| f != n && t != n);
+        }
+        fn remove_edge(&mut self, f: Index, t: Index) {
+            self.0.remove_edge(f, t);
+            self.1.remove(&(f, t));
+        }
+    }
+
+    impl WeightedGraph for Directed {
+        type Weight = u32;
+
+        fn add_weighted_edge(&mut self, f: Index, t: Index, w: u32) {
+            self.0.add_edge(f, t);
+            self.1.insert((f, t), w);
+        }
+        fn edge_weight(&self, f: Index, t: Index) -> Option<u32> {
+            self.1.get(&(f, t)).copied()
+        }
+    }
+
+    #[test]
+    fn test_derived_queries() {
+        let mut g = AdjListGraph::new();
+        assert!(g.is_empty());
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id3);
+        g.remove_node(id3);
+
+        assert!(!g.is_empty());
+        assert_eq!(g.neighbors_count(id1), Some(1));
+        assert_eq!(g.neighbors_count(id3), None);
+        assert_eq!(g.edges_of(id1).collect::<Vec<_>>(), vec![(id1, id2)]);
+        assert_eq!(g.edges_of(id3).count(), 0);
+        assert!(g.contains_edge_between_any(&[id2, id1], id2));
+        assert!(!g.contains_edge_between_any(&[id2], id1));
+    }
+
+    /**
+     * Checks `iter_incident` against the edges of `iter_edges` touching each node.
+     */
+    fn check_incident<G: GraphRead>(g: &G)
+    where
+        G::Index: Copy + std::fmt::Debug,
+    {
+        for n in g.iter_nodes() {
+            let mut expected: Vec<_> = g
+                .iter_edges()
+                .filter_map(|(f, t)| match (f == n, t == n) {
+                    (true, _) => Some((f, t, Direction::Outgoing)),
+                    (false, true) => Some((f, t, Direction::Incoming)),
+                    _ => None,
+                })
+                .collect();
+            let mut actual: Vec<_> = g.iter_incident(n).collect();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_iter_incident() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        for (f, t) in [(0, 1), (1, 2), (2, 0), (1, 1), (0, 2)] {
+            g.add_edge(ids[f], ids[t]);
+        }
+        let mut incident: Vec<_> = g.iter_incident(ids[1]).collect();
+        incident.sort();
+        assert_eq!(
+            incident,
+            vec![
+                (ids[0], ids[1], Direction::Incoming),
+                (ids[1], ids[1], Direction::Outgoing),
+                (ids[1], ids[2], Direction::Outgoing),
+            ]
+        );
+        assert_eq!(g.iter_incident(ids[3]).count(), 0);
+        assert_eq!(g.iter_incident(ids[3].next()).count(), 0);
+        check_incident(&g);
+        check_incident(&Reversed::new(&g));
+
+        let mut u = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| u.add_node()).collect();
+        for (f, t) in [(0, 1), (2, 1), (2, 2)] {
+            u.add_weighted_edge(ids[f], ids[t], 1u32);
+        }
+        check_incident(&u);
+
+        let mut m = MultiGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| m.add_node()).collect();
+        m.add_edge(ids[0], ids[1]);
+        m.add_edge(ids[0], ids[1]);
+        m.add_edge_of_kind(ids[2], ids[1], EdgeKind::Undirected);
+        m.add_edge(ids[2], ids[2]);
+        check_incident(&m);
+    }
+
+    #[test]
+    fn test_remove_nodes_where() {
+        let mut g = AdjVecGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1]);
+        }
+
+        let odd: Vec<_> = ids.iter().copied().skip(1).step_by(2).collect();
+        assert_eq!(g.remove_nodes_where(|n| odd.contains(&n)), 2);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[test]
+    fn test_strength() {
+        let mut g = Directed::default();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 2);
+        g.add_weighted_edge(ids[0], ids[2], 3);
+        g.add_weighted_edge(ids[2], ids[0], 4);
+        g.add_weighted_edge(ids[1], ids[1], 5);
+
+        assert_eq!(g.out_strength(ids[0]), Some(5));
+        assert_eq!(g.in_strength(ids[0]), Some(4));
+        assert_eq!(g.strength(ids[0]), Some(9));
+        assert_eq!(g.strength(ids[1]), Some(12));
+        assert_eq!(g.strength(ids[3]), Some(0));
+        assert_eq!(g.in_strength(ids[3].next()), None);
+    }
+
+    #[test]
+    fn test_shared_pointers() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id1, id2);
+
+        fn count<G: GraphRead>(g: G) -> usize {
+            g.edge_count()
+        }
+        assert_eq!(count(&g), 1);
+        let rc = Rc::new(g);
+        assert!(rc.has_edge(id1, id2));
+        assert_eq!(count(Rc::clone(&rc)), 1);
+        let arc = Arc::new(Rc::try_unwrap(rc).ok().unwrap());
+        assert_eq!(arc.neighbors_count(id1), Some(1));
+        assert_eq!(count(arc), 1);
+    }
 }
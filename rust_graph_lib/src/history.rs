@@ -0,0 +1,339 @@
+use crate::graph::{EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator, WeightedGraph};
+use std::{collections::HashMap, convert::Infallible, hash::Hash, iter::Sum};
+
+/**
+ * Mutation of a graph, as recorded by a [`RecordingGraph`].
+ *
+ * Logs of unweighted graphs use the default weight type [`Infallible`], so they
+ * cannot contain weighted edges.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GraphOp<I, W = Infallible> {
+    /**
+     * A node was added and given the index.
+     */
+    AddNode(I),
+    /**
+     * A node was removed, along with all its edges.
+     */
+    RemoveNode(I),
+    /**
+     * An edge was added, whether or not the graph already had it.
+     */
+    AddEdge(I, I),
+    /**
+     * An edge was added with the weight, or its weight was changed to it.
+     */
+    AddWeightedEdge(I, I, W),
+    /**
+     * An edge in the graph was removed.
+     */
+    RemoveEdge(I, I),
+}
+
+/**
+ * Wrapper around a [`Graph`] logging every mutation, in order, so that it can be
+ * replayed with [`replay`] or saved with [`write_history`](crate::io::history::write_history),
+ * e.g. for audit trails or deterministic replays of simulations.
+ *
+ * Removals of nodes and edges not in the graph are not logged, while additions of
+ * edges always are, even if the edge is already present or an endpoint is missing,
+ * since what they do then depends on the graph: a multigraph adds a parallel edge and
+ * some graphs add the missing endpoints. Replaying an edge whose endpoints were never
+ * logged as added fails. The log is only replayable onto an empty graph if the
+ * recording started from one.
+ */
+pub struct RecordingGraph<G: Graph, W = Infallible> {
+    graph: G,
+    ops: Vec<GraphOp<G::Index, W>>,
+}
+
+impl<G: Graph> RecordingGraph<G> {
+    /**
+     * Wraps the graph, with an empty log; see [`RecordingGraph::weighted`] to record
+     * weighted edges.
+     */
+    pub fn new(graph: G) -> Self {
+        RecordingGraph {
+            graph,
+            ops: Vec::new(),
+        }
+    }
+}
+
+impl<G: WeightedGraph> RecordingGraph<G, G::Weight> {
+    /**
+     * Wraps the graph, with an empty log, recording its weighted edges as well.
+     */
+    pub fn weighted(graph: G) -> Self {
+        RecordingGraph {
+            graph,
+            ops: Vec::new(),
+        }
+    }
+}
+
+impl<G: Graph, W> RecordingGraph<G, W> {
+    /**
+     * Returns the mutations logged so far, oldest first.
+     */
+    pub fn ops(&self) -> &[GraphOp<G::Index, W>] {
+        &self.ops
+    }
+
+    /**
+     * Returns the mutations logged so far and empties the log, e.g. to save it in chunks.
+     */
+    pub fn take_ops(&mut self) -> Vec<GraphOp<G::Index, W>> {
+        std::mem::take(&mut self.ops)
+    }
+
+    /**
+     * Returns the wrapped graph.
+     */
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /**
+     * Unwraps the graph, returning it with the log.
+     */
+    pub fn into_parts(self) -> (G, Vec<GraphOp<G::Index, W>>) {
+        (self.graph, self.ops)
+    }
+}
+
+impl<G: Graph, W> GraphRead for RecordingGraph<G, W>
+where
+    G::Index: Copy,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.has_edge(f, t)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        self.graph.iter_adj(n)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        self.graph.iter_edges()
+    }
+
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>> {
+        self.graph.iter_incident(n)
+    }
+}
+
+impl<G: Graph, W> Graph for RecordingGraph<G, W>
+where
+    G::Index: Copy,
+{
+    fn add_node(&mut self) -> Self::Index {
+        let n = self.graph.add_node();
+        self.ops.push(GraphOp::AddNode(n));
+        n
+    }
+
+    fn add_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.add_edge(f, t);
+        self.ops.push(GraphOp::AddEdge(f, t));
+    }
+
+    fn remove_node(&mut self, n: Self::Index) {
+        let present = self.graph.iter_adj(n).is_some();
+        self.graph.remove_node(n);
+        if present {
+            self.ops.push(GraphOp::RemoveNode(n));
+        }
+    }
+
+    fn remove_edge(&mut self, f: Self::Index, t: Self::Index) {
+        let present = self.graph.has_edge(f, t);
+        self.graph.remove_edge(f, t);
+        if present {
+            self.ops.push(GraphOp::RemoveEdge(f, t));
+        }
+    }
+}
+
+impl<G: WeightedGraph> WeightedGraph for RecordingGraph<G, G::Weight>
+where
+    G::Index: Copy,
+{
+    type Weight = G::Weight;
+
+    fn add_weighted_edge(&mut self, f: Self::Index, t: Self::Index, w: Self::Weight) {
+        self.graph.add_weighted_edge(f, t, w);
+        self.ops.push(GraphOp::AddWeightedEdge(f, t, w));
+    }
+
+    fn edge_weight(&self, f: Self::Index, t: Self::Index) -> Option<Self::Weight> {
+        self.graph.edge_weight(f, t)
+    }
+
+    fn out_strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Weight: Sum,
+    {
+        self.graph.out_strength(n)
+    }
+
+    fn in_strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Weight: Sum,
+    {
+        self.graph.in_strength(n)
+    }
+
+    fn strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Weight: Sum,
+    {
+        self.graph.strength(n)
+    }
+}
+
+fn apply<I, W, G, F>(
+    ops: &[GraphOp<I, W>],
+    graph: &mut G,
+    mut add_weighted: F,
+) -> Result<HashMap<I, G::Index>, I>
+where
+    I: Hash + Eq + Copy,
+    G: Graph,
+    G::Index: Copy,
+    F: FnMut(&mut G, G::Index, G::Index, &W),
+{
+    let mut mapping = HashMap::new();
+    let index = |mapping: &HashMap<I, G::Index>, n: I| mapping.get(&n).copied().ok_or(n);
+    for op in ops {
+        match op {
+            GraphOp::AddNode(n) => {
+                mapping.insert(*n, graph.add_node());
+            }
+            GraphOp::RemoveNode(n) => graph.remove_node(index(&mapping, *n)?),
+            GraphOp::AddEdge(f, t) => {
+                let (f, t) = (index(&mapping, *f)?, index(&mapping, *t)?);
+                graph.add_edge(f, t);
+            }
+            GraphOp::AddWeightedEdge(f, t, w) => {
+                let (f, t) = (index(&mapping, *f)?, index(&mapping, *t)?);
+                add_weighted(graph, f, t, w);
+            }
+            GraphOp::RemoveEdge(f, t) => {
+                let (f, t) = (index(&mapping, *f)?, index(&mapping, *t)?);
+                graph.remove_edge(f, t);
+            }
+        }
+    }
+    Ok(mapping)
+}
+
+/**
+ * Applies the logged mutations to `graph`, adding weighted edges without their weight.
+ *
+ * Recorded indices only identify the nodes within the log, so the graph needs not
+ * give out the same ones: the returned map gives the index in `graph` of each node
+ * added by the log. Fails with the first node used without being added by the log,
+ * having applied the mutations before it.
+ */
+pub fn replay<I, W, G>(ops: &[GraphOp<I, W>], graph: &mut G) -> Result<HashMap<I, G::Index>, I>
+where
+    I: Hash + Eq + Copy,
+    G: Graph,
+    G::Index: Copy,
+{
+    apply(ops, graph, |g, f, t, _| g.add_edge(f, t))
+}
+
+/**
+ * Like [`replay`], adding weighted edges with their weight.
+ */
+pub fn replay_weighted<I, G>(
+    ops: &[GraphOp<I, G::Weight>],
+    graph: &mut G,
+) -> Result<HashMap<I, G::Index>, I>
+where
+    I: Hash + Eq + Copy,
+    G: WeightedGraph,
+    G::Index: Copy,
+{
+    apply(ops, graph, |g, f, t, &w| g.add_weighted_edge(f, t, w))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::{
+        adj_list::AdjListGraph, adj_vec::AdjVecGraph, undirected_weighted::UndirectedWeightedGraph,
+    };
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut g = RecordingGraph::new(AdjListGraph::new());
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.remove_edge(ids[2], ids[0]);
+        g.remove_node(ids[1]);
+        g.add_edge(ids[2], ids[0]);
+
+        assert_eq!(g.ops().len(), 7);
+        assert_eq!(g.ops()[5], GraphOp::RemoveNode(ids[1]));
+
+        let mut copy = AdjVecGraph::new();
+        let mapping = replay(g.ops(), &mut copy).unwrap();
+        assert_eq!((copy.node_count(), copy.edge_count()), (2, 1));
+        assert!(copy.has_edge(mapping[&ids[2]], mapping[&ids[0]]));
+
+        let (_, ops) = g.into_parts();
+        let missing = replay(&ops[1..], &mut AdjListGraph::new());
+        assert_eq!(missing.err(), Some(ids[0]));
+    }
+
+    #[test]
+    fn test_duplicate_edges_logged() {
+        let mut g = RecordingGraph::new(AdjListGraph::new());
+        let [a, b] = [(); 2].map(|_| g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(a, b);
+        assert_eq!(g.ops()[2..], [GraphOp::AddEdge(a, b); 2]);
+
+        let mut copy = AdjListGraph::new();
+        replay(g.ops(), &mut copy).unwrap();
+        assert_eq!(copy.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_replay_weights() {
+        let mut g = RecordingGraph::weighted(UndirectedWeightedGraph::new());
+        let (a, b) = (g.add_node(), g.add_node());
+        g.add_weighted_edge(a, b, 3u32);
+        assert!(g.update_edge_weight(a, b, |w| w + 1));
+        assert_eq!(g.ops().last(), Some(&GraphOp::AddWeightedEdge(a, b, 4)));
+
+        let ops = g.take_ops();
+        assert!(g.ops().is_empty());
+        let mut copy = UndirectedWeightedGraph::new();
+        let mapping = replay_weighted(&ops, &mut copy).unwrap();
+        assert_eq!(copy.edge_weight(mapping[&a], mapping[&b]), Some(4));
+
+        let mut unweighted = AdjListGraph::new();
+        let mapping = replay(&ops, &mut unweighted).unwrap();
+        assert!(unweighted.has_edge(mapping[&a], mapping[&b]));
+    }
+}
@@ -1,9 +1,16 @@
-use crate::graph::{EdgeIterator, Graph, NodeIterator};
-use std::collections::{HashMap, HashSet};
+use crate::format::GraphFormatter;
+use crate::graph::{EdgeIterator, Graph, GraphRead, NodeIterator};
+use crate::invariant::InvariantViolation;
+use crate::memory::{hash_table_bytes, MemoryReport, MemoryUsage};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
+#[cfg(feature = "rayon")]
+use std::hash::Hash;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
-pub struct Index(usize);
+pub struct Index(pub(crate) usize);
 
 impl Index {
     pub fn next(&self) -> Self {
@@ -17,13 +24,42 @@ impl Display for Index {
     }
 }
 
+/**
+ * Whether an [`AdjListGraph`] hands out the indices of removed nodes again.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum IndexReuse {
+    /**
+     * Every node gets a new index, so an index never refers to another node after
+     * its own is removed, at the cost of index space growing with every node added.
+     */
+    #[default]
+    Never,
+    /**
+     * New nodes take the smallest index freed by a removal, if any, so that churning
+     * nodes keeps the index space as large as the graph ever was. An index kept after
+     * its node is removed may then refer to a different node.
+     */
+    FreeList,
+}
+
 /**
  * Adjacency list implementation of [`Graph`].
+ *
+ * Edges are added and removed in constant time; for graphs that are built once and
+ * then only queried, [`AdjVecGraph`](super::adj_vec::AdjVecGraph) is faster and smaller,
+ * and [`freeze`](AdjListGraph::freeze) converts the graph into an immutable
+ * [`FrozenGraph`](super::frozen::FrozenGraph) that is smaller still.
+ *
+ * Indices of removed nodes are never reused unless the graph is created with
+ * [`IndexReuse::FreeList`].
  */
 pub struct AdjListGraph {
-    edges: HashMap<Index, HashSet<Index>>,
-    edge_count: usize,
-    next_id: Index,
+    pub(super) edges: HashMap<Index, HashSet<Index>>,
+    pub(super) edge_count: usize,
+    pub(super) next_id: Index,
+    pub(super) reuse: IndexReuse,
+    pub(super) free: BTreeSet<Index>,
 }
 
 impl AdjListGraph {
@@ -31,11 +67,181 @@ impl AdjListGraph {
      * Creates a new graph.
      */
     pub fn new() -> Self {
+        Self::with_index_reuse(IndexReuse::Never)
+    }
+
+    /**
+     * Creates a new graph with the given policy for the indices of removed nodes.
+     */
+    pub fn with_index_reuse(reuse: IndexReuse) -> Self {
         AdjListGraph {
             edges: HashMap::new(),
             edge_count: 0,
             next_id: Index(1),
+            reuse,
+            free: BTreeSet::new(),
+        }
+    }
+
+    /**
+     * Returns the policy for the indices of removed nodes.
+     */
+    pub fn index_reuse(&self) -> IndexReuse {
+        self.reuse
+    }
+
+    fn release(&mut self, n: Index) {
+        if self.reuse == IndexReuse::FreeList {
+            self.free.insert(n);
+        }
+    }
+
+    /**
+     * Renumbers the live nodes with consecutive indices starting from 1, preserving
+     * their relative order, so that index space freed by removals can be reused.
+     *
+     * Returns the mapping from old to new indices; indices obtained before
+     * the call must be translated through it.
+     */
+    pub fn compact(&mut self) -> HashMap<Index, Index> {
+        let mut nodes: Vec<_> = self.edges.keys().copied().collect();
+        nodes.sort();
+
+        let mapping: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (n, Index(i + 1)))
+            .collect();
+
+        self.edges = self
+            .edges
+            .drain()
+            .map(|(n, adj)| (mapping[&n], adj.iter().map(|a| mapping[a]).collect()))
+            .collect();
+        self.next_id = Index(nodes.len() + 1);
+        self.free.clear();
+
+        mapping
+    }
+
+    /**
+     * Checks the internal bookkeeping: the edge count, that edges only reference
+     * nodes in the graph, that no node has an index not yet assigned and that the
+     * free indices are assigned to no node.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = self.edges.values().map(HashSet::len).sum();
+        if actual != self.edge_count {
+            return Err(InvariantViolation::EdgeCount {
+                stored: self.edge_count,
+                actual,
+            });
+        }
+
+        for (n, adj) in &self.edges {
+            if *n >= self.next_id {
+                return Err(InvariantViolation::Mapping(format!(
+                    "node {} is not below the next index {}",
+                    n, self.next_id
+                )));
+            }
+            if let Some(a) = adj.iter().find(|a| !self.edges.contains_key(a)) {
+                return Err(InvariantViolation::DanglingEdge(format!("{} -> {}", n, a)));
+            }
+        }
+        if let Some(n) = self
+            .free
+            .iter()
+            .find(|&&n| n >= self.next_id || self.edges.contains_key(&n))
+        {
+            return Err(InvariantViolation::Mapping(format!(
+                "free index {} is in use or not yet assigned",
+                n
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl AdjListGraph {
+    /**
+     * Builds a graph from a list of edges between arbitrary node ids, in parallel.
+     *
+     * Edges are sharded by source, each shard is turned into adjacency sets on its own
+     * thread and the shards are then moved into the graph. Nodes are numbered in
+     * ascending id order; the returned map gives the index of each id.
+     */
+    pub fn from_edges_parallel<K, E>(edges: E) -> (Self, HashMap<K, Index>)
+    where
+        K: Hash + Ord + Copy + Send + Sync,
+        E: IntoParallelIterator<Item = (K, K)>,
+    {
+        let edges: Vec<_> = edges.into_par_iter().collect();
+
+        let mut ids: Vec<_> = edges.par_iter().flat_map(|&(f, t)| [f, t]).collect();
+        ids.par_sort_unstable();
+        ids.dedup();
+        let index = |k: &K| Index(ids.binary_search(k).unwrap() + 1);
+
+        let shards = rayon::current_num_threads() * 4;
+        let by_shard = edges
+            .par_chunks(4096)
+            .map(|chunk| {
+                let mut by_shard = vec![Vec::new(); shards];
+                for (f, t) in chunk {
+                    let (f, t) = (index(f), index(t));
+                    by_shard[f.0 % shards].push((f, t));
+                }
+                by_shard
+            })
+            .reduce(
+                || vec![Vec::new(); shards],
+                |mut a, b| {
+                    a.iter_mut().zip(b).for_each(|(a, b)| a.extend(b));
+                    a
+                },
+            );
+        let partial: Vec<HashMap<Index, HashSet<Index>>> = by_shard
+            .into_par_iter()
+            .map(|edges| {
+                let mut adj: HashMap<_, HashSet<_>> = HashMap::new();
+                for (f, t) in edges {
+                    adj.entry(f).or_default().insert(t);
+                }
+                adj
+            })
+            .collect();
+
+        let mut graph = AdjListGraph::new();
+        graph.edges.reserve(ids.len());
+        for adj in partial {
+            graph.edge_count += adj.values().map(HashSet::len).sum::<usize>();
+            graph.edges.extend(adj);
+        }
+        for i in 0..ids.len() {
+            graph.edges.entry(Index(i + 1)).or_default();
         }
+        graph.next_id = Index(ids.len() + 1);
+
+        let mapping = ids.iter().enumerate().map(|(i, &k)| (k, Index(i + 1)));
+        (graph, mapping.collect())
+    }
+
+    /**
+     * Parallel version of [`GraphRead::iter_nodes`].
+     */
+    pub fn par_iter_nodes(&self) -> impl ParallelIterator<Item = Index> + '_ {
+        self.edges.par_iter().map(|(&n, _)| n)
+    }
+
+    /**
+     * Parallel version of [`GraphRead::iter_edges`].
+     */
+    pub fn par_iter_edges(&self) -> impl ParallelIterator<Item = (Index, Index)> + '_ {
+        self.edges
+            .par_iter()
+            .flat_map_iter(|(&f, adj)| adj.iter().map(move |&t| (f, t)))
     }
 }
 
@@ -45,20 +251,62 @@ impl Default for AdjListGraph {
     }
 }
 
-impl Graph for AdjListGraph {
+impl GraphRead for AdjListGraph {
     type Index = Index;
 
+    fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.edges.get(&f).is_some_and(|v| v.contains(&t))
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        Box::new(self.edges.keys().copied())
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.edges.get(&n).map(|adj| {
+            let it = adj.iter();
+            let map: Box<dyn Iterator<Item = Index>> = Box::new(it.copied());
+            map
+        })
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        let it = self
+            .edges
+            .iter()
+            .flat_map(|(k, vs)| vs.iter().map(|v| (*k, *v)));
+
+        Box::new(it)
+    }
+}
+
+impl Graph for AdjListGraph {
     fn add_node(&mut self) -> Index {
-        let id = self.next_id;
-        self.next_id = self.next_id.next();
+        let id = self.free.pop_first().unwrap_or_else(|| {
+            let id = self.next_id;
+            self.next_id = self.next_id.next();
+            id
+        });
         self.edges.insert(id, HashSet::new());
         id
     }
 
     fn add_edge(&mut self, f: Index, t: Index) {
-        self.edges.entry(t).or_insert_with(HashSet::new);
-        self.edges.entry(f).or_insert_with(HashSet::new).insert(t);
-        self.edge_count += 1;
+        for n in [f, t] {
+            self.free.remove(&n);
+        }
+        self.edges.entry(t).or_default();
+        if self.edges.entry(f).or_default().insert(t) {
+            self.edge_count += 1;
+        }
     }
 
     fn remove_node(&mut self, n: Index) {
@@ -69,6 +317,7 @@ impl Graph for AdjListGraph {
         if let Some((_, v)) = self.edges.remove_entry(&n) {
             self.edge_count -= v.len();
         }
+        self.release(n);
 
         let mut to_remove = 0;
         self.edges.iter_mut().for_each(|(_, v)| {
@@ -79,6 +328,33 @@ impl Graph for AdjListGraph {
         self.edge_count -= to_remove;
     }
 
+    fn remove_nodes_where<F>(&mut self, mut remove: F) -> usize
+    where
+        F: FnMut(Index) -> bool,
+    {
+        let doomed: HashSet<_> = self.edges.keys().copied().filter(|&n| remove(n)).collect();
+        if doomed.is_empty() {
+            return 0;
+        }
+
+        let mut removed = 0;
+        self.edges.retain(|n, adj| {
+            if doomed.contains(n) {
+                removed += adj.len();
+                return false;
+            }
+            let len = adj.len();
+            adj.retain(|t| !doomed.contains(t));
+            removed += len - adj.len();
+            true
+        });
+        self.edge_count -= removed;
+        for &n in &doomed {
+            self.release(n);
+        }
+        doomed.len()
+    }
+
     fn remove_edge(&mut self, f: Index, t: Index) {
         if let Some(adjacents) = self.edges.get_mut(&f) {
             if adjacents.remove(&t) {
@@ -86,50 +362,26 @@ impl Graph for AdjListGraph {
             }
         }
     }
+}
 
-    fn node_count(&self) -> usize {
-        self.edges.len()
-    }
-
-    fn edge_count(&self) -> usize {
-        self.edge_count
-    }
-
-    fn has_edge(&self, f: Index, t: Index) -> bool {
-        self.edges.get(&f).map_or(false, |v| v.contains(&t))
-    }
-
-    fn iter_nodes(&self) -> Box<NodeIterator<Index>> {
-        Box::new(self.edges.keys().copied())
-    }
-
-    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<Index>>> {
-        self.edges.get(&n).map(|adj| {
-            let it = adj.iter();
-            let map: Box<dyn Iterator<Item = Index>> = Box::new(it.copied());
-            map
-        })
-    }
-
-    fn iter_edges(&self) -> Box<EdgeIterator<Index>> {
-        let it = self
-            .edges
-            .iter()
-            .flat_map(|(k, vs)| vs.iter().map(|v| (*k, *v)));
-
-        Box::new(it)
+impl MemoryUsage for AdjListGraph {
+    fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: hash_table_bytes::<(Index, HashSet<Index>)>(self.edges.capacity()),
+            adjacency: self
+                .edges
+                .values()
+                .map(|adj| hash_table_bytes::<Index>(adj.capacity()))
+                .sum(),
+            mappings: 0,
+            other: std::mem::size_of::<Self>() + self.free.len() * std::mem::size_of::<Index>(),
+        }
     }
 }
 
 impl Display for AdjListGraph {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for n in self.iter_nodes() {
-            writeln!(f, "Node {}", n)?;
-        }
-        for (x, y) in self.iter_edges() {
-            writeln!(f, "{} -> {}", x, y)?;
-        }
-        Ok(())
+        GraphFormatter::new(self).fmt(f)
     }
 }
 
@@ -188,4 +440,155 @@ mod tests {
         assert_eq!(g.node_count(), 0);
         assert_eq!(g.edge_count(), 0);
     }
+
+    #[test]
+    fn test_remove_nodes_where() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[2], ids[3]);
+        g.add_edge(ids[3], ids[0]);
+
+        assert_eq!(g.remove_nodes_where(|n| n == ids[1] || n == ids[2]), 2);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 1);
+        assert!(g.has_edge(ids[3], ids[0]));
+        assert_eq!(g.remove_nodes_where(|_| false), 0);
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_add_edge_twice() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id2);
+        assert_eq!(g.edge_count(), 1);
+
+        g.remove_edge(id1, id2);
+        assert_eq!(g.edge_count(), 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_from_edges_parallel() {
+        let edges: Vec<(u64, u64)> = (0..10_000)
+            .map(|i| (i % 997 * 10, (i * 31) % 1009 * 10))
+            .collect();
+        let (g, ids) = AdjListGraph::from_edges_parallel(edges.clone());
+
+        let mut expected = AdjListGraph::new();
+        let mut expected_ids = HashMap::new();
+        let stream = edges.iter().map(|&e| Ok::<_, ()>(e));
+        expected
+            .extend_from_stream(stream, &mut expected_ids, |_| {})
+            .unwrap();
+
+        assert_eq!(g.node_count(), expected.node_count());
+        assert_eq!(g.edge_count(), expected.edge_count());
+        assert!(edges.iter().all(|(f, t)| g.has_edge(ids[f], ids[t])));
+        assert_eq!(ids[&0], Index(1));
+        assert_eq!(g.validate(), Ok(()));
+
+        assert_eq!(g.par_iter_nodes().count(), g.node_count());
+        let degree_sum: usize = g
+            .par_iter_nodes()
+            .map(|n| g.neighbors_count(n).unwrap())
+            .sum();
+        assert_eq!(degree_sum, g.edge_count());
+        assert_eq!(g.par_iter_edges().count(), g.edge_count());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id1, id2);
+        assert_eq!(g.validate(), Ok(()));
+
+        g.edge_count = 2;
+        assert_eq!(
+            g.validate(),
+            Err(InvariantViolation::EdgeCount {
+                stored: 2,
+                actual: 1
+            })
+        );
+        g.edge_count = 1;
+        g.edges.remove(&id2);
+        assert!(matches!(
+            g.validate(),
+            Err(InvariantViolation::DanglingEdge(_))
+        ));
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[2]);
+        g.add_edge(ids[2], ids[4]);
+        g.add_edge(ids[4], ids[0]);
+        g.remove_node(ids[1]);
+        g.remove_node(ids[3]);
+
+        let mapping = g.compact();
+        assert_eq!(mapping.len(), 3);
+        assert_eq!(mapping[&ids[0]], Index(1));
+        assert_eq!(mapping[&ids[2]], Index(2));
+        assert_eq!(mapping[&ids[4]], Index(3));
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 3);
+        assert!(g.has_edge(Index(1), Index(2)));
+        assert!(g.has_edge(Index(3), Index(1)));
+
+        assert_eq!(g.add_node(), Index(4));
+    }
+
+    #[test]
+    fn test_index_reuse() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        g.remove_node(id1);
+        assert_ne!(g.add_node(), id1);
+        assert_eq!(g.index_reuse(), IndexReuse::Never);
+
+        let mut g = AdjListGraph::with_index_reuse(IndexReuse::FreeList);
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[3]);
+        g.remove_node(ids[3]);
+        g.remove_nodes_where(|n| n == ids[1]);
+        assert_eq!(g.validate(), Ok(()));
+        assert_eq!(g.add_node(), ids[1]);
+        assert!(!g.has_edge(ids[0], ids[3]));
+        assert_eq!(g.add_node(), ids[3]);
+        assert_eq!(g.add_node(), ids[3].next());
+
+        // adding an edge to a freed index takes it out of the free list
+        g.remove_node(ids[2]);
+        g.add_edge(ids[0], ids[2]);
+        assert_eq!(g.validate(), Ok(()));
+        assert_ne!(g.add_node(), ids[2]);
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let mut g = AdjListGraph::new();
+        let empty = g.memory_usage();
+        assert_eq!(empty.total(), std::mem::size_of::<AdjListGraph>());
+
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let nodes_only = g.memory_usage();
+        assert!(nodes_only.nodes > 0);
+        assert_eq!(nodes_only.adjacency, 0);
+
+        g.add_edge(id1, id2);
+        let with_edges = g.memory_usage();
+        assert!(with_edges.adjacency > 0);
+        assert!(with_edges.total() > nodes_only.total());
+    }
 }
@@ -0,0 +1,335 @@
+use super::adj_list::Index;
+use crate::format::GraphFormatter;
+use crate::graph::{EdgeIterator, Graph, GraphRead, NodeIterator};
+use crate::invariant::InvariantViolation;
+use crate::memory::{MemoryReport, MemoryUsage};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/**
+ * Neighbors up to this count are stored inline with the `smallvec` feature.
+ */
+#[cfg(feature = "smallvec")]
+pub const INLINE_NEIGHBORS: usize = 8;
+
+#[cfg(feature = "smallvec")]
+type Neighbors = smallvec::SmallVec<[Index; INLINE_NEIGHBORS]>;
+#[cfg(not(feature = "smallvec"))]
+type Neighbors = Vec<Index>;
+
+fn heap_bytes(adj: &Neighbors) -> usize {
+    #[cfg(feature = "smallvec")]
+    if !adj.spilled() {
+        return 0;
+    }
+    adj.capacity() * std::mem::size_of::<Index>()
+}
+
+/**
+ * Adjacency list implementation of [`Graph`] keeping the neighbors of each node
+ * in a sorted vector.
+ *
+ * [`GraphRead::has_edge`] is a binary search and iteration walks contiguous memory,
+ * and the graph takes about a third of the memory of an [`AdjListGraph`](super::adj_list::AdjListGraph).
+ * Adding and removing edges is linear in the degree of the node, so this is the
+ * recommended backend for graphs that are built once and then only queried.
+ *
+ * With the `smallvec` feature, up to [`INLINE_NEIGHBORS`] neighbors are stored
+ * inline, saving a heap allocation per node in sparse graphs.
+ */
+pub struct AdjVecGraph {
+    adj: Vec<Option<Neighbors>>,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl AdjVecGraph {
+    /**
+     * Creates a new graph.
+     */
+    pub fn new() -> Self {
+        AdjVecGraph {
+            adj: Vec::new(),
+            node_count: 0,
+            edge_count: 0,
+        }
+    }
+
+    /**
+     * Creates a graph with `n` nodes and no edges, with indices from 1 to `n`.
+     */
+    pub fn with_nodes(n: usize) -> Self {
+        AdjVecGraph {
+            adj: vec![Some(Neighbors::new()); n],
+            node_count: n,
+            edge_count: 0,
+        }
+    }
+
+    /**
+     * Releases the unused capacity of the neighbor vectors.
+     */
+    pub fn shrink_to_fit(&mut self) {
+        self.adj.shrink_to_fit();
+        self.adj
+            .iter_mut()
+            .flatten()
+            .for_each(Neighbors::shrink_to_fit);
+    }
+
+    /**
+     * Checks the internal bookkeeping: the node and edge counts and that neighbors
+     * are sorted, unique and in the graph.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = self.adj.iter().flatten().count();
+        if actual != self.node_count {
+            return Err(InvariantViolation::NodeCount {
+                stored: self.node_count,
+                actual,
+            });
+        }
+
+        let actual = self.adj.iter().flatten().map(|adj| adj.len()).sum();
+        if actual != self.edge_count {
+            return Err(InvariantViolation::EdgeCount {
+                stored: self.edge_count,
+                actual,
+            });
+        }
+
+        for (pos, adj) in self.adj.iter().enumerate() {
+            let adj = match adj {
+                Some(adj) => adj,
+                None => continue,
+            };
+            if adj.windows(2).any(|w| w[0] >= w[1]) {
+                return Err(InvariantViolation::Mapping(format!(
+                    "neighbors of {} are not sorted",
+                    Self::index(pos)
+                )));
+            }
+            if let Some(a) = adj.iter().find(|&&a| self.neighbors(a).is_none()) {
+                return Err(InvariantViolation::DanglingEdge(format!(
+                    "{} -> {}",
+                    Self::index(pos),
+                    a
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn neighbors(&self, n: Index) -> Option<&Neighbors> {
+        self.adj.get(n.0.checked_sub(1)?)?.as_ref()
+    }
+
+    fn neighbors_mut(&mut self, n: Index) -> Option<&mut Neighbors> {
+        self.adj.get_mut(n.0.checked_sub(1)?)?.as_mut()
+    }
+
+    fn index(pos: usize) -> Index {
+        Index(pos + 1)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl AdjVecGraph {
+    /**
+     * Parallel version of [`GraphRead::iter_nodes`].
+     */
+    pub fn par_iter_nodes(&self) -> impl ParallelIterator<Item = Index> + '_ {
+        let it = self.adj.par_iter().enumerate();
+        it.filter_map(|(pos, adj)| adj.as_ref().map(|_| Self::index(pos)))
+    }
+
+    /**
+     * Parallel version of [`GraphRead::iter_edges`].
+     */
+    pub fn par_iter_edges(&self) -> impl ParallelIterator<Item = (Index, Index)> + '_ {
+        self.adj
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(pos, adj)| adj.iter().flatten().map(move |&t| (Self::index(pos), t)))
+    }
+}
+
+impl Default for AdjVecGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphRead for AdjVecGraph {
+    type Index = Index;
+
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.neighbors(f)
+            .is_some_and(|adj| adj.binary_search(&t).is_ok())
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        let it = self.adj.iter().enumerate();
+        Box::new(it.filter_map(|(pos, adj)| adj.as_ref().map(|_| Self::index(pos))))
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.neighbors(n).map(|adj| {
+            let it: Box<NodeIterator<'_, Index>> = Box::new(adj.iter().copied());
+            it
+        })
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        let it = self
+            .adj
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, adj)| adj.iter().flatten().map(move |&t| (Self::index(pos), t)));
+        Box::new(it)
+    }
+}
+
+impl Graph for AdjVecGraph {
+    fn add_node(&mut self) -> Index {
+        self.adj.push(Some(Neighbors::new()));
+        self.node_count += 1;
+        Self::index(self.adj.len() - 1)
+    }
+
+    fn add_edge(&mut self, f: Index, t: Index) {
+        if self.neighbors(t).is_none() {
+            return;
+        }
+        if let Some(adj) = self.neighbors_mut(f) {
+            if let Err(pos) = adj.binary_search(&t) {
+                adj.insert(pos, t);
+                self.edge_count += 1;
+            }
+        }
+    }
+
+    fn remove_node(&mut self, n: Index) {
+        let removed = match self.neighbors_mut(n).map(std::mem::take) {
+            Some(adj) => adj.len(),
+            None => return,
+        };
+        self.adj[n.0 - 1] = None;
+        self.node_count -= 1;
+        self.edge_count -= removed;
+
+        for adj in self.adj.iter_mut().flatten() {
+            if let Ok(pos) = adj.binary_search(&n) {
+                adj.remove(pos);
+                self.edge_count -= 1;
+            }
+        }
+    }
+
+    fn remove_edge(&mut self, f: Index, t: Index) {
+        if let Some(adj) = self.neighbors_mut(f) {
+            if let Ok(pos) = adj.binary_search(&t) {
+                adj.remove(pos);
+                self.edge_count -= 1;
+            }
+        }
+    }
+}
+
+impl MemoryUsage for AdjVecGraph {
+    fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: self.adj.capacity() * std::mem::size_of::<Option<Neighbors>>(),
+            adjacency: self.adj.iter().flatten().map(heap_bytes).sum(),
+            mappings: 0,
+            other: std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+impl Display for AdjVecGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    #[test]
+    fn test_add_remove() {
+        let mut g = AdjVecGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_edge(id1, id3);
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id2);
+        g.add_edge(id3, id1);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(g.iter_adj(id1).unwrap().collect::<Vec<_>>(), vec![id2, id3]);
+        assert!(g.has_edge(id3, id1) && !g.has_edge(id2, id1));
+
+        g.remove_node(id1);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 0);
+        assert!(g.iter_adj(id1).is_none());
+        g.add_edge(id2, id1);
+        assert_eq!(g.edge_count(), 0);
+
+        assert_eq!(g.add_node(), Index(4));
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_smaller_than_adj_list() {
+        let mut g = AdjVecGraph::with_nodes(100);
+        let mut h = AdjListGraph::new();
+        for _ in 0..100 {
+            h.add_node();
+        }
+        for i in 1..=100 {
+            for j in (i..=100).step_by(7) {
+                g.add_edge(Index(i), Index(j));
+                h.add_edge(Index(i), Index(j));
+            }
+        }
+        g.shrink_to_fit();
+
+        let mut edges: Vec<_> = h.iter_edges().collect();
+        edges.sort();
+        assert_eq!(g.iter_edges().collect::<Vec<_>>(), edges);
+        // inline neighbors trade some memory for fewer allocations
+        let factor = if cfg!(feature = "smallvec") { 1 } else { 2 };
+        assert!(g.memory_usage().total() * factor < h.memory_usage().total());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter() {
+        let mut g = AdjVecGraph::with_nodes(50);
+        for i in 1..50 {
+            g.add_edge(Index(i), Index(i + 1));
+        }
+        g.remove_node(Index(10));
+
+        let mut nodes: Vec<_> = g.par_iter_nodes().collect();
+        nodes.sort();
+        assert_eq!(nodes, g.iter_nodes().collect::<Vec<_>>());
+        let mut edges: Vec<_> = g.par_iter_edges().collect();
+        edges.sort();
+        assert_eq!(edges, g.iter_edges().collect::<Vec<_>>());
+    }
+}
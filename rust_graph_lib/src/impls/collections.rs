@@ -0,0 +1,175 @@
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+    iter,
+};
+
+// maps from each node to its successors are read-only graphs, so that data already
+// stored in them can be analyzed without copying it; successors missing from the keys
+// are nodes without outgoing edges, and finding them takes a scan of all the lists
+macro_rules! impl_adjacency_map {
+    ($map:ty, $($bounds:tt)*) => {
+        impl<N, $($bounds)*> GraphRead for $map
+        where
+            N: Hash + Ord + Copy,
+        {
+            type Index = N;
+
+            fn has_edge(&self, f: N, t: N) -> bool {
+                self.get(&f).is_some_and(|adj| adj.contains(&t))
+            }
+
+            fn node_count(&self) -> usize {
+                self.iter_nodes().count()
+            }
+
+            fn edge_count(&self) -> usize {
+                self.values().map(Vec::len).sum()
+            }
+
+            fn iter_nodes(&self) -> Box<NodeIterator<'_, N>> {
+                let mut seen = HashSet::new();
+                let leaves = self
+                    .values()
+                    .flatten()
+                    .copied()
+                    .filter(move |t| !self.contains_key(t) && seen.insert(*t));
+                Box::new(self.keys().copied().chain(leaves))
+            }
+
+            fn iter_adj(&self, n: N) -> Option<Box<NodeIterator<'_, N>>> {
+                match self.get(&n) {
+                    Some(adj) => Some(Box::new(adj.iter().copied())),
+                    None if self.values().any(|adj| adj.contains(&n)) => {
+                        Some(Box::new(iter::empty()))
+                    }
+                    None => None,
+                }
+            }
+
+            fn iter_edges(&self) -> Box<EdgeIterator<'_, N>> {
+                Box::new(
+                    self.iter()
+                        .flat_map(|(&f, adj)| adj.iter().map(move |&t| (f, t))),
+                )
+            }
+        }
+    };
+}
+
+impl_adjacency_map!(HashMap<N, Vec<N>, S>, S: BuildHasher);
+impl_adjacency_map!(BTreeMap<N, Vec<N>>,);
+
+/**
+ * An edge list is a graph whose nodes are the endpoints of its edges; adjacency
+ * queries scan the whole list, so they take `O(m)` time.
+ */
+impl<N: Hash + Ord + Copy> GraphRead for Vec<(N, N)> {
+    type Index = N;
+
+    fn has_edge(&self, f: N, t: N) -> bool {
+        self.contains(&(f, t))
+    }
+
+    fn node_count(&self) -> usize {
+        self.iter_nodes().count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.len()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, N>> {
+        let mut seen = HashSet::new();
+        let endpoints = self.iter().flat_map(|&(f, t)| [f, t]);
+        Box::new(endpoints.filter(move |n| seen.insert(*n)))
+    }
+
+    fn iter_adj(&self, n: N) -> Option<Box<NodeIterator<'_, N>>> {
+        if !self.iter().any(|&(f, t)| f == n || t == n) {
+            return None;
+        }
+        let adj = self.iter().filter(move |e| e.0 == n).map(|e| e.1);
+        Some(Box::new(adj))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, N>> {
+        Box::new(self.iter().copied())
+    }
+}
+
+/**
+ * A vector of adjacency lists is a graph on the positions `0..len`, where the list at
+ * each position holds its successors.
+ */
+impl GraphRead for Vec<Vec<usize>> {
+    type Index = usize;
+
+    fn has_edge(&self, f: usize, t: usize) -> bool {
+        self.get(f).is_some_and(|adj| adj.contains(&t))
+    }
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.iter().map(Vec::len).sum()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, usize>> {
+        Box::new(0..self.len())
+    }
+
+    fn iter_adj(&self, n: usize) -> Option<Box<NodeIterator<'_, usize>>> {
+        let adj = self.get(n)?;
+        Some(Box::new(adj.iter().copied()))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, usize>> {
+        Box::new(
+            self.iter()
+                .enumerate()
+                .flat_map(|(f, adj)| adj.iter().map(move |&t| (f, t))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{reachable_from, Algorithm, Dijkstra};
+
+    #[test]
+    fn test_adjacency_maps() {
+        let map = HashMap::from([("a", vec!["b", "c"]), ("b", vec!["d"])]);
+        assert_eq!(map.node_count(), 4);
+        assert_eq!(map.edge_count(), 3);
+        assert!(map.has_edge("a", "c") && !map.has_edge("c", "a"));
+        assert_eq!(map.iter_adj("d").map(Iterator::count), Some(0));
+        assert!(map.iter_adj("e").is_none());
+        assert_eq!(reachable_from(&map, "a").len(), 4);
+        assert_eq!(Dijkstra::new(&map).run("a", "d"), Some(vec!["a", "b", "d"]));
+
+        let tree: BTreeMap<_, _> = map.into_iter().collect();
+        let mut nodes: Vec<_> = tree.iter_nodes().collect();
+        nodes.sort();
+        assert_eq!(nodes, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_edge_lists() {
+        let edges: Vec<(usize, usize)> = vec![(1, 2), (2, 3), (1, 3), (7, 1)];
+        assert_eq!(edges.node_count(), 4);
+        assert_eq!(edges.iter_adj(1).unwrap().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(edges.iter_adj(3).map(Iterator::count), Some(0));
+        assert!(edges.iter_adj(4).is_none());
+        assert_eq!(reachable_from(&edges, 7).len(), 4);
+
+        let lists = vec![vec![1], vec![2, 0], vec![]];
+        assert_eq!((lists.node_count(), lists.edge_count()), (3, 3));
+        assert!(lists.has_edge(1, 0) && !lists.has_edge(3, 0));
+        assert_eq!(Dijkstra::new(&lists).run(0, 2), Some(vec![0, 1, 2]));
+    }
+}
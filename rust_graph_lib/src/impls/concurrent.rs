@@ -0,0 +1,271 @@
+use super::adj_list::Index;
+use crate::graph::{EdgeIterator, Graph, GraphRead, NodeIterator};
+use crate::invariant::InvariantViolation;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+};
+
+const SHARDS: usize = 16;
+
+type Shard = HashMap<Index, HashSet<Index>>;
+
+/**
+ * Adjacency list implementation of [`Graph`] that can be shared between threads.
+ *
+ * Nodes are spread over independently locked shards, so readers only block writers
+ * touching the same shard and mutations take `&self`. Edges between nodes not in the
+ * graph are ignored. The [`GraphRead`] iterators work on a copy of the data taken
+ * when they are created, so they never hold a lock.
+ */
+pub struct ConcurrentAdjListGraph {
+    shards: Vec<RwLock<Shard>>,
+    next_id: AtomicUsize,
+    edge_count: AtomicUsize,
+}
+
+impl ConcurrentAdjListGraph {
+    /**
+     * Creates a new graph.
+     */
+    pub fn new() -> Self {
+        ConcurrentAdjListGraph {
+            shards: (0..SHARDS).map(|_| RwLock::default()).collect(),
+            next_id: AtomicUsize::new(1),
+            edge_count: AtomicUsize::new(0),
+        }
+    }
+
+    fn shard(n: Index) -> usize {
+        n.0 % SHARDS
+    }
+
+    fn read(&self, shard: usize) -> RwLockReadGuard<'_, Shard> {
+        self.shards[shard].read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self, shard: usize) -> RwLockWriteGuard<'_, Shard> {
+        self.shards[shard]
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /**
+     * Adds a node, returning its index.
+     */
+    pub fn insert_node(&self) -> Index {
+        let id = Index(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.write(Self::shard(id)).insert(id, HashSet::new());
+        id
+    }
+
+    /**
+     * Adds an edge; does nothing if either node is not in the graph.
+     */
+    pub fn insert_edge(&self, f: Index, t: Index) {
+        let (sf, st) = (Self::shard(f), Self::shard(t));
+        // the count is updated while the locks are held, so that validate never sees
+        // it out of sync with the shards
+        let insert = |from: &mut Shard, has_to: bool| {
+            if has_to && from.get_mut(&f).is_some_and(|adj| adj.insert(t)) {
+                self.edge_count.fetch_add(1, Ordering::Relaxed);
+            }
+        };
+        // locks are always taken in shard order, so concurrent calls cannot deadlock
+        if sf == st {
+            let mut shard = self.write(sf);
+            let has_to = shard.contains_key(&t);
+            insert(&mut shard, has_to);
+        } else if sf < st {
+            let mut from = self.write(sf);
+            let to = self.read(st);
+            insert(&mut from, to.contains_key(&t));
+        } else {
+            let to = self.read(st);
+            let mut from = self.write(sf);
+            insert(&mut from, to.contains_key(&t));
+        }
+    }
+
+    /**
+     * Removes a node and its edges. This locks the whole graph.
+     */
+    pub fn delete_node(&self, n: Index) {
+        let mut shards: Vec<_> = (0..SHARDS).map(|s| self.write(s)).collect();
+        let removed = match shards[Self::shard(n)].remove(&n) {
+            Some(adj) => adj.len(),
+            None => return,
+        };
+        let incoming: usize = shards
+            .iter_mut()
+            .flat_map(|shard| shard.values_mut())
+            .map(|adj| adj.remove(&n))
+            .filter(|&removed| removed)
+            .count();
+        self.edge_count
+            .fetch_sub(removed + incoming, Ordering::Relaxed);
+    }
+
+    /**
+     * Removes an edge.
+     */
+    pub fn delete_edge(&self, f: Index, t: Index) {
+        let mut shard = self.write(Self::shard(f));
+        if shard.get_mut(&f).is_some_and(|adj| adj.remove(&t)) {
+            self.edge_count.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /**
+     * Checks the internal bookkeeping: the edge count, that edges only reference nodes
+     * in the graph and that each node is in its shard. This blocks all writers.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let shards: Vec<_> = (0..SHARDS).map(|s| self.read(s)).collect();
+        let has_node = |n: &Index| shards[Self::shard(*n)].contains_key(n);
+
+        let mut actual = 0;
+        for (s, shard) in shards.iter().enumerate() {
+            for (n, adj) in shard.iter() {
+                if Self::shard(*n) != s {
+                    return Err(InvariantViolation::Mapping(format!(
+                        "node {} is in shard {}",
+                        n, s
+                    )));
+                }
+                if let Some(a) = adj.iter().find(|a| !has_node(a)) {
+                    return Err(InvariantViolation::DanglingEdge(format!("{} -> {}", n, a)));
+                }
+                actual += adj.len();
+            }
+        }
+
+        let stored = self.edge_count.load(Ordering::Relaxed);
+        if actual != stored {
+            return Err(InvariantViolation::EdgeCount { stored, actual });
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConcurrentAdjListGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphRead for ConcurrentAdjListGraph {
+    type Index = Index;
+
+    fn node_count(&self) -> usize {
+        (0..SHARDS).map(|s| self.read(s).len()).sum()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count.load(Ordering::Relaxed)
+    }
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.read(Self::shard(f))
+            .get(&f)
+            .is_some_and(|adj| adj.contains(&t))
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        let nodes: Vec<_> = (0..SHARDS)
+            .flat_map(|s| self.read(s).keys().copied().collect::<Vec<_>>())
+            .collect();
+        Box::new(nodes.into_iter())
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        let adj: Vec<_> = self.read(Self::shard(n)).get(&n)?.iter().copied().collect();
+        Some(Box::new(adj.into_iter()))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        let edges: Vec<_> = (0..SHARDS)
+            .flat_map(|s| {
+                let shard = self.read(s);
+                shard
+                    .iter()
+                    .flat_map(|(&f, adj)| adj.iter().map(move |&t| (f, t)))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Box::new(edges.into_iter())
+    }
+}
+
+impl Graph for ConcurrentAdjListGraph {
+    fn add_node(&mut self) -> Index {
+        self.insert_node()
+    }
+
+    fn add_edge(&mut self, f: Index, t: Index) {
+        self.insert_edge(f, t)
+    }
+
+    fn remove_node(&mut self, n: Index) {
+        self.delete_node(n)
+    }
+
+    fn remove_edge(&mut self, f: Index, t: Index) {
+        self.delete_edge(f, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::reachable_from;
+
+    #[test]
+    fn test_add_remove() {
+        let mut g = ConcurrentAdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id2);
+        g.add_edge(id2, Index(99));
+        assert_eq!(g.edge_count(), 1);
+
+        g.add_edge(id2, id1);
+        g.remove_node(id1);
+        assert_eq!(g.node_count(), 1);
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_concurrent_reads_and_writes() {
+        let g = ConcurrentAdjListGraph::new();
+        let nodes: Vec<_> = (0..200).map(|_| g.insert_node()).collect();
+
+        std::thread::scope(|s| {
+            for w in 0..4 {
+                let (g, nodes) = (&g, &nodes);
+                s.spawn(move || {
+                    for i in (w..nodes.len() - 1).step_by(4) {
+                        g.insert_edge(nodes[i], nodes[i + 1]);
+                    }
+                });
+            }
+            s.spawn(|| {
+                for _ in 0..20 {
+                    let reached = reachable_from(&g, nodes[0]);
+                    assert!(reached.len() <= nodes.len());
+                    // the count is kept in sync even while edges are being added
+                    assert_eq!(g.validate(), Ok(()));
+                }
+            });
+        });
+
+        assert_eq!(g.edge_count(), 199);
+        assert_eq!(reachable_from(&g, nodes[0]).len(), 200);
+        assert_eq!(g.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+
+/**
+ * Read-only graph borrowing a slice of `(source, target)` edges, for one-off analyses
+ * of data loaded by other systems.
+ *
+ * The nodes are the endpoints of the edges, and the edges are never copied: creating
+ * the view sorts the positions of the edges by source in `O(m log m)`, using four bytes
+ * per edge, so that adjacency queries are binary searches. Repeated edges are kept.
+ *
+ * ```
+ * use rust_graph_lib::{algorithms::reachable_from, graph::GraphRead, impls::edge_slice::EdgeSliceGraph};
+ *
+ * let edges = [(10, 20), (20, 30), (40, 10)];
+ * let g = EdgeSliceGraph::new(&edges);
+ * assert_eq!(g.node_count(), 4);
+ * assert_eq!(reachable_from(&g, 10).len(), 3);
+ * ```
+ */
+pub struct EdgeSliceGraph<'a> {
+    edges: &'a [(u32, u32)],
+    by_source: Vec<u32>,
+    nodes: Vec<u32>,
+}
+
+impl<'a> EdgeSliceGraph<'a> {
+    /**
+     * Creates the view, indexing the edges by source.
+     *
+     * # Panics
+     *
+     * Panics if there are more than `u32::MAX` edges.
+     */
+    pub fn new(edges: &'a [(u32, u32)]) -> Self {
+        let count = u32::try_from(edges.len()).expect("too many edges");
+        let mut by_source: Vec<u32> = (0..count).collect();
+        by_source.sort_unstable_by_key(|&i| edges[i as usize]);
+
+        let mut nodes: Vec<_> = edges.iter().flat_map(|&(f, t)| [f, t]).collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        EdgeSliceGraph {
+            edges,
+            by_source,
+            nodes,
+        }
+    }
+
+    /**
+     * Returns the edges the view was created from.
+     */
+    pub fn edges(&self) -> &'a [(u32, u32)] {
+        self.edges
+    }
+
+    fn edge(&self, i: u32) -> (u32, u32) {
+        self.edges[i as usize]
+    }
+
+    /**
+     * Returns the sorted positions of the edges leaving `n`.
+     */
+    fn leaving(&self, n: u32) -> &[u32] {
+        let start = self.by_source.partition_point(|&i| self.edge(i).0 < n);
+        let end = self.by_source.partition_point(|&i| self.edge(i).0 <= n);
+        &self.by_source[start..end]
+    }
+}
+
+impl GraphRead for EdgeSliceGraph<'_> {
+    type Index = u32;
+
+    fn has_edge(&self, f: u32, t: u32) -> bool {
+        let leaving = self.leaving(f);
+        leaving
+            .binary_search_by_key(&t, |&i| self.edge(i).1)
+            .is_ok()
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, u32>> {
+        Box::new(self.nodes.iter().copied())
+    }
+
+    fn iter_adj(&self, n: u32) -> Option<Box<NodeIterator<'_, u32>>> {
+        self.nodes.binary_search(&n).ok()?;
+        let leaving = self.leaving(n).iter();
+        Some(Box::new(leaving.map(|&i| self.edge(i).1)))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, u32>> {
+        Box::new(self.edges.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Algorithm, Dijkstra};
+
+    #[test]
+    fn test_edge_slice() {
+        let edges = vec![(5, 1), (1, 9), (5, 9), (1, 9), (9, 9)];
+        let g = EdgeSliceGraph::new(&edges);
+
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 5);
+        assert!(g.has_edge(5, 9) && g.has_edge(9, 9));
+        assert!(!g.has_edge(9, 5) && !g.has_edge(2, 1));
+        assert_eq!(g.iter_adj(5).unwrap().collect::<Vec<_>>(), vec![1, 9]);
+        assert_eq!(g.neighbors_count(1), Some(2));
+        assert!(g.iter_adj(4).is_none());
+        assert_eq!(g.iter_nodes().collect::<Vec<_>>(), vec![1, 5, 9]);
+        assert_eq!(Dijkstra::new(&g).run(5, 9), Some(vec![5, 9]));
+        assert_eq!(g.edges().len(), 5);
+
+        let empty = EdgeSliceGraph::new(&[]);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+use super::adj_list::{AdjListGraph, Index, IndexReuse};
+use crate::format::GraphFormatter;
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+use crate::memory::{MemoryReport, MemoryUsage};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+
+/**
+ * Immutable graph in compressed sparse row form, created by [`AdjListGraph::freeze`].
+ *
+ * The neighbors of all the nodes are stored sorted in a single array, with the
+ * weights of the edges in a parallel one, so iterating over them walks contiguous
+ * memory and [`GraphRead::has_edge`] and [`edge_weight`](FrozenGraph::edge_weight)
+ * are binary searches. Nodes keep the indices they had in the mutable graph, and
+ * [`thaw`](FrozenGraph::thaw) turns the graph back into an [`AdjListGraph`].
+ */
+pub struct FrozenGraph<W = ()> {
+    nodes: Vec<Index>,
+    offsets: Vec<usize>,
+    targets: Vec<Index>,
+    weights: Vec<W>,
+    next_id: Index,
+}
+
+impl AdjListGraph {
+    /**
+     * Converts the graph into its immutable, compact form, without weights.
+     */
+    pub fn freeze(self) -> FrozenGraph {
+        self.freeze_with_weights(|_, _| ())
+    }
+
+    /**
+     * Converts the graph into its immutable, compact form, where the edge `(f, t)`
+     * weighs `weight(f, t)`.
+     */
+    pub fn freeze_with_weights<W, F>(self, mut weight: F) -> FrozenGraph<W>
+    where
+        F: FnMut(Index, Index) -> W,
+    {
+        let mut nodes: Vec<_> = self.edges.keys().copied().collect();
+        nodes.sort();
+
+        let mut offsets = Vec::with_capacity(nodes.len() + 1);
+        let mut targets = Vec::with_capacity(self.edge_count);
+        offsets.push(0);
+        for n in &nodes {
+            let start = targets.len();
+            targets.extend(&self.edges[n]);
+            targets[start..].sort();
+            offsets.push(targets.len());
+        }
+
+        let weights = nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &f)| {
+                targets[offsets[i]..offsets[i + 1]]
+                    .iter()
+                    .map(move |&t| (f, t))
+            })
+            .map(|(f, t)| weight(f, t))
+            .collect();
+        FrozenGraph {
+            nodes,
+            offsets,
+            targets,
+            weights,
+            next_id: self.next_id,
+        }
+    }
+}
+
+impl<W> FrozenGraph<W> {
+    /**
+     * Converts the graph back into an [`AdjListGraph`] with the same indices,
+     * dropping the weights. The result never reuses indices, see [`IndexReuse`].
+     */
+    pub fn thaw(self) -> AdjListGraph {
+        let edges: HashMap<_, HashSet<_>> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| {
+                (
+                    n,
+                    self.targets[self.offsets[i]..self.offsets[i + 1]]
+                        .iter()
+                        .copied()
+                        .collect(),
+                )
+            })
+            .collect();
+        AdjListGraph {
+            edges,
+            edge_count: self.targets.len(),
+            next_id: self.next_id,
+            reuse: IndexReuse::Never,
+            free: BTreeSet::new(),
+        }
+    }
+
+    /**
+     * Returns the weight of an edge, if the edge is in the graph.
+     */
+    pub fn edge_weight(&self, f: Index, t: Index) -> Option<&W> {
+        let row = self.row(f)?;
+        let pos = self.targets[row.clone()].binary_search(&t).ok()?;
+        Some(&self.weights[row.start + pos])
+    }
+
+    /**
+     * Returns the neighbors of a node with the weights of the edges reaching them,
+     * if the node is in the graph.
+     */
+    pub fn iter_weighted_adj(&self, n: Index) -> Option<impl Iterator<Item = (Index, &W)>> {
+        let row = self.row(n)?;
+        Some(
+            self.targets[row.clone()]
+                .iter()
+                .copied()
+                .zip(&self.weights[row]),
+        )
+    }
+
+    /**
+     * Returns the positions of the neighbors of `n` in the targets array.
+     */
+    fn row(&self, n: Index) -> Option<Range<usize>> {
+        let i = self.nodes.binary_search(&n).ok()?;
+        Some(self.offsets[i]..self.offsets[i + 1])
+    }
+}
+
+impl<W> GraphRead for FrozenGraph<W> {
+    type Index = Index;
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.row(f)
+            .is_some_and(|row| self.targets[row].binary_search(&t).is_ok())
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        Box::new(self.nodes.iter().copied())
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        let row = self.row(n)?;
+        Some(Box::new(self.targets[row].iter().copied()))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        let it = self.nodes.iter().enumerate().flat_map(|(i, &f)| {
+            let row = &self.targets[self.offsets[i]..self.offsets[i + 1]];
+            row.iter().map(move |&t| (f, t))
+        });
+        Box::new(it)
+    }
+}
+
+impl<W> MemoryUsage for FrozenGraph<W> {
+    fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: self.nodes.capacity() * std::mem::size_of::<Index>(),
+            adjacency: self.offsets.capacity() * std::mem::size_of::<usize>()
+                + self.targets.capacity() * std::mem::size_of::<Index>()
+                + self.weights.capacity() * std::mem::size_of::<W>(),
+            mappings: 0,
+            other: std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+impl<W> Display for FrozenGraph<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{Algorithm, Dijkstra};
+    use crate::graph::Graph;
+
+    #[test]
+    fn test_freeze_and_thaw() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[2]);
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[3]);
+        g.add_edge(ids[2], ids[3]);
+        g.add_edge(ids[3], ids[4]);
+        g.remove_node(ids[4]);
+
+        let frozen =
+            g.freeze_with_weights(|f, t| if f == ids[0] && t == ids[1] { 10u32 } else { 1 });
+        assert_eq!(frozen.node_count(), 4);
+        assert_eq!(frozen.edge_count(), 4);
+        assert_eq!(
+            frozen.iter_adj(ids[0]).unwrap().collect::<Vec<_>>(),
+            vec![ids[1], ids[2]]
+        );
+        assert!(frozen.has_edge(ids[2], ids[3]) && !frozen.has_edge(ids[3], ids[2]));
+        assert!(frozen.iter_adj(ids[4]).is_none());
+        assert_eq!(frozen.edge_weight(ids[0], ids[1]), Some(&10));
+        assert_eq!(frozen.edge_weight(ids[1], ids[0]), None);
+        assert_eq!(
+            frozen
+                .iter_weighted_adj(ids[0])
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![(ids[1], &10), (ids[2], &1)]
+        );
+
+        let weight = |f, t| *frozen.edge_weight(f, t).unwrap();
+        assert_eq!(
+            Dijkstra::with_weights(&frozen, weight).run(ids[0], ids[3]),
+            Some(vec![ids[0], ids[2], ids[3]])
+        );
+
+        let mut g = frozen.thaw();
+        assert_eq!(g.edge_count(), 4);
+        assert!(g.has_edge(ids[0], ids[1]));
+        assert_eq!(g.validate(), Ok(()));
+        assert_eq!(g.add_node(), ids[4].next());
+    }
+
+    #[test]
+    fn test_empty() {
+        let frozen = AdjListGraph::new().freeze();
+        assert!(frozen.is_empty());
+        assert_eq!(frozen.iter_edges().count(), 0);
+        assert!(frozen.thaw().is_empty());
+    }
+}
@@ -0,0 +1,482 @@
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+use crate::invariant::InvariantViolation;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+/**
+ * Cost of a straight move onto a cell of cost 1, see [`GridGraph::move_cost`].
+ */
+pub const STRAIGHT_COST: u32 = 10;
+/**
+ * Cost of a diagonal move onto a cell of cost 1, approximating `10 * sqrt(2)`.
+ */
+pub const DIAGONAL_COST: u32 = 14;
+
+/**
+ * Moves allowed between the cells of a [`GridGraph`].
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Connectivity {
+    /**
+     * Horizontal and vertical moves only.
+     */
+    Four,
+    /**
+     * Diagonal moves too, unless they cut the corner of a wall.
+     */
+    Eight,
+}
+
+/**
+ * Read-only graph over a 2D occupancy grid, whose nodes are the passable cells
+ * as `(x, y)` pairs and whose edges join neighboring cells.
+ *
+ * Edges are computed on the fly, so arbitrarily large grids take one `Option<u32>` per cell.
+ * Each passable cell has a cost of entering it, at least 1; [`GridGraph::move_cost`] and
+ * [`GridGraph::heuristic`] can be passed to [`Dijkstra`](crate::algorithms::Dijkstra)
+ * and [`AStar`](crate::algorithms::AStar).
+ */
+pub struct GridGraph {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<u32>>,
+    connectivity: Connectivity,
+    passable: usize,
+}
+
+impl GridGraph {
+    /**
+     * Creates a grid with all cells passable with cost 1.
+     */
+    pub fn new(width: usize, height: usize, connectivity: Connectivity) -> Self {
+        GridGraph {
+            width,
+            height,
+            cells: vec![Some(1); width * height],
+            connectivity,
+            passable: width * height,
+        }
+    }
+
+    /**
+     * Returns the width of the grid.
+     */
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /**
+     * Returns the height of the grid.
+     */
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /**
+     * Returns the moves allowed between cells.
+     */
+    pub fn connectivity(&self) -> Connectivity {
+        self.connectivity
+    }
+
+    fn cell(&self, (x, y): (usize, usize)) -> Option<u32> {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x]
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Returns the cost of entering the cell, or `None` for walls and cells outside the grid.
+     */
+    pub fn cost(&self, pos: (usize, usize)) -> Option<u32> {
+        self.cell(pos)
+    }
+
+    /**
+     * Checks if the cell is in the grid and not a wall.
+     */
+    pub fn is_passable(&self, pos: (usize, usize)) -> bool {
+        self.cell(pos).is_some()
+    }
+
+    /**
+     * Sets the cost of entering the cell, or makes it a wall if `cost` is `None`.
+     *
+     * Panics if the cell is outside the grid or the cost is 0.
+     */
+    pub fn set_cost(&mut self, (x, y): (usize, usize), cost: Option<u32>) {
+        assert!(x < self.width && y < self.height, "cell outside the grid");
+        assert!(cost != Some(0), "cell costs must be at least 1");
+
+        let cell = &mut self.cells[y * self.width + x];
+        match (cell.is_some(), cost.is_some()) {
+            (true, false) => self.passable -= 1,
+            (false, true) => self.passable += 1,
+            _ => {}
+        }
+        *cell = cost;
+    }
+
+    /**
+     * Makes the cell a wall.
+     */
+    pub fn set_wall(&mut self, pos: (usize, usize)) {
+        self.set_cost(pos, None);
+    }
+
+    /**
+     * Checks the internal bookkeeping: the number of passable cells and that they
+     * all have a cost of at least 1.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = self.cells.iter().filter(|c| c.is_some()).count();
+        if actual != self.passable {
+            return Err(InvariantViolation::NodeCount {
+                stored: self.passable,
+                actual,
+            });
+        }
+        match self.cells.iter().position(|&c| c == Some(0)) {
+            Some(i) => Err(InvariantViolation::Mapping(format!(
+                "cell ({}, {}) has cost 0",
+                i % self.width,
+                i / self.width
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /**
+     * Returns the cost of moving between two neighboring cells: the cost of entering `t`
+     * scaled by [`STRAIGHT_COST`] or [`DIAGONAL_COST`], saturating at [`u32::MAX`].
+     */
+    pub fn move_cost(&self, f: (usize, usize), t: (usize, usize)) -> u32 {
+        let cost = self.cell(t).unwrap_or(1);
+        if f.0 != t.0 && f.1 != t.1 {
+            cost.saturating_mul(DIAGONAL_COST)
+        } else {
+            cost.saturating_mul(STRAIGHT_COST)
+        }
+    }
+
+    /**
+     * Returns a lower bound of the cost of moving from `n` to `target`, in the units
+     * of [`GridGraph::move_cost`]: the Manhattan distance for [`Connectivity::Four`]
+     * and the octile distance for [`Connectivity::Eight`].
+     */
+    pub fn heuristic(&self, n: (usize, usize), target: (usize, usize)) -> u32 {
+        let dx = n.0.abs_diff(target.0) as u32;
+        let dy = n.1.abs_diff(target.1) as u32;
+        match self.connectivity {
+            Connectivity::Four => (dx + dy) * STRAIGHT_COST,
+            Connectivity::Eight => {
+                let (min, max) = (dx.min(dy), dx.max(dy));
+                min * DIAGONAL_COST + (max - min) * STRAIGHT_COST
+            }
+        }
+    }
+
+    fn neighbors(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        const STEPS: [(isize, isize); 8] = [
+            (1, 0),
+            (0, 1),
+            (-1, 0),
+            (0, -1),
+            (1, 1),
+            (-1, 1),
+            (-1, -1),
+            (1, -1),
+        ];
+        let steps = match self.connectivity {
+            Connectivity::Four => &STEPS[..4],
+            Connectivity::Eight => &STEPS[..],
+        };
+
+        steps.iter().filter_map(move |&(dx, dy)| {
+            let t = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            if !self.is_passable(t) {
+                return None;
+            }
+            // diagonal moves must not squeeze between two walls or cut a wall corner
+            if dx != 0 && dy != 0 && !(self.is_passable((t.0, y)) && self.is_passable((x, t.1))) {
+                return None;
+            }
+            Some(t)
+        })
+    }
+}
+
+/**
+ * Straight-line distance between the centers of two cells, in thousandths of a cell.
+ */
+fn euclidean(a: (usize, usize), b: (usize, usize)) -> u64 {
+    let dx = a.0.abs_diff(b.0) as f64;
+    let dy = a.1.abs_diff(b.1) as f64;
+    (dx.hypot(dy) * 1000.0).round() as u64
+}
+
+impl GridGraph {
+    /**
+     * Checks if the segment between the centers of two cells only crosses passable cells.
+     *
+     * Where the segment passes exactly through the corner of four cells, both cells
+     * beside it must be passable, as for diagonal moves. Cell costs are ignored.
+     */
+    pub fn has_line_of_sight(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        if !self.is_passable(a) || !self.is_passable(b) {
+            return false;
+        }
+
+        let (dx, dy) = (a.0.abs_diff(b.0) as i64, a.1.abs_diff(b.1) as i64);
+        let sx = if b.0 > a.0 { 1 } else { -1 };
+        let sy = if b.1 > a.1 { 1 } else { -1 };
+        let passable =
+            |x: i64, y: i64| x >= 0 && y >= 0 && self.is_passable((x as usize, y as usize));
+
+        let (mut x, mut y) = (a.0 as i64, a.1 as i64);
+        let (mut ix, mut iy) = (0, 0);
+        while ix < dx || iy < dy {
+            // compares where the segment crosses the next vertical and horizontal cell borders
+            let decision = (1 + 2 * ix) * dy - (1 + 2 * iy) * dx;
+            if decision == 0 {
+                if !passable(x + sx, y) || !passable(x, y + sy) {
+                    return false;
+                }
+                x += sx;
+                y += sy;
+                ix += 1;
+                iy += 1;
+            } else if decision < 0 {
+                x += sx;
+                ix += 1;
+            } else {
+                y += sy;
+                iy += 1;
+            }
+            if !passable(x, y) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /**
+     * Shortens a path of cells by string pulling: each kept cell is followed by the
+     * farthest later cell in [line of sight](GridGraph::has_line_of_sight), so that the
+     * agent walks straight segments instead of zig-zagging between neighbors.
+     *
+     * The result starts and ends with the same cells as `path`.
+     */
+    pub fn smooth_path(&self, path: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let Some((&first, rest)) = path.split_first() else {
+            return Vec::new();
+        };
+
+        let mut smoothed = vec![first];
+        let mut anchor = first;
+        for (i, &cell) in rest.iter().enumerate() {
+            // `path[i]` is the cell before `cell`
+            if !self.has_line_of_sight(anchor, cell) && path[i] != anchor {
+                anchor = path[i];
+                smoothed.push(anchor);
+            }
+        }
+        if rest.last().is_some_and(|&last| last != anchor) {
+            smoothed.extend(rest.last());
+        }
+        smoothed
+    }
+
+    /**
+     * Searches an any-angle path with Theta*, returning the corners of the path.
+     *
+     * Like A*, but a cell can be reached straight from the parent of the cell being
+     * expanded when they are in line of sight, so consecutive cells of the result are
+     * not necessarily neighbors. Segments cost their Euclidean length and cell costs other
+     * than walls are ignored. The paths are usually close to the true shortest ones,
+     * although this is not guaranteed.
+     */
+    pub fn any_angle_path(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Option<Vec<(usize, usize)>> {
+        if !self.is_passable(from) || !self.is_passable(to) {
+            return None;
+        }
+
+        let mut costs = HashMap::new();
+        let mut parents = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        costs.insert(from, 0);
+        parents.insert(from, from);
+        heap.push(Reverse((euclidean(from, to), 0, from)));
+
+        while let Some(Reverse((_, cost, node))) = heap.pop() {
+            if costs[&node] < cost {
+                continue;
+            }
+            if node == to {
+                let mut path = vec![to];
+                let mut cur = to;
+                while cur != from {
+                    cur = parents[&cur];
+                    path.push(cur);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let parent = parents[&node];
+            for adj in self.neighbors(node) {
+                let (via, alt) = if self.has_line_of_sight(parent, adj) {
+                    (parent, costs[&parent] + euclidean(parent, adj))
+                } else {
+                    (node, cost + euclidean(node, adj))
+                };
+                if costs.get(&adj).is_none_or(|&c| alt < c) {
+                    costs.insert(adj, alt);
+                    parents.insert(adj, via);
+                    heap.push(Reverse((alt + euclidean(adj, to), alt, adj)));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl GraphRead for GridGraph {
+    type Index = (usize, usize);
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.is_passable(f) && self.neighbors(f).any(|n| n == t)
+    }
+
+    fn node_count(&self) -> usize {
+        self.passable
+    }
+
+    /**
+     * Counts the edges, visiting the whole grid.
+     */
+    fn edge_count(&self) -> usize {
+        self.iter_edges().count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        Box::new(
+            (0..self.height)
+                .flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+                .filter(move |&p| self.is_passable(p)),
+        )
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        if !self.is_passable(n) {
+            return None;
+        }
+        Some(Box::new(self.neighbors(n)))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        Box::new(
+            self.iter_nodes()
+                .flat_map(move |f| self.neighbors(f).map(move |t| (f, t))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{AStar, Algorithm, Dijkstra};
+
+    #[test]
+    fn test_connectivity() {
+        let mut g = GridGraph::new(3, 3, Connectivity::Eight);
+        assert_eq!(g.node_count(), 9);
+        assert_eq!(g.iter_adj((1, 1)).unwrap().count(), 8);
+        assert_eq!(g.edge_count(), 40);
+
+        g.set_wall((1, 0));
+        assert_eq!(g.node_count(), 8);
+        assert!(g.iter_adj((1, 0)).is_none());
+        // cutting the corner of the wall is not allowed
+        assert!(!g.has_edge((0, 0), (1, 1)));
+        assert!(g.has_edge((0, 1), (1, 2)));
+
+        let g4 = GridGraph::new(3, 3, Connectivity::Four);
+        assert_eq!(g4.iter_adj((0, 0)).unwrap().count(), 2);
+        assert_eq!(g4.edge_count(), 24);
+    }
+
+    #[test]
+    fn test_move_cost_saturates() {
+        let mut g = GridGraph::new(2, 2, Connectivity::Eight);
+        g.set_cost((1, 1), Some(u32::MAX / 2));
+        assert_eq!(g.move_cost((0, 0), (1, 1)), u32::MAX);
+        assert_eq!(g.move_cost((1, 0), (1, 1)), u32::MAX);
+        assert_eq!(g.move_cost((1, 1), (0, 1)), STRAIGHT_COST);
+    }
+
+    #[test]
+    fn test_pathfinding_around_walls() {
+        let mut g = GridGraph::new(5, 5, Connectivity::Four);
+        for y in 0..4 {
+            g.set_wall((2, y));
+        }
+        g.set_cost((1, 4), Some(5));
+
+        let astar = AStar::with_weights(&g, |f, t| g.move_cost(f, t), |n, t| g.heuristic(n, t));
+        let path = astar.run((0, 0), (4, 0)).unwrap();
+        let dijkstra = Dijkstra::with_weights(&g, |f, t| g.move_cost(f, t));
+        let expected = dijkstra.run((0, 0), (4, 0)).unwrap();
+
+        let cost =
+            |p: &[(usize, usize)]| p.windows(2).map(|w| g.move_cost(w[0], w[1])).sum::<u32>();
+        assert_eq!(cost(&path), cost(&expected));
+        assert!(path.contains(&(2, 4)));
+        // the only way around the wall is the gap in the bottom row
+        assert_eq!(path.len(), 13);
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let mut g = GridGraph::new(6, 6, Connectivity::Eight);
+        assert!(g.has_line_of_sight((0, 0), (5, 3)));
+        g.set_wall((2, 1));
+        assert!(!g.has_line_of_sight((0, 0), (5, 3)));
+        assert!(g.has_line_of_sight((0, 0), (5, 0)));
+        // exactly through the corner of a wall
+        g.set_wall((3, 2));
+        assert!(!g.has_line_of_sight((2, 2), (4, 0)));
+        assert!(g.has_line_of_sight((4, 4), (4, 4)));
+    }
+
+    #[test]
+    fn test_smoothing_and_any_angle() {
+        let mut g = GridGraph::new(8, 5, Connectivity::Four);
+        for y in 0..3 {
+            g.set_wall((4, y));
+        }
+
+        let dijkstra = Dijkstra::with_weights(&g, |f, t| g.move_cost(f, t));
+        let path = dijkstra.run((0, 0), (7, 0)).unwrap();
+        let smoothed = g.smooth_path(&path);
+        assert_eq!(smoothed.first(), Some(&(0, 0)));
+        assert_eq!(smoothed.last(), Some(&(7, 0)));
+        assert!(smoothed.len() < 6);
+        assert!(smoothed.windows(2).all(|w| g.has_line_of_sight(w[0], w[1])));
+        assert_eq!(g.smooth_path(&[(1, 1)]), vec![(1, 1)]);
+
+        let corners = g.any_angle_path((0, 0), (7, 0)).unwrap();
+        assert!(corners.windows(2).all(|w| g.has_line_of_sight(w[0], w[1])));
+        assert!(corners.iter().any(|c| c.1 == 3));
+        assert_eq!(corners.len(), 4);
+        assert_eq!(g.any_angle_path((0, 0), (4, 0)), None);
+    }
+}
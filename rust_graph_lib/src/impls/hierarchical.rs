@@ -0,0 +1,482 @@
+use crate::graph::{EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator};
+use crate::io::dot::{format_attrs, quote};
+use crate::ops::{aggregate_by, Aggregation};
+use std::{collections::HashMap, fmt::Display, hash::Hash, io::Write};
+
+/**
+ * Index of a cluster of a [`HierarchicalGraph`].
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct ClusterId(usize);
+
+impl Display for ClusterId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cluster_{}", self.0)
+    }
+}
+
+/**
+ * Node of the graph returned by [`HierarchicalGraph::flatten`]: either a node of the
+ * graph or a collapsed cluster standing for all the nodes it contains.
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum ViewNode<I> {
+    Node(I),
+    Cluster(ClusterId),
+}
+
+struct Cluster<I> {
+    label: String,
+    parent: Option<ClusterId>,
+    children: Vec<ClusterId>,
+    nodes: Vec<I>,
+    collapsed: bool,
+}
+
+/**
+ * Wrapper around a [`Graph`] grouping its nodes into nested clusters, such as the
+ * components and layers of an architecture diagram.
+ *
+ * Each node is in at most one cluster, the innermost one containing it, and each
+ * cluster can contain other clusters. Clusters can be collapsed, so that
+ * [`flatten`](Self::flatten) and [`write_dot`](Self::write_dot) show them as a
+ * single node, and expanded again to show their contents.
+ */
+pub struct HierarchicalGraph<G: Graph> {
+    graph: G,
+    clusters: Vec<Cluster<G::Index>>,
+    cluster_of: HashMap<G::Index, ClusterId>,
+}
+
+impl<G> HierarchicalGraph<G>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+{
+    /**
+     * Wraps the graph; its existing nodes are in no cluster.
+     */
+    pub fn new(graph: G) -> Self {
+        HierarchicalGraph {
+            graph,
+            clusters: Vec::new(),
+            cluster_of: HashMap::new(),
+        }
+    }
+
+    /**
+     * Adds an expanded cluster inside `parent`, or at the top level if `parent` is
+     * `None`. Returns `None` if `parent` is not a cluster of this graph.
+     */
+    pub fn add_cluster(&mut self, label: &str, parent: Option<ClusterId>) -> Option<ClusterId> {
+        let c = ClusterId(self.clusters.len());
+        if let Some(p) = parent {
+            self.clusters.get_mut(p.0)?.children.push(c);
+        }
+        self.clusters.push(Cluster {
+            label: label.to_string(),
+            parent,
+            children: Vec::new(),
+            nodes: Vec::new(),
+            collapsed: false,
+        });
+        Some(c)
+    }
+
+    /**
+     * Adds a node to the graph inside the given cluster.
+     */
+    pub fn add_node_to(&mut self, c: ClusterId) -> G::Index {
+        let n = self.graph.add_node();
+        self.move_node(n, Some(c));
+        n
+    }
+
+    /**
+     * Moves a node into the given cluster, or out of any cluster if `c` is `None`.
+     * Returns `false`, leaving the graph unchanged, if the node or the cluster is not
+     * in the graph.
+     */
+    pub fn move_node(&mut self, n: G::Index, c: Option<ClusterId>) -> bool {
+        if self.graph.iter_adj(n).is_none() || c.is_some_and(|c| c.0 >= self.clusters.len()) {
+            return false;
+        }
+        if let Some(old) = self.cluster_of.remove(&n) {
+            self.clusters[old.0].nodes.retain(|&m| m != n);
+        }
+        if let Some(c) = c {
+            self.clusters[c.0].nodes.push(n);
+            self.cluster_of.insert(n, c);
+        }
+        true
+    }
+
+    /**
+     * Returns the innermost cluster containing a node.
+     */
+    pub fn cluster_of(&self, n: G::Index) -> Option<ClusterId> {
+        self.cluster_of.get(&n).copied()
+    }
+
+    /**
+     * Returns the label of a cluster.
+     */
+    pub fn label(&self, c: ClusterId) -> Option<&str> {
+        self.clusters.get(c.0).map(|cl| cl.label.as_str())
+    }
+
+    /**
+     * Returns the cluster containing a cluster, if it is not at the top level.
+     */
+    pub fn parent(&self, c: ClusterId) -> Option<ClusterId> {
+        self.clusters.get(c.0)?.parent
+    }
+
+    /**
+     * Returns the clusters directly inside a cluster, in creation order.
+     */
+    pub fn children(&self, c: ClusterId) -> &[ClusterId] {
+        self.clusters.get(c.0).map_or(&[], |cl| &cl.children)
+    }
+
+    /**
+     * Returns the nodes in a cluster or in any of the clusters inside it, in
+     * ascending order.
+     */
+    pub fn members(&self, c: ClusterId) -> Vec<G::Index> {
+        let mut members = Vec::new();
+        let mut stack = vec![c];
+        while let Some(c) = stack.pop() {
+            if let Some(cl) = self.clusters.get(c.0) {
+                members.extend(&cl.nodes);
+                stack.extend(&cl.children);
+            }
+        }
+        members.sort();
+        members
+    }
+
+    /**
+     * Collapses a cluster, returning `false` if it is not in the graph.
+     */
+    pub fn collapse(&mut self, c: ClusterId) -> bool {
+        self.set_collapsed(c, true)
+    }
+
+    /**
+     * Expands a cluster, returning `false` if it is not in the graph. The clusters
+     * inside it keep their own state.
+     */
+    pub fn expand(&mut self, c: ClusterId) -> bool {
+        self.set_collapsed(c, false)
+    }
+
+    /**
+     * Expands every cluster.
+     */
+    pub fn expand_all(&mut self) {
+        for cl in &mut self.clusters {
+            cl.collapsed = false;
+        }
+    }
+
+    /**
+     * Returns whether a cluster is collapsed.
+     */
+    pub fn is_collapsed(&self, c: ClusterId) -> bool {
+        self.clusters.get(c.0).is_some_and(|cl| cl.collapsed)
+    }
+
+    fn set_collapsed(&mut self, c: ClusterId, collapsed: bool) -> bool {
+        match self.clusters.get_mut(c.0) {
+            Some(cl) => {
+                cl.collapsed = collapsed;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * Returns the outermost collapsed cluster containing a node, where the node is
+     * hidden when viewing the graph.
+     */
+    fn hidden_in(&self, n: G::Index) -> Option<ClusterId> {
+        let mut outermost = None;
+        let mut current = self.cluster_of(n);
+        while let Some(c) = current {
+            let cl = &self.clusters[c.0];
+            if cl.collapsed {
+                outermost = Some(c);
+            }
+            current = cl.parent;
+        }
+        outermost
+    }
+
+    /**
+     * Returns the graph as currently shown, where each collapsed cluster that is not
+     * inside another collapsed cluster replaces all the nodes it contains, along with
+     * the number of edges each of its edges stands for; see [`aggregate_by`].
+     *
+     * Edges between nodes of the same collapsed cluster become a loop on it. With
+     * every cluster expanded, the result has the same nodes and edges as the graph.
+     */
+    pub fn flatten(&self) -> Aggregation<ViewNode<G::Index>, G::Index, usize> {
+        aggregate_by(&self.graph, |n| match self.hidden_in(n) {
+            Some(c) => ViewNode::Cluster(c),
+            None => ViewNode::Node(n),
+        })
+    }
+
+    /**
+     * Writes the graph in Graphviz DOT format, with each expanded cluster as a
+     * `subgraph` whose name starts with `cluster`, so that it is drawn as a labelled
+     * box around its contents.
+     *
+     * The graph is written as returned by [`flatten`](Self::flatten): collapsed clusters
+     * are box-shaped nodes labelled like the cluster, without the loops standing for
+     * their inner edges.
+     */
+    pub fn write_dot<W: Write>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        G::Index: Display,
+    {
+        let view = self.flatten();
+        writeln!(writer, "digraph {{")?;
+        for (c, cl) in self.clusters.iter().enumerate() {
+            if cl.parent.is_none() {
+                self.write_cluster(&mut writer, ClusterId(c), 1)?;
+            }
+        }
+        let mut loose: Vec<_> = self
+            .graph
+            .iter_nodes()
+            .filter(|n| !self.cluster_of.contains_key(n))
+            .collect();
+        loose.sort();
+        for n in loose {
+            writeln!(writer, "  {};", quote(&n.to_string()))?;
+        }
+
+        let name = |n: ViewNode<G::Index>| match n {
+            ViewNode::Node(n) => quote(&n.to_string()),
+            ViewNode::Cluster(c) => quote(&c.to_string()),
+        };
+        let mut edges: Vec<_> = view
+            .graph
+            .iter_edges()
+            .map(|(f, t)| (view.graph.key(f).unwrap(), view.graph.key(t).unwrap()))
+            .filter(|(f, t)| f != t || matches!(f, ViewNode::Node(_)))
+            .collect();
+        edges.sort();
+        for (&f, &t) in edges {
+            writeln!(writer, "  {} -> {};", name(f), name(t))?;
+        }
+        writeln!(writer, "}}")
+    }
+
+    fn write_cluster<W: Write>(
+        &self,
+        writer: &mut W,
+        c: ClusterId,
+        depth: usize,
+    ) -> std::io::Result<()>
+    where
+        G::Index: Display,
+    {
+        let cl = &self.clusters[c.0];
+        let indent = "  ".repeat(depth);
+        if cl.collapsed {
+            let attrs = vec![("label", cl.label.clone()), ("shape", "box".to_string())];
+            return writeln!(
+                writer,
+                "{}{}{};",
+                indent,
+                quote(&c.to_string()),
+                format_attrs(&attrs)
+            );
+        }
+
+        writeln!(writer, "{}subgraph {} {{", indent, quote(&c.to_string()))?;
+        writeln!(writer, "{}  label={};", indent, quote(&cl.label))?;
+        let mut nodes = cl.nodes.clone();
+        nodes.sort();
+        for n in nodes {
+            writeln!(writer, "{}  {};", indent, quote(&n.to_string()))?;
+        }
+        for &child in &cl.children {
+            self.write_cluster(writer, child, depth + 1)?;
+        }
+        writeln!(writer, "{}}}", indent)
+    }
+
+    /**
+     * Returns the wrapped graph.
+     */
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /**
+     * Drops the clusters, returning the wrapped graph.
+     */
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+}
+
+impl<G> GraphRead for HierarchicalGraph<G>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.has_edge(f, t)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        self.graph.iter_adj(n)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        self.graph.iter_edges()
+    }
+
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>> {
+        self.graph.iter_incident(n)
+    }
+}
+
+impl<G> Graph for HierarchicalGraph<G>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+{
+    fn add_node(&mut self) -> Self::Index {
+        self.graph.add_node()
+    }
+
+    fn add_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.add_edge(f, t)
+    }
+
+    fn remove_node(&mut self, n: Self::Index) {
+        self.move_node(n, None);
+        self.graph.remove_node(n)
+    }
+
+    fn remove_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.remove_edge(f, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    #[test]
+    fn test_clusters() {
+        let mut g = HierarchicalGraph::new(AdjListGraph::new());
+        let backend = g.add_cluster("backend", None).unwrap();
+        let db = g.add_cluster("db", Some(backend)).unwrap();
+        let api = g.add_node_to(backend);
+        let store = g.add_node_to(db);
+        let client = g.add_node();
+        g.add_edge(client, api);
+        g.add_edge(api, store);
+
+        assert_eq!(g.cluster_of(store), Some(db));
+        assert_eq!(g.parent(db), Some(backend));
+        assert_eq!(g.children(backend), &[db]);
+        assert_eq!(g.members(backend), vec![api, store]);
+        assert_eq!(g.add_cluster("x", Some(ClusterId(5))), None);
+
+        assert!(g.move_node(store, Some(backend)));
+        assert!(g.members(db).is_empty());
+        g.remove_node(store);
+        assert_eq!(g.cluster_of(store), None);
+        assert_eq!(g.members(backend), vec![api]);
+        assert!(!g.move_node(store, Some(db)));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let mut g = HierarchicalGraph::new(AdjListGraph::new());
+        let outer = g.add_cluster("outer", None).unwrap();
+        let inner = g.add_cluster("inner", Some(outer)).unwrap();
+        let a = g.add_node_to(outer);
+        let b = g.add_node_to(inner);
+        let c = g.add_node_to(inner);
+        let d = g.add_node();
+        for (f, t) in [(a, b), (b, c), (c, d), (b, d), (d, a)] {
+            g.add_edge(f, t);
+        }
+
+        assert_eq!(g.flatten().graph.edge_count(), 5);
+        g.collapse(inner);
+        let view = g.flatten();
+        assert_eq!(view.graph.node_count(), 3);
+        let hidden = ViewNode::Cluster(inner);
+        assert_eq!(view.weight(&hidden, &ViewNode::Node(d)), Some(2));
+        assert_eq!(view.weight(&hidden, &hidden), Some(1));
+        assert_eq!(view.membership[&c], view.graph.node_index(&hidden).unwrap());
+
+        // collapsing the outer cluster hides the inner one as well
+        g.collapse(outer);
+        assert_eq!(g.flatten().graph.node_count(), 2);
+        g.expand(outer);
+        assert!(g.is_collapsed(inner));
+        g.expand_all();
+        assert_eq!(g.flatten().graph.node_count(), 4);
+    }
+
+    #[test]
+    fn test_write_dot() {
+        let mut g = HierarchicalGraph::new(AdjListGraph::new());
+        let outer = g.add_cluster("outer", None).unwrap();
+        let inner = g.add_cluster("inner", Some(outer)).unwrap();
+        let a = g.add_node_to(outer);
+        let b = g.add_node_to(inner);
+        let c = g.add_node_to(inner);
+        let d = g.add_node();
+        for (f, t) in [(a, b), (b, c), (c, d)] {
+            g.add_edge(f, t);
+        }
+
+        let dot = |g: &HierarchicalGraph<_>| {
+            let mut out = Vec::new();
+            g.write_dot(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            dot(&g),
+            "digraph {\n  subgraph \"cluster_0\" {\n    label=\"outer\";\n    \"1\";\n    \
+             subgraph \"cluster_1\" {\n      label=\"inner\";\n      \"2\";\n      \"3\";\n    \
+             }\n  }\n  \"4\";\n  \"1\" -> \"2\";\n  \"2\" -> \"3\";\n  \"3\" -> \"4\";\n}\n"
+        );
+
+        g.collapse(inner);
+        assert_eq!(
+            dot(&g),
+            "digraph {\n  subgraph \"cluster_0\" {\n    label=\"outer\";\n    \"1\";\n    \
+             \"cluster_1\" [label=\"inner\", shape=\"box\"];\n  }\n  \"4\";\n  \
+             \"1\" -> \"cluster_1\";\n  \"cluster_1\" -> \"4\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::graph::{ImplicitGraph, NodeIterator};
+use std::marker::PhantomData;
+
+/**
+ * [`ImplicitGraph`] whose successors are computed by a closure.
+ *
+ * ```
+ * use rust_graph_lib::{algorithms::{Algorithm, Dijkstra}, impls::implicit::FnGraph};
+ *
+ * // the fewest `+1` and `*2` steps to turn 1 into 10
+ * let g = FnGraph::new(|n: u32| [n + 1, n * 2].into_iter().filter(|&s| s <= 10).collect::<Vec<_>>());
+ * let path = Dijkstra::new(&g).run(1, 10).unwrap();
+ * assert_eq!(path, vec![1, 2, 4, 5, 10]);
+ * ```
+ */
+pub struct FnGraph<I, F> {
+    successors: F,
+    index: PhantomData<fn(I) -> I>,
+}
+
+impl<I, F> FnGraph<I, F> {
+    /**
+     * Creates a graph where the successors of `n` are the items of `successors(n)`.
+     */
+    pub fn new(successors: F) -> Self {
+        FnGraph {
+            successors,
+            index: PhantomData,
+        }
+    }
+}
+
+impl<I, F, S> ImplicitGraph for FnGraph<I, F>
+where
+    I: Ord,
+    F: Fn(I) -> S,
+    S: IntoIterator<Item = I>,
+    S::IntoIter: 'static,
+{
+    type Index = I;
+
+    fn successors(&self, n: I) -> Box<NodeIterator<'_, I>> {
+        Box::new((self.successors)(n).into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{AStar, Algorithm, Dfs};
+
+    #[test]
+    fn test_search_state_space() {
+        // knight moves on an unbounded board, never materialized
+        let g = FnGraph::new(|(x, y): (i32, i32)| {
+            [
+                (1, 2),
+                (2, 1),
+                (2, -1),
+                (1, -2),
+                (-1, -2),
+                (-2, -1),
+                (-2, 1),
+                (-1, 2),
+            ]
+            .map(|(dx, dy)| (x + dx, y + dy))
+        });
+        let remaining = |(x, y): (i32, i32), (tx, ty): (i32, i32)| {
+            ((x - tx).unsigned_abs() + (y - ty).unsigned_abs()).div_ceil(3)
+        };
+
+        let path = AStar::new(&g, remaining).run((0, 0), (5, 5)).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path.last(), Some(&(5, 5)));
+    }
+
+    #[test]
+    fn test_dfs_on_finite_space() {
+        let g = FnGraph::new(|n: u8| (n < 5).then_some(n + 1));
+        let path = Dfs::new(&g).run(0, 5).unwrap();
+        assert_eq!(path, vec![0, 1, 2, 3, 4, 5]);
+        assert!(Dfs::new(&g).run(5, 0).is_none());
+    }
+}
@@ -0,0 +1,332 @@
+use super::adj_list::Index;
+use crate::format::GraphFormatter;
+use crate::graph::{EdgeIterator, Graph, GraphRead, NodeIterator};
+use crate::invariant::InvariantViolation;
+use crate::memory::{MemoryReport, MemoryUsage};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/**
+ * Dense matrix implementation of [`Graph`].
+ *
+ * Stores one flag per ordered pair of nodes, so it uses `O(n²)` memory regardless of
+ * the number of edges but answers [`GraphRead::has_edge`] in constant time.
+ * Edges between nodes not in the graph are ignored.
+ */
+pub struct IncMatrixGraph {
+    matrix: Vec<Vec<bool>>,
+    alive: Vec<bool>,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl IncMatrixGraph {
+    /**
+     * Creates a new graph.
+     */
+    pub fn new() -> Self {
+        IncMatrixGraph {
+            matrix: Vec::new(),
+            alive: Vec::new(),
+            node_count: 0,
+            edge_count: 0,
+        }
+    }
+
+    /**
+     * Creates a graph with `n` nodes and no edges, with indices from 1 to `n`.
+     */
+    pub fn with_nodes(n: usize) -> Self {
+        IncMatrixGraph {
+            matrix: vec![vec![false; n]; n],
+            alive: vec![true; n],
+            node_count: n,
+            edge_count: 0,
+        }
+    }
+
+    /**
+     * Checks the internal bookkeeping: the node and edge counts and that removed
+     * nodes have no edges left.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = self.alive.iter().filter(|&&a| a).count();
+        if actual != self.node_count {
+            return Err(InvariantViolation::NodeCount {
+                stored: self.node_count,
+                actual,
+            });
+        }
+
+        let mut actual = 0;
+        for (f, row) in self.matrix.iter().enumerate() {
+            for (t, _) in row.iter().enumerate().filter(|(_, &e)| e) {
+                if !self.alive[f] || !self.alive[t] {
+                    return Err(InvariantViolation::DanglingEdge(format!(
+                        "{} -> {}",
+                        Self::index(f),
+                        Self::index(t)
+                    )));
+                }
+                actual += 1;
+            }
+        }
+        if actual != self.edge_count {
+            return Err(InvariantViolation::EdgeCount {
+                stored: self.edge_count,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    fn pos(&self, n: Index) -> Option<usize> {
+        let pos = n.0.checked_sub(1)?;
+        self.alive.get(pos).copied().unwrap_or(false).then_some(pos)
+    }
+
+    fn index(pos: usize) -> Index {
+        Index(pos + 1)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl IncMatrixGraph {
+    /**
+     * Parallel version of [`GraphRead::iter_nodes`].
+     */
+    pub fn par_iter_nodes(&self) -> impl ParallelIterator<Item = Index> + '_ {
+        let it = self.alive.par_iter().enumerate();
+        it.filter(|(_, &a)| a).map(|(i, _)| Self::index(i))
+    }
+
+    /**
+     * Parallel version of [`GraphRead::iter_edges`].
+     */
+    pub fn par_iter_edges(&self) -> impl ParallelIterator<Item = (Index, Index)> + '_ {
+        self.matrix
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(f, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &e)| e)
+                    .map(move |(t, _)| (Self::index(f), Self::index(t)))
+            })
+    }
+}
+
+impl Default for IncMatrixGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphRead for IncMatrixGraph {
+    type Index = Index;
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        match (self.pos(f), self.pos(t)) {
+            (Some(f), Some(t)) => self.matrix[f][t],
+            _ => false,
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        Box::new(
+            self.alive
+                .iter()
+                .enumerate()
+                .filter(|(_, &a)| a)
+                .map(|(i, _)| Self::index(i)),
+        )
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        let pos = self.pos(n)?;
+        let it: Box<NodeIterator<'_, Index>> = Box::new(
+            self.matrix[pos]
+                .iter()
+                .enumerate()
+                .filter(|(_, &e)| e)
+                .map(|(i, _)| Self::index(i)),
+        );
+        Some(it)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        let it = self.matrix.iter().enumerate().flat_map(|(f, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, &e)| e)
+                .map(move |(t, _)| (Self::index(f), Self::index(t)))
+        });
+        Box::new(it)
+    }
+}
+
+impl Graph for IncMatrixGraph {
+    fn add_node(&mut self) -> Index {
+        for row in &mut self.matrix {
+            row.push(false);
+        }
+        self.alive.push(true);
+        self.matrix.push(vec![false; self.alive.len()]);
+        self.node_count += 1;
+        Self::index(self.alive.len() - 1)
+    }
+
+    fn add_edge(&mut self, f: Index, t: Index) {
+        if let (Some(f), Some(t)) = (self.pos(f), self.pos(t)) {
+            if !self.matrix[f][t] {
+                self.matrix[f][t] = true;
+                self.edge_count += 1;
+            }
+        }
+    }
+
+    fn remove_node(&mut self, n: Index) {
+        let pos = match self.pos(n) {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        let mut removed = 0;
+        for (i, row) in self.matrix.iter_mut().enumerate() {
+            if std::mem::take(&mut row[pos]) {
+                removed += 1;
+            }
+            if i == pos {
+                removed += row.iter().filter(|&&e| e).count();
+                row.fill(false);
+            }
+        }
+        self.alive[pos] = false;
+        self.node_count -= 1;
+        self.edge_count -= removed;
+    }
+
+    fn remove_nodes_where<F>(&mut self, mut remove: F) -> usize
+    where
+        F: FnMut(Index) -> bool,
+    {
+        let doomed: Vec<_> = (0..self.alive.len())
+            .map(|pos| self.alive[pos] && remove(Self::index(pos)))
+            .collect();
+
+        let mut removed = 0;
+        for (f, row) in self.matrix.iter_mut().enumerate() {
+            for (t, e) in row.iter_mut().enumerate() {
+                if *e && (doomed[f] || doomed[t]) {
+                    *e = false;
+                    removed += 1;
+                }
+            }
+        }
+        let count = doomed.iter().filter(|&&d| d).count();
+        for (alive, doomed) in self.alive.iter_mut().zip(doomed) {
+            *alive &= !doomed;
+        }
+        self.node_count -= count;
+        self.edge_count -= removed;
+        count
+    }
+
+    fn remove_edge(&mut self, f: Index, t: Index) {
+        if let (Some(f), Some(t)) = (self.pos(f), self.pos(t)) {
+            if std::mem::take(&mut self.matrix[f][t]) {
+                self.edge_count -= 1;
+            }
+        }
+    }
+}
+
+impl MemoryUsage for IncMatrixGraph {
+    fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: self.alive.capacity(),
+            adjacency: self
+                .matrix
+                .iter()
+                .map(|row| row.capacity() + std::mem::size_of::<Vec<bool>>())
+                .sum(),
+            mappings: 0,
+            other: std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+impl Display for IncMatrixGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_remove() {
+        let mut g = IncMatrixGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id1);
+        assert_eq!(g.edge_count(), 3);
+        assert!(g.has_edge(id1, id2) && !g.has_edge(id2, id1));
+
+        g.remove_node(id1);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 1);
+        assert!(g.iter_adj(id1).is_none());
+        assert_eq!(g.iter_edges().collect::<Vec<_>>(), vec![(id2, id3)]);
+
+        g.remove_edge(id2, id3);
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_remove_nodes_where() {
+        let mut g = IncMatrixGraph::with_nodes(5);
+        for f in 1..=5 {
+            for t in 1..=5 {
+                g.add_edge(Index(f), Index(t));
+            }
+        }
+
+        assert_eq!(g.remove_nodes_where(|n| n.0 % 2 == 1), 3);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 4);
+        assert!(g.iter_adj(Index(3)).is_none());
+        assert_eq!(g.remove_nodes_where(|_| false), 0);
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_with_nodes() {
+        let mut g = IncMatrixGraph::with_nodes(3);
+        assert_eq!(g.node_count(), 3);
+        let nodes: Vec<_> = g.iter_nodes().collect();
+        assert_eq!(nodes, vec![Index(1), Index(2), Index(3)]);
+
+        assert_eq!(g.add_node(), Index(4));
+        g.add_edge(Index(4), Index(1));
+        g.add_edge(Index(4), Index(9));
+        assert_eq!(g.edge_count(), 1);
+    }
+}
@@ -0,0 +1,586 @@
+use crate::format::GraphFormatter;
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+use crate::invariant::InvariantViolation;
+use crate::memory::{hash_table_bytes, MemoryReport, MemoryUsage};
+use crate::utils::{Slab, SlotKey};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+    hash::Hash,
+    ops::Add,
+};
+
+struct Node<K, V, E> {
+    key: K,
+    value: V,
+    adj: Vec<SlotKey>,
+    // weight of the edge to each neighbor, in the same order as `adj`
+    weights: Vec<E>,
+}
+
+/**
+ * How [`KeyedGraph::merge`] combines the weights of an edge found in both graphs.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum EdgeConflictPolicy {
+    /**
+     * Keeps the smaller weight.
+     */
+    KeepMin,
+    /**
+     * Adds the weights.
+     */
+    Sum,
+    /**
+     * Takes the weight of the merged graph.
+     */
+    Overwrite,
+}
+
+/**
+ * Graph whose nodes are identified by a user key and carry a payload, and whose
+ * edges carry a weight of type `E`, none by default.
+ *
+ * Nodes, payloads and their sorted neighbor lists live contiguously in a [`Slab`],
+ * so going from an index to its payload is a direct slot access. Indices of removed
+ * nodes are never valid again, even when their slot is reused.
+ */
+pub struct KeyedGraph<K, V, E = ()> {
+    nodes: Slab<Node<K, V, E>>,
+    by_key: HashMap<K, SlotKey>,
+    edge_count: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> KeyedGraph<K, V> {
+    /**
+     * Creates a new graph without edge weights.
+     */
+    pub fn new() -> Self {
+        Self::weighted()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, E> KeyedGraph<K, V, E> {
+    /**
+     * Creates a new graph whose edges weigh an `E`.
+     */
+    pub fn weighted() -> Self {
+        KeyedGraph {
+            nodes: Slab::new(),
+            by_key: HashMap::new(),
+            edge_count: 0,
+        }
+    }
+
+    /**
+     * Adds a node with the given key and payload, returning its index.
+     *
+     * If a node with the same key is already in the graph, its payload is replaced.
+     */
+    pub fn insert_node(&mut self, key: K, value: V) -> SlotKey {
+        if let Some(&n) = self.by_key.get(&key) {
+            self.nodes.get_mut(n).unwrap().value = value;
+            return n;
+        }
+
+        let n = self.nodes.insert(Node {
+            key: key.clone(),
+            value,
+            adj: Vec::new(),
+            weights: Vec::new(),
+        });
+        self.by_key.insert(key, n);
+        n
+    }
+
+    /**
+     * Returns the index of the node with the given key.
+     */
+    pub fn node_index(&self, key: &K) -> Option<SlotKey> {
+        self.by_key.get(key).copied()
+    }
+
+    /**
+     * Returns the key of a node.
+     */
+    pub fn key(&self, n: SlotKey) -> Option<&K> {
+        self.nodes.get(n).map(|node| &node.key)
+    }
+
+    /**
+     * Returns the payload of a node.
+     */
+    pub fn payload(&self, n: SlotKey) -> Option<&V> {
+        self.nodes.get(n).map(|node| &node.value)
+    }
+
+    /**
+     * Returns the payload of a node mutably.
+     */
+    pub fn payload_mut(&mut self, n: SlotKey) -> Option<&mut V> {
+        self.nodes.get_mut(n).map(|node| &mut node.value)
+    }
+
+    /**
+     * Changes the key of the node with key `old` to `new`, keeping its index, payload
+     * and edges.
+     *
+     * Returns `false`, leaving the graph unchanged, if there is no node with key `old`
+     * or another node already has key `new`.
+     */
+    pub fn rename_node(&mut self, old: &K, new: K) -> bool {
+        let Some(&n) = self.by_key.get(old) else {
+            return false;
+        };
+        if self.by_key.contains_key(&new) {
+            return *old == new;
+        }
+
+        self.by_key.remove(old);
+        self.by_key.insert(new.clone(), n);
+        self.nodes.get_mut(n).unwrap().key = new;
+        true
+    }
+
+    /**
+     * Adds an edge with the given weight, or replaces the weight of the edge if it is
+     * already in the graph; does nothing if either node is not in the graph.
+     */
+    pub fn add_weighted_edge(&mut self, f: SlotKey, t: SlotKey, w: E) {
+        if !self.nodes.contains(t) {
+            return;
+        }
+        if let Some(node) = self.nodes.get_mut(f) {
+            match node.adj.binary_search(&t) {
+                Ok(pos) => node.weights[pos] = w,
+                Err(pos) => {
+                    node.adj.insert(pos, t);
+                    node.weights.insert(pos, w);
+                    self.edge_count += 1;
+                }
+            }
+        }
+    }
+
+    /**
+     * Returns the weight of an edge, if the edge is in the graph.
+     */
+    pub fn edge_weight(&self, f: SlotKey, t: SlotKey) -> Option<&E> {
+        let node = self.nodes.get(f)?;
+        let pos = node.adj.binary_search(&t).ok()?;
+        Some(&node.weights[pos])
+    }
+
+    /**
+     * Adds the nodes and edges of `other`, identifying nodes by key, and returns the
+     * index in this graph of each node of `other`; see [`KeyedGraph::merge_with`].
+     */
+    pub fn merge(&mut self, other: Self, policy: EdgeConflictPolicy) -> HashMap<SlotKey, SlotKey>
+    where
+        E: Copy + PartialOrd + Add<Output = E>,
+    {
+        self.merge_with(other, |&old, new| match policy {
+            EdgeConflictPolicy::KeepMin if old <= new => old,
+            EdgeConflictPolicy::Sum => old + new,
+            _ => new,
+        })
+    }
+
+    /**
+     * Adds the nodes and edges of `other`, identifying nodes by key, and returns the
+     * index in this graph of each node of `other`, e.g. to combine graphs built from
+     * several data sources.
+     *
+     * Nodes only in `other` are added with their payload, while nodes with a key
+     * already in this graph keep their own. Edges in both graphs get the weight
+     * `resolve(old, new)`, given the weight in this graph and the one in `other`.
+     */
+    pub fn merge_with<F>(&mut self, other: Self, mut resolve: F) -> HashMap<SlotKey, SlotKey>
+    where
+        F: FnMut(&E, E) -> E,
+    {
+        let mut mapping = HashMap::with_capacity(other.nodes.len());
+        let mut edges = Vec::with_capacity(other.nodes.len());
+        for (n, node) in other.nodes.into_entries() {
+            let m = match self.node_index(&node.key) {
+                Some(m) => m,
+                None => self.insert_node(node.key, node.value),
+            };
+            mapping.insert(n, m);
+            edges.push((m, node.adj, node.weights));
+        }
+
+        for (f, adj, weights) in edges {
+            for (t, w) in adj.into_iter().zip(weights) {
+                let t = mapping[&t];
+                let w = match self.edge_weight(f, t) {
+                    Some(old) => resolve(old, w),
+                    None => w,
+                };
+                self.add_weighted_edge(f, t, w);
+            }
+        }
+        mapping
+    }
+
+    /**
+     * Removes a node and its edges, returning its key and payload.
+     */
+    pub fn remove_node(&mut self, n: SlotKey) -> Option<(K, V)> {
+        let node = self.nodes.remove(n)?;
+        self.by_key.remove(&node.key);
+        self.edge_count -= node.adj.len();
+
+        for other in self.nodes.values_mut() {
+            if let Ok(pos) = other.adj.binary_search(&n) {
+                other.adj.remove(pos);
+                other.weights.remove(pos);
+                self.edge_count -= 1;
+            }
+        }
+        Some((node.key, node.value))
+    }
+
+    /**
+     * Removes the nodes for which `remove` returns `true`, given their index, key and
+     * payload, with all their edges, in a single pass over the neighbor lists.
+     *
+     * Returns the keys and payloads of the removed nodes, in slot order.
+     */
+    pub fn remove_nodes_where<F>(&mut self, mut remove: F) -> Vec<(K, V)>
+    where
+        F: FnMut(SlotKey, &K, &V) -> bool,
+    {
+        let doomed: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|(n, node)| remove(*n, &node.key, &node.value))
+            .map(|(n, _)| n)
+            .collect();
+        if doomed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut removed = Vec::with_capacity(doomed.len());
+        for &n in &doomed {
+            let node = self.nodes.remove(n).unwrap();
+            self.by_key.remove(&node.key);
+            self.edge_count -= node.adj.len();
+            removed.push((node.key, node.value));
+        }
+        let doomed: HashSet<_> = doomed.into_iter().collect();
+        for node in self.nodes.values_mut() {
+            let len = node.adj.len();
+            let mut kept = node.adj.iter().map(|t| !doomed.contains(t));
+            node.weights.retain(|_| kept.next().unwrap());
+            node.adj.retain(|t| !doomed.contains(t));
+            self.edge_count -= len - node.adj.len();
+        }
+        removed
+    }
+
+    /**
+     * Removes an edge.
+     */
+    pub fn remove_edge(&mut self, f: SlotKey, t: SlotKey) {
+        if let Some(node) = self.nodes.get_mut(f) {
+            if let Ok(pos) = node.adj.binary_search(&t) {
+                node.adj.remove(pos);
+                node.weights.remove(pos);
+                self.edge_count -= 1;
+            }
+        }
+    }
+
+    /**
+     * Checks the internal bookkeeping: the edge count, that edges only reference
+     * nodes in the graph and have a weight, and that the key index agrees with the
+     * nodes.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = self.nodes.iter().map(|(_, node)| node.adj.len()).sum();
+        if actual != self.edge_count {
+            return Err(InvariantViolation::EdgeCount {
+                stored: self.edge_count,
+                actual,
+            });
+        }
+
+        if self.by_key.len() != self.nodes.len() {
+            return Err(InvariantViolation::Mapping(format!(
+                "{} keys for {} nodes",
+                self.by_key.len(),
+                self.nodes.len()
+            )));
+        }
+        for (n, node) in self.nodes.iter() {
+            if self.by_key.get(&node.key) != Some(&n) {
+                return Err(InvariantViolation::Mapping(format!(
+                    "key of node {} is not indexed",
+                    n
+                )));
+            }
+            if let Some(a) = node.adj.iter().find(|&&a| !self.nodes.contains(a)) {
+                return Err(InvariantViolation::DanglingEdge(format!("{} -> {}", n, a)));
+            }
+            if node.weights.len() != node.adj.len() {
+                return Err(InvariantViolation::Mapping(format!(
+                    "{} weights for the {} edges of node {}",
+                    node.weights.len(),
+                    node.adj.len(),
+                    n
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K: Hash + Eq + Clone, V, E: Default> KeyedGraph<K, V, E> {
+    /**
+     * Adds an edge with the default weight, leaving the weight unchanged if the edge
+     * is already in the graph; does nothing if either node is not in the graph.
+     */
+    pub fn add_edge(&mut self, f: SlotKey, t: SlotKey) {
+        if self.edge_weight(f, t).is_none() {
+            self.add_weighted_edge(f, t, E::default());
+        }
+    }
+
+    /**
+     * Adds an edge between the nodes with the given keys, returning `false` if either is missing.
+     */
+    pub fn add_edge_by_key(&mut self, f: &K, t: &K) -> bool {
+        match (self.node_index(f), self.node_index(t)) {
+            (Some(f), Some(t)) => {
+                self.add_edge(f, t);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for KeyedGraph<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, E> GraphRead for KeyedGraph<K, V, E> {
+    type Index = SlotKey;
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn has_edge(&self, f: SlotKey, t: SlotKey) -> bool {
+        self.nodes
+            .get(f)
+            .is_some_and(|node| node.adj.binary_search(&t).is_ok())
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, SlotKey>> {
+        Box::new(self.nodes.iter().map(|(n, _)| n))
+    }
+
+    fn iter_adj(&self, n: SlotKey) -> Option<Box<NodeIterator<'_, SlotKey>>> {
+        self.nodes.get(n).map(|node| {
+            let it: Box<NodeIterator<'_, SlotKey>> = Box::new(node.adj.iter().copied());
+            it
+        })
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, SlotKey>> {
+        let it = self
+            .nodes
+            .iter()
+            .flat_map(|(n, node)| node.adj.iter().map(move |&t| (n, t)));
+        Box::new(it)
+    }
+}
+
+impl<K, V, E> MemoryUsage for KeyedGraph<K, V, E> {
+    fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: self.nodes.capacity() * std::mem::size_of::<Node<K, V, E>>(),
+            adjacency: self
+                .nodes
+                .iter()
+                .map(|(_, node)| {
+                    node.adj.capacity() * std::mem::size_of::<SlotKey>()
+                        + node.weights.capacity() * std::mem::size_of::<E>()
+                })
+                .sum(),
+            mappings: hash_table_bytes::<(K, SlotKey)>(self.by_key.capacity()),
+            other: std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+impl<K, V, E> Display for KeyedGraph<K, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_and_payloads() {
+        let mut g = KeyedGraph::new();
+        let rome = g.insert_node("rome", 2_800_000);
+        let milan = g.insert_node("milan", 1_300_000);
+        assert!(g.add_edge_by_key(&"rome", &"milan"));
+        assert!(!g.add_edge_by_key(&"rome", &"turin"));
+
+        assert_eq!(g.insert_node("milan", 1_400_000), milan);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.payload(milan), Some(&1_400_000));
+        *g.payload_mut(rome).unwrap() += 1;
+        assert_eq!(g.payload(rome), Some(&2_800_001));
+        assert_eq!(g.key(rome), Some(&"rome"));
+        assert!(g.has_edge(rome, milan));
+    }
+
+    #[test]
+    fn test_rename_node() {
+        let mut g = KeyedGraph::new();
+        let a = g.insert_node("a", 1);
+        let b = g.insert_node("b", 2);
+        g.add_edge(a, b);
+        g.add_edge(b, a);
+
+        assert!(g.rename_node(&"a", "z"));
+        assert_eq!(g.node_index(&"z"), Some(a));
+        assert_eq!(g.node_index(&"a"), None);
+        assert_eq!(g.key(a), Some(&"z"));
+        assert_eq!(g.payload(a), Some(&1));
+        assert!(g.has_edge(a, b) && g.has_edge(b, a));
+
+        assert!(!g.rename_node(&"a", "y"));
+        assert!(!g.rename_node(&"z", "b"));
+        assert!(g.rename_node(&"b", "b"));
+        assert_eq!(g.key(a), Some(&"z"));
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_remove_nodes_where() {
+        let mut g = KeyedGraph::new();
+        let ids: Vec<_> = (0..6).map(|i| g.insert_node(i, i * 10)).collect();
+        for i in 0..6 {
+            g.add_edge(ids[i], ids[(i + 1) % 6]);
+            g.add_edge(ids[i], ids[(i + 3) % 6]);
+        }
+
+        let removed = g.remove_nodes_where(|_, k, &v| k % 2 == 0 && v > 0);
+        assert_eq!(removed, vec![(2, 20), (4, 40)]);
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.node_index(&2), None);
+        assert!(g.has_edge(ids[0], ids[1]) && !g.has_edge(ids[1], ids[2]));
+        assert_eq!(g.edge_count(), 4);
+        assert_eq!(g.validate(), Ok(()));
+        assert!(g.remove_nodes_where(|_, _, _| false).is_empty());
+    }
+
+    #[test]
+    fn test_edge_weights() {
+        let mut g = KeyedGraph::weighted();
+        let ids: Vec<_> = (0..4).map(|i| g.insert_node(i, ())).collect();
+        g.add_weighted_edge(ids[0], ids[2], 5u32);
+        g.add_weighted_edge(ids[0], ids[1], 3);
+        g.add_weighted_edge(ids[0], ids[3], 1);
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[0]);
+        assert_eq!(g.edge_weight(ids[0], ids[1]), Some(&3));
+        assert_eq!(g.edge_weight(ids[1], ids[0]), Some(&0));
+
+        g.remove_edge(ids[0], ids[1]);
+        g.remove_node(ids[3]);
+        assert_eq!(g.edge_weight(ids[0], ids[2]), Some(&5));
+        g.remove_nodes_where(|_, &k, _| k == 1);
+        assert_eq!(g.edge_weight(ids[0], ids[2]), Some(&5));
+        assert_eq!(g.edge_count(), 1);
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_merge() {
+        let source = |edges: &[(&'static str, &'static str, u32)]| {
+            let mut g = KeyedGraph::weighted();
+            for &(f, t, w) in edges {
+                let f = g.insert_node(f, f.len());
+                let t = g.insert_node(t, t.len());
+                g.add_weighted_edge(f, t, w);
+            }
+            g
+        };
+        let base = || source(&[("rome", "milan", 6), ("milan", "turin", 2)]);
+        let other = || {
+            let mut g = source(&[
+                ("milan", "rome", 1),
+                ("rome", "milan", 4),
+                ("turin", "genoa", 3),
+            ]);
+            *g.payload_mut(g.node_index(&"rome").unwrap()).unwrap() = 0;
+            g
+        };
+        let weight = |g: &KeyedGraph<_, _, u32>, f, t| {
+            let (f, t) = (g.node_index(&f).unwrap(), g.node_index(&t).unwrap());
+            g.edge_weight(f, t).copied()
+        };
+
+        let mut g = base();
+        let incoming = other();
+        let genoa = incoming.node_index(&"genoa").unwrap();
+        let mapping = g.merge(incoming, EdgeConflictPolicy::KeepMin);
+        assert_eq!((g.node_count(), g.edge_count()), (4, 4));
+        assert_eq!(g.key(mapping[&genoa]), Some(&"genoa"));
+        assert_eq!(g.payload(g.node_index(&"rome").unwrap()), Some(&4));
+        assert_eq!(weight(&g, "rome", "milan"), Some(4));
+        assert_eq!(weight(&g, "milan", "rome"), Some(1));
+        assert_eq!(weight(&g, "turin", "genoa"), Some(3));
+        assert_eq!(g.validate(), Ok(()));
+
+        for (policy, expected) in [
+            (EdgeConflictPolicy::Sum, 10),
+            (EdgeConflictPolicy::Overwrite, 4),
+        ] {
+            let mut g = base();
+            g.merge(other(), policy);
+            assert_eq!(weight(&g, "rome", "milan"), Some(expected));
+            assert_eq!(weight(&g, "milan", "turin"), Some(2));
+        }
+
+        let mut g = base();
+        g.merge_with(other(), |&old, new| old.max(new) * 10);
+        assert_eq!(weight(&g, "rome", "milan"), Some(60));
+    }
+
+    #[test]
+    fn test_removed_index_stays_invalid() {
+        let mut g = KeyedGraph::new();
+        let a = g.insert_node('a', ());
+        let b = g.insert_node('b', ());
+        g.add_edge(a, b);
+        g.add_edge(b, a);
+
+        assert_eq!(g.remove_node(a), Some(('a', ())));
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.node_index(&'a'), None);
+
+        let c = g.insert_node('c', ());
+        assert_eq!(c.slot(), a.slot());
+        assert_eq!(g.payload(a), None);
+        g.add_edge(b, a);
+        assert!(!g.has_edge(b, c));
+        assert_eq!(g.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::format::GraphFormatter;
+use crate::graph::{EdgeIterator, GraphRead, NodeIterator};
+use crate::io::{
+    binary::{check_offsets, read_header, BinaryWeight, Header},
+    IoError,
+};
+use memmap2::Mmap;
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    fs::File,
+    ops::Range,
+    path::Path,
+};
+
+/**
+ * Read-only graph backed by a memory-mapped binary snapshot written with
+ * [`BinaryWriter::csr`](crate::io::binary::BinaryWriter::csr).
+ *
+ * Opening the file maps it and checks the offsets of each node, so only the pages
+ * that queries touch are read from disk and graphs larger than the available memory
+ * can be traversed. Nodes are the positions `0..node_count` of the snapshot, and
+ * [`GraphRead::has_edge`] and [`edge_weight`](MmapCsrGraph::edge_weight) are binary
+ * searches among the sorted targets of the source.
+ *
+ * The file must not be modified while it is open.
+ */
+pub struct MmapCsrGraph {
+    map: Mmap,
+    header: Header,
+    offsets_at: usize,
+    targets_at: usize,
+    record_size: usize,
+}
+
+impl MmapCsrGraph {
+    /**
+     * Maps the snapshot at `path`, which must use the CSR layout.
+     */
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, IoError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is only read, and the file is documented not to change while open
+        let map = unsafe { Mmap::map(&file)? };
+
+        let mut rest = &map[..];
+        let header = read_header(&mut rest)?;
+        if !header.csr {
+            return Err(IoError::Format(
+                "the snapshot does not use the CSR layout".to_string(),
+            ));
+        }
+        let offsets_at = map.len() - rest.len();
+        let record_size = 4 + header.weight.map_or(0, |w| w.size());
+        let targets_at = (header.node_count as usize + 1)
+            .checked_mul(8)
+            .and_then(|len| len.checked_add(offsets_at));
+        let len = usize::try_from(header.edge_count)
+            .ok()
+            .and_then(|m| m.checked_mul(record_size))
+            .zip(targets_at)
+            .and_then(|(len, at)| len.checked_add(at));
+        let (Some(targets_at), Some(len)) = (targets_at, len) else {
+            return Err(IoError::Format("invalid counts".to_string()));
+        };
+        if map.len() != len {
+            return Err(IoError::Format(format!(
+                "expected {} bytes, found {}",
+                len,
+                map.len()
+            )));
+        }
+
+        let graph = MmapCsrGraph {
+            map,
+            header,
+            offsets_at,
+            targets_at,
+            record_size,
+        };
+        check_offsets(
+            (0..=graph.header.node_count as usize).map(|i| graph.offset(i)),
+            header.edge_count,
+        )?;
+        Ok(graph)
+    }
+
+    /**
+     * Returns the header of the snapshot.
+     */
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /**
+     * Returns the weight of an edge, if the edge is in the graph.
+     *
+     * # Panics
+     *
+     * Panics if `W` is not the weight type of the snapshot.
+     */
+    pub fn edge_weight<W: BinaryWeight>(&self, f: u32, t: u32) -> Option<W> {
+        assert_eq!(
+            self.header.weight,
+            Some(W::TYPE),
+            "weight type of the snapshot"
+        );
+        let row = self.row(f)?;
+        let pos = self.find(row, t)?;
+        let mut bytes = &self.map[self.record(pos) + 4..];
+        Some(W::read_le(&mut bytes).expect("record inside the map"))
+    }
+
+    fn offset(&self, i: usize) -> u64 {
+        let at = self.offsets_at + i * 8;
+        u64::from_le_bytes(self.map[at..at + 8].try_into().unwrap())
+    }
+
+    fn record(&self, i: usize) -> usize {
+        self.targets_at + i * self.record_size
+    }
+
+    fn target(&self, i: usize) -> u32 {
+        let at = self.record(i);
+        u32::from_le_bytes(self.map[at..at + 4].try_into().unwrap())
+    }
+
+    /**
+     * Returns the positions of the records of the edges leaving `n`.
+     */
+    fn row(&self, n: u32) -> Option<Range<usize>> {
+        let n = n as usize;
+        if n as u64 >= self.header.node_count {
+            return None;
+        }
+        Some(self.offset(n) as usize..self.offset(n + 1) as usize)
+    }
+
+    fn find(&self, row: Range<usize>, t: u32) -> Option<usize> {
+        let (mut lo, mut hi) = (row.start, row.end);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.target(mid).cmp(&t) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+}
+
+impl GraphRead for MmapCsrGraph {
+    type Index = u32;
+
+    fn has_edge(&self, f: u32, t: u32) -> bool {
+        self.row(f).and_then(|row| self.find(row, t)).is_some()
+    }
+
+    fn node_count(&self) -> usize {
+        self.header.node_count as usize
+    }
+
+    fn edge_count(&self) -> usize {
+        self.header.edge_count as usize
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, u32>> {
+        Box::new(0..self.header.node_count as u32)
+    }
+
+    fn iter_adj(&self, n: u32) -> Option<Box<NodeIterator<'_, u32>>> {
+        let row = self.row(n)?;
+        Some(Box::new(row.map(|i| self.target(i))))
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, u32>> {
+        Box::new(self.iter_nodes().flat_map(move |f| {
+            self.row(f)
+                .into_iter()
+                .flatten()
+                .map(move |i| (f, self.target(i)))
+        }))
+    }
+}
+
+impl Display for MmapCsrGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, WeightedGraph},
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+        io::binary::{load_weighted_binary, save_binary, BinaryWriter},
+    };
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rust_graph_{}_{}.bin", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_open() {
+        let mut g = UndirectedWeightedGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[2], ids[0], 1.5);
+        g.add_weighted_edge(ids[0], ids[1], 2.0);
+        g.add_weighted_edge(ids[1], ids[1], 0.5);
+
+        let path = temp_path("open");
+        let mut out = Vec::new();
+        BinaryWriter::new(&g)
+            .undirected(true)
+            .csr(true)
+            .write_weighted(&mut out)
+            .unwrap();
+        std::fs::write(&path, &out).unwrap();
+
+        let m = MmapCsrGraph::open(&path).unwrap();
+        assert!(m.header().csr && !m.header().undirected);
+        assert_eq!((m.node_count(), m.edge_count()), (4, 5));
+        assert_eq!(m.iter_adj(0).unwrap().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(m.has_edge(2, 0) && m.has_edge(1, 1) && !m.has_edge(2, 1));
+        assert_eq!(m.iter_adj(3).unwrap().count(), 0);
+        assert!(m.iter_adj(4).is_none());
+        assert_eq!(m.edge_weight::<f64>(1, 0), Some(2.0));
+        assert_eq!(m.edge_weight::<f64>(3, 0), None);
+        assert_eq!(m.iter_edges().count(), 5);
+
+        let loaded: UndirectedWeightedGraph<f64> = load_weighted_binary(out.as_slice()).unwrap();
+        assert_eq!(loaded.edge_count(), 3);
+        assert_eq!(loaded.edge_weight(ids[0], ids[2]), Some(1.5));
+        drop(m);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let mut g = AdjListGraph::new();
+        let a = g.add_node();
+        g.add_edge(a, a);
+
+        let path = temp_path("invalid");
+        let mut out = Vec::new();
+        save_binary(&g, &mut out).unwrap();
+        std::fs::write(&path, &out).unwrap();
+        assert!(matches!(MmapCsrGraph::open(&path), Err(IoError::Format(_))));
+
+        out.clear();
+        BinaryWriter::new(&g).csr(true).write(&mut out).unwrap();
+        out.pop();
+        std::fs::write(&path, &out).unwrap();
+        assert!(matches!(MmapCsrGraph::open(&path), Err(IoError::Format(_))));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            MmapCsrGraph::open(temp_path("missing")),
+            Err(IoError::Io(_))
+        ));
+    }
+}
@@ -1 +1,19 @@
 pub mod adj_list;
+pub mod adj_vec;
+mod collections;
+pub mod concurrent;
+pub mod edge_slice;
+pub mod frozen;
+pub mod grid;
+pub mod hierarchical;
+pub mod implicit;
+pub mod inc_matrix;
+pub mod keyed;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod multi;
+#[cfg(feature = "persistent")]
+pub mod persistent;
+pub mod temporal;
+pub mod typed;
+pub mod undirected_weighted;
@@ -0,0 +1,395 @@
+use super::adj_list::Index;
+use crate::format::GraphFormatter;
+use crate::graph::{Direction, EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator};
+use crate::invariant::InvariantViolation;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+/**
+ * Direction of an edge of a [`MultiGraph`].
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum EdgeKind {
+    /**
+     * Traversable only from its source to its target, like a one-way street.
+     */
+    Directed,
+    /**
+     * Traversable in both directions, like a two-way street.
+     */
+    Undirected,
+}
+
+/**
+ * Identifier of an edge of a [`MultiGraph`].
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+pub struct EdgeId(usize);
+
+impl Display for EdgeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "e{}", self.0)
+    }
+}
+
+struct EdgeData {
+    from: Index,
+    to: Index,
+    kind: EdgeKind,
+}
+
+impl EdgeData {
+    /**
+     * Returns the `(from, to)` pairs along which the edge can be traversed, both
+     * directions for an undirected edge that is not a loop.
+     */
+    fn hops(&self) -> impl Iterator<Item = (Index, Index)> {
+        let back = (self.kind == EdgeKind::Undirected && self.from != self.to)
+            .then_some((self.to, self.from));
+        std::iter::once((self.from, self.to)).chain(back)
+    }
+
+    /**
+     * Returns the node reached from `n` along the edge in the given direction
+     * (`true` for outgoing), if the edge can be traversed that way.
+     */
+    fn neighbor(&self, n: Index, outgoing: bool) -> Option<Index> {
+        let (near, far) = if outgoing {
+            (self.from, self.to)
+        } else {
+            (self.to, self.from)
+        };
+        match self.kind {
+            EdgeKind::Directed => (near == n).then_some(far),
+            EdgeKind::Undirected if self.from == n => Some(self.to),
+            EdgeKind::Undirected => (self.to == n).then_some(self.from),
+        }
+    }
+}
+
+/**
+ * Graph allowing parallel edges, each either directed or undirected, for modeling
+ * networks that mix both, such as roads with one-way and two-way streets.
+ *
+ * As a [`GraphRead`], a node is adjacent to the nodes reachable along one of its
+ * edges, once per edge, and each undirected edge that is not a loop is counted and
+ * listed by [`GraphRead::iter_edges`] in both directions. Edges added through
+ * [`Graph::add_edge`] are directed.
+ */
+pub struct MultiGraph {
+    edges: HashMap<EdgeId, EdgeData>,
+    incident: HashMap<Index, Vec<EdgeId>>,
+    next_node: Index,
+    next_edge: usize,
+}
+
+impl MultiGraph {
+    /**
+     * Creates a new graph.
+     */
+    pub fn new() -> Self {
+        MultiGraph {
+            edges: HashMap::new(),
+            incident: HashMap::new(),
+            next_node: Index(1),
+            next_edge: 0,
+        }
+    }
+
+    /**
+     * Adds an edge of the given kind, even if the nodes are already connected,
+     * returning its id, or `None` if either node is not in the graph.
+     */
+    pub fn add_edge_of_kind(&mut self, f: Index, t: Index, kind: EdgeKind) -> Option<EdgeId> {
+        if !self.incident.contains_key(&f) || !self.incident.contains_key(&t) {
+            return None;
+        }
+
+        let id = EdgeId(self.next_edge);
+        self.next_edge += 1;
+        self.edges.insert(
+            id,
+            EdgeData {
+                from: f,
+                to: t,
+                kind,
+            },
+        );
+        self.incident.get_mut(&f).unwrap().push(id);
+        if f != t {
+            self.incident.get_mut(&t).unwrap().push(id);
+        }
+        Some(id)
+    }
+
+    /**
+     * Returns the source, target and kind of an edge.
+     */
+    pub fn edge(&self, id: EdgeId) -> Option<(Index, Index, EdgeKind)> {
+        self.edges.get(&id).map(|e| (e.from, e.to, e.kind))
+    }
+
+    /**
+     * Removes an edge by id, returning its source, target and kind.
+     */
+    pub fn remove_edge_by_id(&mut self, id: EdgeId) -> Option<(Index, Index, EdgeKind)> {
+        let e = self.edges.remove(&id)?;
+        for n in [e.from, e.to] {
+            if let Some(incident) = self.incident.get_mut(&n) {
+                incident.retain(|&i| i != id);
+            }
+        }
+        Some((e.from, e.to, e.kind))
+    }
+
+    /**
+     * Iterates over the ids of the edges that can be traversed from `f` to `t`.
+     */
+    pub fn edges_between(&self, f: Index, t: Index) -> impl Iterator<Item = EdgeId> + '_ {
+        let incident = self.incident.get(&f).into_iter().flatten();
+        incident
+            .copied()
+            .filter(move |id| self.edges[id].neighbor(f, true) == Some(t))
+    }
+
+    fn neighbors<P>(
+        &self,
+        n: Index,
+        outgoing: bool,
+        keep: P,
+    ) -> Option<Box<NodeIterator<'_, Index>>>
+    where
+        P: Fn(EdgeKind) -> bool + 'static,
+    {
+        let incident = self.incident.get(&n)?;
+        let it = incident.iter().filter_map(move |id| {
+            let e = &self.edges[id];
+            if keep(e.kind) {
+                e.neighbor(n, outgoing)
+            } else {
+                None
+            }
+        });
+        Some(Box::new(it))
+    }
+
+    /**
+     * Iterates over the nodes reachable from `n` along one of its edges, once per edge.
+     */
+    pub fn iter_out(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.neighbors(n, true, |_| true)
+    }
+
+    /**
+     * Iterates over the nodes from which `n` is reachable along one of their edges, once per edge.
+     */
+    pub fn iter_in(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.neighbors(n, false, |_| true)
+    }
+
+    /**
+     * Iterates over the nodes joined to `n` by an undirected edge, once per edge.
+     */
+    pub fn iter_undirected(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.neighbors(n, true, |kind| kind == EdgeKind::Undirected)
+    }
+
+    /**
+     * Checks the internal bookkeeping: that edges only reference nodes in the graph
+     * and that each edge is listed by its endpoints, and only by them.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        for (id, e) in &self.edges {
+            if !self.incident.contains_key(&e.from) || !self.incident.contains_key(&e.to) {
+                return Err(InvariantViolation::DanglingEdge(format!(
+                    "{}: {} -> {}",
+                    id, e.from, e.to
+                )));
+            }
+        }
+
+        let mut listed = 0;
+        for (n, incident) in &self.incident {
+            for id in incident {
+                match self.edges.get(id) {
+                    Some(e) if e.from == *n || e.to == *n => listed += 1,
+                    _ => {
+                        return Err(InvariantViolation::Mapping(format!(
+                            "edge {} is listed by node {}",
+                            id, n
+                        )))
+                    }
+                }
+            }
+        }
+        let expected = self
+            .edges
+            .values()
+            .map(|e| if e.from == e.to { 1 } else { 2 })
+            .sum();
+        if listed != expected {
+            return Err(InvariantViolation::Mapping(format!(
+                "{} incident entries for {} expected",
+                listed, expected
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for MultiGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphRead for MultiGraph {
+    type Index = Index;
+
+    fn node_count(&self) -> usize {
+        self.incident.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.values().map(|e| e.hops().count()).sum()
+    }
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.edges_between(f, t).next().is_some()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        Box::new(self.incident.keys().copied())
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.iter_out(n)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        Box::new(self.edges.values().flat_map(EdgeData::hops))
+    }
+
+    fn iter_incident(&self, n: Index) -> Box<IncidentIterator<'_, Index>> {
+        let incident = self.incident.get(&n).into_iter().flatten();
+        Box::new(incident.flat_map(move |id| {
+            self.edges[id].hops().map(move |(f, t)| {
+                let direction = if f == n {
+                    Direction::Outgoing
+                } else {
+                    Direction::Incoming
+                };
+                (f, t, direction)
+            })
+        }))
+    }
+}
+
+impl Graph for MultiGraph {
+    fn add_node(&mut self) -> Index {
+        let id = self.next_node;
+        self.next_node = self.next_node.next();
+        self.incident.insert(id, Vec::new());
+        id
+    }
+
+    fn add_edge(&mut self, f: Index, t: Index) {
+        self.add_edge_of_kind(f, t, EdgeKind::Directed);
+    }
+
+    fn remove_node(&mut self, n: Index) {
+        for id in self.incident.remove(&n).into_iter().flatten() {
+            self.remove_edge_by_id(id);
+        }
+    }
+
+    /**
+     * Removes all the edges that can be traversed from `f` to `t`.
+     */
+    fn remove_edge(&mut self, f: Index, t: Index) {
+        let ids: Vec<_> = self.edges_between(f, t).collect();
+        for id in ids {
+            self.remove_edge_by_id(id);
+        }
+    }
+}
+
+impl Display for MultiGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(it: Option<Box<NodeIterator<'_, Index>>>) -> Vec<Index> {
+        let mut v: Vec<_> = it.unwrap().collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn test_mixed_streets() {
+        let mut g = MultiGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        let one_way = g.add_edge_of_kind(a, b, EdgeKind::Directed).unwrap();
+        g.add_edge_of_kind(b, c, EdgeKind::Undirected);
+        g.add_edge_of_kind(b, c, EdgeKind::Directed);
+
+        assert_eq!(g.edge_count(), 4);
+        assert!(g.has_edge(a, b) && !g.has_edge(b, a));
+        assert!(g.has_edge(c, b));
+        assert_eq!(sorted(g.iter_out(b)), vec![c, c]);
+        assert_eq!(sorted(g.iter_in(b)), vec![a, c]);
+        assert_eq!(sorted(g.iter_undirected(c)), vec![b]);
+        assert_eq!(g.edges_between(b, c).count(), 2);
+        assert_eq!(g.edge(one_way), Some((a, b, EdgeKind::Directed)));
+        assert_eq!(g.add_edge_of_kind(a, Index(9), EdgeKind::Directed), None);
+        assert_eq!(g.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_edges_match_adjacency() {
+        let mut g = MultiGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_edge_of_kind(a, b, EdgeKind::Undirected);
+        g.add_edge_of_kind(b, c, EdgeKind::Directed);
+        g.add_edge_of_kind(b, c, EdgeKind::Undirected);
+        g.add_edge_of_kind(c, c, EdgeKind::Undirected);
+
+        let mut edges: Vec<_> = g.iter_edges().collect();
+        edges.sort();
+        let mut adj: Vec<_> = g
+            .iter_nodes()
+            .flat_map(|n| g.iter_adj(n).unwrap().map(move |m| (n, m)))
+            .collect();
+        adj.sort();
+        assert_eq!(edges, adj);
+        assert_eq!(g.edge_count(), edges.len());
+        assert!(edges.iter().all(|&(f, t)| g.has_edge(f, t)));
+        assert_eq!(edges, vec![(a, b), (b, a), (b, c), (b, c), (c, b), (c, c)]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut g = MultiGraph::new();
+        let [a, b] = [(); 2].map(|_| g.add_node());
+        g.add_edge_of_kind(a, b, EdgeKind::Undirected);
+        g.add_edge(b, a);
+        g.add_edge(a, a);
+
+        g.remove_edge(b, a);
+        assert_eq!(g.edge_count(), 1);
+        assert!(g.has_edge(a, a));
+
+        g.add_edge(b, a);
+        g.remove_node(a);
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.iter_out(b).unwrap().count(), 0);
+        assert_eq!(g.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,265 @@
+use super::adj_list::Index;
+use crate::format::GraphFormatter;
+use crate::graph::{Direction, EdgeIterator, GraphRead, IncidentIterator, NodeIterator};
+use crate::invariant::InvariantViolation;
+use im::{OrdMap, OrdSet};
+use std::fmt::{Display, Formatter};
+
+/**
+ * Immutable graph whose updates return a new graph sharing most of its structure
+ * with the old one, for backtracking searches and versioned documents that keep
+ * many versions of a graph around.
+ *
+ * Adjacency is stored in persistent balanced trees, so cloning takes constant time
+ * and each update copies only `O(log n)` tree nodes, leaving every other version
+ * untouched. Incoming edges are indexed too, to remove nodes without scanning the
+ * graph. Nodes, neighbors and edges are iterated in ascending order.
+ *
+ * ```
+ * use rust_graph_lib::{graph::GraphRead, impls::persistent::PersistentGraph};
+ *
+ * let (g, a) = PersistentGraph::new().add_node();
+ * let (g, b) = g.add_node();
+ * let with_edge = g.add_edge(a, b);
+ * assert!(with_edge.has_edge(a, b));
+ * assert!(!g.has_edge(a, b));
+ * ```
+ */
+#[derive(Clone)]
+pub struct PersistentGraph {
+    outgoing: OrdMap<Index, OrdSet<Index>>,
+    incoming: OrdMap<Index, OrdSet<Index>>,
+    edge_count: usize,
+    next_id: Index,
+}
+
+impl PersistentGraph {
+    /**
+     * Creates a new graph.
+     */
+    pub fn new() -> Self {
+        PersistentGraph {
+            outgoing: OrdMap::new(),
+            incoming: OrdMap::new(),
+            edge_count: 0,
+            next_id: Index(1),
+        }
+    }
+
+    /**
+     * Returns a graph with a new node, along with its index. Indices are never reused.
+     */
+    pub fn add_node(&self) -> (Self, Index) {
+        let mut g = self.clone();
+        let id = g.next_id;
+        g.next_id = id.next();
+        g.outgoing.insert(id, OrdSet::new());
+        g.incoming.insert(id, OrdSet::new());
+        (g, id)
+    }
+
+    /**
+     * Returns a graph with the edge `(f, t)` added, or the same graph if the edge is
+     * already in it or either node is not.
+     */
+    pub fn add_edge(&self, f: Index, t: Index) -> Self {
+        let mut g = self.clone();
+        if !g.incoming.contains_key(&t) {
+            return g;
+        }
+        if let Some(adj) = g.outgoing.get_mut(&f) {
+            if adj.insert(t).is_none() {
+                g.incoming.get_mut(&t).unwrap().insert(f);
+                g.edge_count += 1;
+            }
+        }
+        g
+    }
+
+    /**
+     * Returns a graph without the edge `(f, t)`.
+     */
+    pub fn remove_edge(&self, f: Index, t: Index) -> Self {
+        let mut g = self.clone();
+        if let Some(adj) = g.outgoing.get_mut(&f) {
+            if adj.remove(&t).is_some() {
+                g.incoming.get_mut(&t).unwrap().remove(&f);
+                g.edge_count -= 1;
+            }
+        }
+        g
+    }
+
+    /**
+     * Returns a graph without the node `n` and its edges, taking time proportional
+     * to the edges of `n` only.
+     */
+    pub fn remove_node(&self, n: Index) -> Self {
+        let mut g = self.clone();
+        let (Some(outgoing), Some(incoming)) = (g.outgoing.remove(&n), g.incoming.remove(&n))
+        else {
+            return g;
+        };
+        for t in outgoing.iter().filter(|&&t| t != n) {
+            g.incoming.get_mut(t).unwrap().remove(&n);
+        }
+        for f in incoming.iter().filter(|&&f| f != n) {
+            g.outgoing.get_mut(f).unwrap().remove(&n);
+        }
+        let loops = usize::from(outgoing.contains(&n));
+        g.edge_count -= outgoing.len() + incoming.len() - loops;
+        g
+    }
+
+    /**
+     * Checks the internal bookkeeping: the edge count, that edges only reference
+     * nodes in the graph and that the incoming edges mirror the outgoing ones.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let actual = self.outgoing.values().map(OrdSet::len).sum();
+        if actual != self.edge_count {
+            return Err(InvariantViolation::EdgeCount {
+                stored: self.edge_count,
+                actual,
+            });
+        }
+        if self.incoming.len() != self.outgoing.len() {
+            return Err(InvariantViolation::Mapping(format!(
+                "{} nodes with incoming edges for {} nodes",
+                self.incoming.len(),
+                self.outgoing.len()
+            )));
+        }
+        for (f, t) in self.iter_edges() {
+            if !self.incoming.get(&t).is_some_and(|inc| inc.contains(&f)) {
+                return Err(InvariantViolation::DanglingEdge(format!("{} -> {}", f, t)));
+            }
+        }
+        let mirrored: usize = self.incoming.values().map(OrdSet::len).sum();
+        if mirrored != actual {
+            return Err(InvariantViolation::Mapping(format!(
+                "{} incoming edges for {} edges",
+                mirrored, actual
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for PersistentGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphRead for PersistentGraph {
+    type Index = Index;
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.outgoing.get(&f).is_some_and(|adj| adj.contains(&t))
+    }
+
+    fn node_count(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        Box::new(self.outgoing.keys().copied())
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.outgoing.get(&n).map(|adj| {
+            let it: Box<NodeIterator<'_, Index>> = Box::new(adj.iter().copied());
+            it
+        })
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        let it = self
+            .outgoing
+            .iter()
+            .flat_map(|(&f, adj)| adj.iter().map(move |&t| (f, t)));
+        Box::new(it)
+    }
+
+    fn iter_incident(&self, n: Index) -> Box<IncidentIterator<'_, Index>> {
+        let (Some(outgoing), Some(incoming)) = (self.outgoing.get(&n), self.incoming.get(&n))
+        else {
+            return Box::new(std::iter::empty());
+        };
+        let outgoing = outgoing.iter().map(move |&t| (n, t, Direction::Outgoing));
+        let incoming = incoming
+            .iter()
+            .filter(move |&&f| f != n)
+            .map(move |&f| (f, n, Direction::Incoming));
+        Box::new(outgoing.chain(incoming))
+    }
+}
+
+impl Display for PersistentGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_independent() {
+        let mut g = PersistentGraph::new();
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            let (next, id) = g.add_node();
+            g = next;
+            ids.push(id);
+        }
+        let base = g.add_edge(ids[0], ids[1]).add_edge(ids[1], ids[2]);
+
+        // branch off two versions, as a backtracking search would
+        let left = base.add_edge(ids[2], ids[3]).add_edge(ids[3], ids[3]);
+        let right = base.remove_node(ids[1]);
+        assert_eq!((base.node_count(), base.edge_count()), (4, 2));
+        assert_eq!((left.node_count(), left.edge_count()), (4, 4));
+        assert_eq!((right.node_count(), right.edge_count()), (3, 0));
+        assert!(base.has_edge(ids[1], ids[2]) && !base.has_edge(ids[2], ids[3]));
+        assert_eq!(
+            left.iter_edges().collect::<Vec<_>>(),
+            vec![
+                (ids[0], ids[1]),
+                (ids[1], ids[2]),
+                (ids[2], ids[3]),
+                (ids[3], ids[3]),
+            ]
+        );
+
+        let trimmed = left.remove_node(ids[3]);
+        assert_eq!(trimmed.edge_count(), 2);
+        assert_eq!(left.remove_edge(ids[1], ids[2]).edge_count(), 3);
+        assert_eq!(
+            left.iter_incident(ids[3]).collect::<Vec<_>>(),
+            vec![
+                (ids[3], ids[3], Direction::Outgoing),
+                (ids[2], ids[3], Direction::Incoming),
+            ]
+        );
+        for g in [&base, &left, &right, &trimmed] {
+            assert_eq!(g.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_missing_nodes() {
+        let (g, a) = PersistentGraph::new().add_node();
+        let missing = a.next();
+        assert_eq!(g.add_edge(a, missing).edge_count(), 0);
+        assert_eq!(g.add_edge(missing, a).edge_count(), 0);
+        assert_eq!(g.remove_node(missing).node_count(), 1);
+        assert_eq!(g.iter_incident(missing).count(), 0);
+    }
+}
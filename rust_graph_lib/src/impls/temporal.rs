@@ -0,0 +1,263 @@
+use crate::graph::{EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator};
+use crate::invariant::InvariantViolation;
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+/**
+ * Wrapper around a [`Graph`] whose edges can carry a validity interval
+ * `[start, end)`, for modeling evolving networks.
+ *
+ * Edges without an interval are always valid. The state of the graph at a given
+ * time is available through [`TemporalGraph::snapshot_at`], which any algorithm can run on.
+ */
+pub struct TemporalGraph<G: Graph, T> {
+    graph: G,
+    validity: HashMap<(G::Index, G::Index), (T, T)>,
+}
+
+impl<G, T> TemporalGraph<G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Ord + Copy,
+{
+    /**
+     * Wraps the graph; its existing edges are always valid.
+     */
+    pub fn new(graph: G) -> Self {
+        TemporalGraph {
+            graph,
+            validity: HashMap::new(),
+        }
+    }
+
+    /**
+     * Checks that each validity interval belongs to an edge of the graph.
+     * The inner graph is not checked.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation>
+    where
+        G::Index: Debug,
+    {
+        match self
+            .validity
+            .keys()
+            .find(|&&(f, t)| !self.graph.has_edge(f, t))
+        {
+            Some((f, t)) => Err(InvariantViolation::Mapping(format!(
+                "interval for missing edge {:?} -> {:?}",
+                f, t
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /**
+     * Adds an edge valid from `start` (inclusive) to `end` (exclusive).
+     */
+    pub fn add_temporal_edge(&mut self, f: G::Index, t: G::Index, start: T, end: T) {
+        self.graph.add_edge(f, t);
+        self.validity.insert((f, t), (start, end));
+    }
+
+    /**
+     * Returns the validity interval of an edge, or `None` if it is always valid or not in the graph.
+     */
+    pub fn validity(&self, f: G::Index, t: G::Index) -> Option<(T, T)> {
+        self.validity.get(&(f, t)).copied()
+    }
+
+    /**
+     * Checks if an edge is in the graph and valid at the given time.
+     */
+    pub fn is_valid_at(&self, f: G::Index, t: G::Index, time: T) -> bool {
+        self.graph.has_edge(f, t)
+            && self
+                .validity
+                .get(&(f, t))
+                .is_none_or(|&(start, end)| start <= time && time < end)
+    }
+
+    /**
+     * Returns a read-only view of the graph containing only the edges valid at the given time.
+     */
+    pub fn snapshot_at(&self, time: T) -> TemporalSnapshot<'_, G, T> {
+        TemporalSnapshot { graph: self, time }
+    }
+
+    /**
+     * Returns the wrapped graph, including all edges regardless of their validity.
+     */
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+}
+
+impl<G, T> GraphRead for TemporalGraph<G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Ord + Copy,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.has_edge(f, t)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        self.graph.iter_adj(n)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        self.graph.iter_edges()
+    }
+
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>> {
+        self.graph.iter_incident(n)
+    }
+}
+
+impl<G, T> Graph for TemporalGraph<G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Ord + Copy,
+{
+    fn add_node(&mut self) -> Self::Index {
+        self.graph.add_node()
+    }
+
+    /**
+     * Adds an always valid edge, clearing the interval if the edge already had one.
+     */
+    fn add_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.add_edge(f, t);
+        self.validity.remove(&(f, t));
+    }
+
+    fn remove_node(&mut self, n: Self::Index) {
+        self.graph.remove_node(n);
+        self.validity.retain(|&(f, t), _| f != n && t != n);
+    }
+
+    fn remove_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.remove_edge(f, t);
+        self.validity.remove(&(f, t));
+    }
+}
+
+/**
+ * View of a [`TemporalGraph`] at a given time, returned by [`TemporalGraph::snapshot_at`].
+ *
+ * [`GraphRead::edge_count`] has to scan all the edges.
+ */
+pub struct TemporalSnapshot<'a, G: Graph, T> {
+    graph: &'a TemporalGraph<G, T>,
+    time: T,
+}
+
+impl<G, T> GraphRead for TemporalSnapshot<'_, G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Ord + Copy,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.is_valid_at(f, t, self.time)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.iter_edges().count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        self.graph.iter_adj(n).map(|adj| {
+            let it: Box<NodeIterator<'_, Self::Index>> =
+                Box::new(adj.filter(move |&a| self.graph.is_valid_at(n, a, self.time)));
+            it
+        })
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        Box::new(
+            self.graph
+                .iter_edges()
+                .filter(|&(f, t)| self.graph.is_valid_at(f, t, self.time)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{Algorithm, Dijkstra},
+        impls::adj_list::AdjListGraph,
+    };
+
+    #[test]
+    fn test_snapshot() {
+        let mut g = TemporalGraph::new(AdjListGraph::new());
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_temporal_edge(id2, id3, 10, 20);
+
+        let before = g.snapshot_at(5);
+        assert_eq!(before.edge_count(), 1);
+        assert!(!before.has_edge(id2, id3));
+        assert_eq!(before.iter_adj(id2).unwrap().count(), 0);
+
+        let during = g.snapshot_at(10);
+        assert_eq!(during.edge_count(), 2);
+        assert!(during.has_edge(id2, id3));
+
+        assert!(!g.snapshot_at(20).has_edge(id2, id3));
+        assert_eq!(g.validity(id2, id3), Some((10, 20)));
+    }
+
+    #[test]
+    fn test_algorithms_on_snapshot() {
+        let mut g = TemporalGraph::new(AdjListGraph::new());
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_temporal_edge(id1, id3, 0, 5);
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+
+        let early = g.snapshot_at(1);
+        assert_eq!(Dijkstra::new(&early).run(id1, id3), Some(vec![id1, id3]));
+
+        let late = g.snapshot_at(7);
+        assert_eq!(
+            Dijkstra::new(&late).run(id1, id3),
+            Some(vec![id1, id2, id3])
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::graph::{EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator};
+use crate::invariant::InvariantViolation;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+};
+
+/**
+ * Wrapper around a [`Graph`] attaching a type tag (e.g. an enum) to its nodes,
+ * for knowledge-graph-like data where edges connect different kinds of entities.
+ *
+ * Nodes added through [`Graph::add_node`] have no type.
+ */
+pub struct TypedGraph<G: Graph, T> {
+    graph: G,
+    types: HashMap<G::Index, T>,
+    by_type: HashMap<T, HashSet<G::Index>>,
+}
+
+impl<G, T> TypedGraph<G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Hash + Eq + Clone,
+{
+    /**
+     * Wraps the graph; its existing nodes have no type.
+     */
+    pub fn new(graph: G) -> Self {
+        TypedGraph {
+            graph,
+            types: HashMap::new(),
+            by_type: HashMap::new(),
+        }
+    }
+
+    /**
+     * Checks that only nodes in the graph have a type and that the index by type
+     * agrees with the type of each node. The inner graph is not checked.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation>
+    where
+        G::Index: Debug,
+    {
+        if let Some(n) = self
+            .types
+            .keys()
+            .find(|&&n| self.graph.iter_adj(n).is_none())
+        {
+            return Err(InvariantViolation::Mapping(format!(
+                "removed node {:?} has a type",
+                n
+            )));
+        }
+
+        let indexed: usize = self.by_type.values().map(HashSet::len).sum();
+        for (tag, nodes) in &self.by_type {
+            if let Some(n) = nodes.iter().find(|n| self.types.get(n) != Some(tag)) {
+                return Err(InvariantViolation::Mapping(format!(
+                    "node {:?} is indexed under the wrong type",
+                    n
+                )));
+            }
+        }
+        if indexed != self.types.len() {
+            return Err(InvariantViolation::Mapping(format!(
+                "{} typed nodes but {} indexed",
+                self.types.len(),
+                indexed
+            )));
+        }
+        Ok(())
+    }
+
+    /**
+     * Adds a node with the given type.
+     */
+    pub fn add_typed_node(&mut self, tag: T) -> G::Index {
+        let n = self.graph.add_node();
+        self.set_node_type(n, tag);
+        n
+    }
+
+    /**
+     * Sets the type of a node, returning the previous one.
+     */
+    pub fn set_node_type(&mut self, n: G::Index, tag: T) -> Option<T> {
+        let old = self.clear_node_type(n);
+        self.by_type.entry(tag.clone()).or_default().insert(n);
+        self.types.insert(n, tag);
+        old
+    }
+
+    /**
+     * Removes the type of a node, returning it.
+     */
+    pub fn clear_node_type(&mut self, n: G::Index) -> Option<T> {
+        let old = self.types.remove(&n)?;
+        if let Some(nodes) = self.by_type.get_mut(&old) {
+            nodes.remove(&n);
+            if nodes.is_empty() {
+                self.by_type.remove(&old);
+            }
+        }
+        Some(old)
+    }
+
+    /**
+     * Returns the type of a node.
+     */
+    pub fn node_type(&self, n: G::Index) -> Option<&T> {
+        self.types.get(&n)
+    }
+
+    /**
+     * Returns an iterator over the nodes with the given type.
+     */
+    pub fn iter_nodes_of_type<'s>(&'s self, tag: &T) -> Box<NodeIterator<'s, G::Index>> {
+        match self.by_type.get(tag) {
+            Some(nodes) => Box::new(nodes.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    /**
+     * Returns an iterator over the nodes adjacent to `n` having the given type.
+     */
+    pub fn iter_adj_of_type<'s>(
+        &'s self,
+        n: G::Index,
+        tag: &'s T,
+    ) -> Option<Box<NodeIterator<'s, G::Index>>> {
+        self.graph.iter_adj(n).map(|adj| {
+            let it: Box<NodeIterator<'s, G::Index>> =
+                Box::new(adj.filter(move |a| self.types.get(a) == Some(tag)));
+            it
+        })
+    }
+
+    /**
+     * Returns the wrapped graph.
+     */
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+}
+
+impl<G, T> GraphRead for TypedGraph<G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Hash + Eq + Clone,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.has_edge(f, t)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        self.graph.iter_adj(n)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        self.graph.iter_edges()
+    }
+
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>> {
+        self.graph.iter_incident(n)
+    }
+}
+
+impl<G, T> Graph for TypedGraph<G, T>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    T: Hash + Eq + Clone,
+{
+    fn add_node(&mut self) -> Self::Index {
+        self.graph.add_node()
+    }
+
+    fn add_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.add_edge(f, t)
+    }
+
+    fn remove_node(&mut self, n: Self::Index) {
+        self.clear_node_type(n);
+        self.graph.remove_node(n)
+    }
+
+    fn remove_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.remove_edge(f, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    enum Kind {
+        Person,
+        Company,
+    }
+
+    #[test]
+    fn test_iter_by_type() {
+        let mut g = TypedGraph::new(AdjListGraph::new());
+        let alice = g.add_typed_node(Kind::Person);
+        let bob = g.add_typed_node(Kind::Person);
+        let acme = g.add_typed_node(Kind::Company);
+        let untyped = g.add_node();
+
+        g.add_edge(alice, bob);
+        g.add_edge(alice, acme);
+        g.add_edge(alice, untyped);
+
+        let mut people: Vec<_> = g.iter_nodes_of_type(&Kind::Person).collect();
+        people.sort();
+        assert_eq!(people, vec![alice, bob]);
+        assert_eq!(g.node_type(untyped), None);
+
+        let companies: Vec<_> = g.iter_adj_of_type(alice, &Kind::Company).unwrap().collect();
+        assert_eq!(companies, vec![acme]);
+    }
+
+    #[test]
+    fn test_retype_and_remove() {
+        let mut g = TypedGraph::new(AdjListGraph::new());
+        let n = g.add_typed_node(Kind::Person);
+
+        assert_eq!(g.set_node_type(n, Kind::Company), Some(Kind::Person));
+        assert_eq!(g.iter_nodes_of_type(&Kind::Person).count(), 0);
+        assert_eq!(g.iter_nodes_of_type(&Kind::Company).count(), 1);
+
+        g.remove_node(n);
+        assert_eq!(g.node_type(n), None);
+        assert_eq!(g.iter_nodes_of_type(&Kind::Company).count(), 0);
+        assert_eq!(g.validate(), Ok(()));
+    }
+}
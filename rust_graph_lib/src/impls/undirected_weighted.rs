@@ -0,0 +1,450 @@
+use super::adj_list::Index;
+use crate::format::GraphFormatter;
+use crate::graph::{
+    Direction, EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator, WeightedGraph,
+};
+use crate::invariant::InvariantViolation;
+use crate::memory::{hash_table_bytes, MemoryReport, MemoryUsage};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+
+fn key(f: Index, t: Index) -> (Index, Index) {
+    if f <= t {
+        (f, t)
+    } else {
+        (t, f)
+    }
+}
+
+/**
+ * Neighbors of a node: the edges towards larger or equal nodes, with their weight,
+ * and the smaller nodes whose edge is stored with them.
+ */
+struct Neighbors<W> {
+    higher: HashMap<Index, W>,
+    lower: HashSet<Index>,
+}
+
+impl<W> Neighbors<W> {
+    fn new() -> Self {
+        Neighbors {
+            higher: HashMap::new(),
+            lower: HashSet::new(),
+        }
+    }
+}
+
+/**
+ * Undirected weighted implementation of [`Graph`].
+ *
+ * Each edge is stored once, with its weight, by its smaller endpoint, while the larger
+ * endpoint only records the smaller one so that [`GraphRead::iter_adj`] yields every
+ * neighbor; weights thus take half the memory of inserting both directions. An edge
+ * `a - b` is reported by [`GraphRead::has_edge`] and [`WeightedGraph::edge_weight`] in
+ * both directions, is counted once by [`GraphRead::edge_count`] and is yielded once by
+ * [`GraphRead::iter_edges`], as `(min, max)`.
+ *
+ * Edges added through [`Graph::add_edge`] get the default weight.
+ */
+pub struct UndirectedWeightedGraph<W = u32> {
+    adj: HashMap<Index, Neighbors<W>>,
+    edge_count: usize,
+    next_id: Index,
+}
+
+impl<W: Copy + Default> UndirectedWeightedGraph<W> {
+    /**
+     * Creates a new graph.
+     */
+    pub fn new() -> Self {
+        UndirectedWeightedGraph {
+            adj: HashMap::new(),
+            edge_count: 0,
+            next_id: Index(1),
+        }
+    }
+}
+
+impl<W: Copy> UndirectedWeightedGraph<W> {
+    /**
+     * Checks the internal bookkeeping: the edge count, and that each edge joins nodes
+     * in the graph and is recorded by both its endpoints.
+     */
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        for (&n, neighbors) in &self.adj {
+            for &h in neighbors.higher.keys() {
+                let Some(other) = self.adj.get(&h) else {
+                    return Err(InvariantViolation::DanglingEdge(format!("{} - {}", n, h)));
+                };
+                if h < n || (h != n && !other.lower.contains(&n)) {
+                    return Err(InvariantViolation::Mapping(format!(
+                        "edge {} - {} without a matching adjacency",
+                        n, h
+                    )));
+                }
+            }
+            for &l in &neighbors.lower {
+                let Some(other) = self.adj.get(&l) else {
+                    return Err(InvariantViolation::DanglingEdge(format!("{} - {}", l, n)));
+                };
+                if l >= n || !other.higher.contains_key(&n) {
+                    return Err(InvariantViolation::Mapping(format!(
+                        "adjacency {} - {} without a weight",
+                        l, n
+                    )));
+                }
+            }
+        }
+
+        let actual = self.adj.values().map(|n| n.higher.len()).sum();
+        if actual != self.edge_count {
+            return Err(InvariantViolation::EdgeCount {
+                stored: self.edge_count,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    fn weight(&self, f: Index, t: Index) -> Option<&W> {
+        let (a, b) = key(f, t);
+        self.adj.get(&a)?.higher.get(&b)
+    }
+
+    /**
+     * Returns a copy of the graph, with the same indices, whose weights are mapped through `f`.
+     */
+    pub fn map_weights<V, F>(&self, mut f: F) -> UndirectedWeightedGraph<V>
+    where
+        F: FnMut(W) -> V,
+    {
+        let adj = self.adj.iter().map(|(&n, neighbors)| {
+            let higher = neighbors.higher.iter().map(|(&h, &w)| (h, f(w))).collect();
+            let lower = neighbors.lower.clone();
+            (n, Neighbors { higher, lower })
+        });
+        UndirectedWeightedGraph {
+            adj: adj.collect(),
+            edge_count: self.edge_count,
+            next_id: self.next_id,
+        }
+    }
+
+    /**
+     * Replaces each weight with the result of `f`.
+     */
+    pub fn map_weights_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(W) -> W,
+    {
+        for w in self.adj.values_mut().flat_map(|n| n.higher.values_mut()) {
+            *w = f(*w);
+        }
+    }
+
+    /**
+     * Returns a copy of the graph with the weights divided by the largest one, so that
+     * they are all in `[0, 1]` for non-negative weights. All zero weights are left as zero.
+     */
+    pub fn normalize_weights(&self) -> UndirectedWeightedGraph<f64>
+    where
+        W: Into<f64>,
+    {
+        let max = self
+            .adj
+            .values()
+            .flat_map(|n| n.higher.values())
+            .map(|&w| w.into())
+            .fold(0.0, f64::max);
+        if max == 0.0 {
+            return self.map_weights(Into::into);
+        }
+        self.map_weights(|w| w.into() / max)
+    }
+
+    /**
+     * Returns a copy of the graph with each weight replaced by its reciprocal, turning
+     * similarities into distances. Zero weights become [`f64::INFINITY`].
+     */
+    pub fn invert_weights(&self) -> UndirectedWeightedGraph<f64>
+    where
+        W: Into<f64>,
+    {
+        self.map_weights(|w| 1.0 / w.into())
+    }
+}
+
+impl<W: Copy + Default> Default for UndirectedWeightedGraph<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Copy + Default> GraphRead for UndirectedWeightedGraph<W> {
+    type Index = Index;
+
+    fn has_edge(&self, f: Index, t: Index) -> bool {
+        self.weight(f, t).is_some()
+    }
+
+    fn node_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Index>> {
+        Box::new(self.adj.keys().copied())
+    }
+
+    fn iter_adj(&self, n: Index) -> Option<Box<NodeIterator<'_, Index>>> {
+        self.adj.get(&n).map(|neighbors| {
+            let adj = neighbors.higher.keys().chain(&neighbors.lower);
+            let it: Box<NodeIterator<'_, Index>> = Box::new(adj.copied());
+            it
+        })
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Index>> {
+        Box::new(
+            self.adj
+                .iter()
+                .flat_map(|(&n, neighbors)| neighbors.higher.keys().map(move |&h| (n, h))),
+        )
+    }
+
+    fn iter_incident(&self, n: Index) -> Box<IncidentIterator<'_, Index>> {
+        let Some(neighbors) = self.adj.get(&n) else {
+            return Box::new(std::iter::empty());
+        };
+        let outgoing = neighbors
+            .higher
+            .keys()
+            .map(move |&h| (n, h, Direction::Outgoing));
+        let incoming = neighbors
+            .lower
+            .iter()
+            .map(move |&l| (l, n, Direction::Incoming));
+        Box::new(outgoing.chain(incoming))
+    }
+}
+
+impl<W: Copy + Default> Graph for UndirectedWeightedGraph<W> {
+    fn add_node(&mut self) -> Index {
+        let id = self.next_id;
+        self.next_id = self.next_id.next();
+        self.adj.insert(id, Neighbors::new());
+        id
+    }
+
+    fn add_edge(&mut self, f: Index, t: Index) {
+        self.add_weighted_edge(f, t, W::default());
+    }
+
+    fn remove_node(&mut self, n: Index) {
+        if let Some(neighbors) = self.adj.remove(&n) {
+            self.edge_count -= neighbors.higher.len() + neighbors.lower.len();
+            for h in neighbors.higher.keys() {
+                if let Some(other) = self.adj.get_mut(h) {
+                    other.lower.remove(&n);
+                }
+            }
+            for l in &neighbors.lower {
+                if let Some(other) = self.adj.get_mut(l) {
+                    other.higher.remove(&n);
+                }
+            }
+        }
+    }
+
+    fn remove_edge(&mut self, f: Index, t: Index) {
+        let (a, b) = key(f, t);
+        let removed = self.adj.get_mut(&a).and_then(|n| n.higher.remove(&b));
+        if removed.is_some() {
+            self.edge_count -= 1;
+            if let Some(other) = self.adj.get_mut(&b) {
+                other.lower.remove(&a);
+            }
+        }
+    }
+}
+
+impl<W: Copy + Default> WeightedGraph for UndirectedWeightedGraph<W> {
+    type Weight = W;
+
+    fn add_weighted_edge(&mut self, f: Index, t: Index, w: W) {
+        let (a, b) = key(f, t);
+        let higher = &mut self.adj.entry(a).or_insert_with(Neighbors::new).higher;
+        if higher.insert(b, w).is_none() {
+            self.edge_count += 1;
+            if a != b {
+                self.adj
+                    .entry(b)
+                    .or_insert_with(Neighbors::new)
+                    .lower
+                    .insert(a);
+            }
+        }
+    }
+
+    fn edge_weight(&self, f: Index, t: Index) -> Option<W> {
+        self.weight(f, t).copied()
+    }
+
+    fn set_edge_weight(&mut self, f: Index, t: Index, w: W) -> bool {
+        let (a, b) = key(f, t);
+        match self.adj.get_mut(&a).and_then(|n| n.higher.get_mut(&b)) {
+            Some(weight) => {
+                *weight = w;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn in_strength(&self, n: Index) -> Option<W>
+    where
+        W: Sum,
+    {
+        self.out_strength(n)
+    }
+
+    fn strength(&self, n: Index) -> Option<W>
+    where
+        W: Sum,
+    {
+        self.out_strength(n)
+    }
+}
+
+impl<W> MemoryUsage for UndirectedWeightedGraph<W> {
+    fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            nodes: hash_table_bytes::<(Index, Neighbors<W>)>(self.adj.capacity()),
+            adjacency: self
+                .adj
+                .values()
+                .map(|n| {
+                    hash_table_bytes::<(Index, W)>(n.higher.capacity())
+                        + hash_table_bytes::<Index>(n.lower.capacity())
+                })
+                .sum(),
+            mappings: 0,
+            other: std::mem::size_of::<Self>(),
+        }
+    }
+}
+
+impl<W: Copy + Default> Display for UndirectedWeightedGraph<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        GraphFormatter::new(self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges_both_ways() {
+        let mut g = UndirectedWeightedGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_weighted_edge(id2, id1, 5);
+        g.add_weighted_edge(id2, id3, 2);
+        assert_eq!(g.edge_count(), 2);
+        assert!(g.has_edge(id1, id2) && g.has_edge(id2, id1));
+        assert_eq!(g.edge_weight(id1, id2), Some(5));
+        assert_eq!(g.edge_weight(id3, id2), Some(2));
+        assert_eq!(g.edge_weight(id1, id3), None);
+
+        let mut edges: Vec<_> = g.iter_edges().collect();
+        edges.sort();
+        assert_eq!(edges, vec![(id1, id2), (id2, id3)]);
+
+        g.add_weighted_edge(id1, id2, 7);
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(g.edge_weight(id2, id1), Some(7));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut g = UndirectedWeightedGraph::<u32>::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id3);
+        assert_eq!(g.edge_count(), 3);
+
+        g.remove_edge(id2, id1);
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(g.iter_adj(id1).unwrap().count(), 0);
+
+        g.remove_node(id3);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_count(), 0);
+        assert_eq!(g.iter_adj(id2).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut g = UndirectedWeightedGraph::<u32>::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_weighted_edge(id1, id2, 3);
+        g.add_edge(id2, id2);
+        assert_eq!(g.validate(), Ok(()));
+
+        // the edge is stored once, by its smaller endpoint
+        assert!(g.adj[&id1].higher.contains_key(&id2) && g.adj[&id2].higher.contains_key(&id2));
+        assert!(g.adj[&id2].lower.contains(&id1) && g.adj[&id1].lower.is_empty());
+
+        g.adj.get_mut(&id2).unwrap().lower.remove(&id1);
+        assert!(matches!(g.validate(), Err(InvariantViolation::Mapping(_))));
+    }
+
+    #[test]
+    fn test_weight_transforms() {
+        let mut g = UndirectedWeightedGraph::<u32>::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_weighted_edge(id1, id2, 4);
+        g.add_weighted_edge(id2, id3, 2);
+        g.add_weighted_edge(id3, id1, 0);
+
+        let normalized = g.normalize_weights();
+        assert_eq!(normalized.edge_weight(id1, id2), Some(1.0));
+        assert_eq!(normalized.edge_weight(id3, id2), Some(0.5));
+
+        let inverted = g.invert_weights();
+        assert_eq!(inverted.edge_weight(id1, id2), Some(0.25));
+        assert_eq!(inverted.edge_weight(id1, id3), Some(f64::INFINITY));
+        assert_eq!(inverted.node_count(), 3);
+
+        g.map_weights_in_place(|w| w * 10);
+        assert_eq!(g.edge_weight(id2, id3), Some(20));
+        assert_eq!(g.map_weights(|w| w + 1).edge_weight(id1, id3), Some(1));
+    }
+
+    #[test]
+    fn test_strength() {
+        let mut g = UndirectedWeightedGraph::<u32>::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 4);
+        g.add_weighted_edge(ids[2], ids[1], 2);
+
+        assert_eq!(g.strength(ids[1]), Some(6));
+        assert_eq!(g.in_strength(ids[1]), Some(6));
+        assert_eq!(g.out_strength(ids[0]), Some(4));
+        assert_eq!(g.strength(ids[2].next()), None);
+    }
+}
@@ -0,0 +1,44 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/**
+ * Inconsistency in the internal bookkeeping of a graph, found by its `validate` method.
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum InvariantViolation {
+    /**
+     * The stored node count differs from the number of nodes.
+     */
+    NodeCount { stored: usize, actual: usize },
+    /**
+     * The stored edge count differs from the number of edges.
+     */
+    EdgeCount { stored: usize, actual: usize },
+    /**
+     * An edge references a node not in the graph.
+     */
+    DanglingEdge(String),
+    /**
+     * An auxiliary structure (index, weights, type tags...) disagrees with the graph.
+     */
+    Mapping(String),
+}
+
+impl Display for InvariantViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvariantViolation::NodeCount { stored, actual } => {
+                write!(f, "node count is {} but there are {} nodes", stored, actual)
+            }
+            InvariantViolation::EdgeCount { stored, actual } => {
+                write!(f, "edge count is {} but there are {} edges", stored, actual)
+            }
+            InvariantViolation::DanglingEdge(message) => write!(f, "dangling edge: {}", message),
+            InvariantViolation::Mapping(message) => write!(f, "inconsistent mapping: {}", message),
+        }
+    }
+}
+
+impl Error for InvariantViolation {}
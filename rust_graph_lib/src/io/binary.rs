@@ -0,0 +1,685 @@
+use super::IoError;
+use crate::graph::{Graph, GraphRead, WeightedGraph};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    hash::Hash,
+    io::{Read, Write},
+};
+
+/**
+ * Magic number of the snapshots written before the header was introduced, read as version 1.
+ */
+const LEGACY_MAGIC: &[u8; 4] = b"RGBN";
+const MAGIC: &[u8; 4] = b"RGBH";
+
+/**
+ * The newest version of the format, written by this crate.
+ */
+pub const VERSION: u16 = 2;
+
+const UNDIRECTED: u16 = 1;
+const WEIGHTED: u16 = 2;
+const CSR: u16 = 4;
+const KNOWN_FLAGS: u16 = UNDIRECTED | WEIGHTED | CSR;
+
+/**
+ * Encoding of the edge weights of a binary snapshot.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum WeightType {
+    U32,
+    U64,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl WeightType {
+    const ALL: [WeightType; 6] = [
+        WeightType::U32,
+        WeightType::U64,
+        WeightType::I32,
+        WeightType::I64,
+        WeightType::F32,
+        WeightType::F64,
+    ];
+
+    fn tag(self) -> u8 {
+        Self::ALL.iter().position(|&t| t == self).unwrap() as u8 + 1
+    }
+
+    pub(crate) fn size(self) -> usize {
+        match self {
+            WeightType::U32 | WeightType::I32 | WeightType::F32 => 4,
+            WeightType::U64 | WeightType::I64 | WeightType::F64 => 8,
+        }
+    }
+}
+
+impl Display for WeightType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WeightType::U32 => "u32",
+            WeightType::U64 => "u64",
+            WeightType::I32 => "i32",
+            WeightType::I64 => "i64",
+            WeightType::F32 => "f32",
+            WeightType::F64 => "f64",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/**
+ * Edge weight that can be stored in a binary snapshot, as little-endian bytes.
+ */
+pub trait BinaryWeight: Copy {
+    const TYPE: WeightType;
+
+    fn write_le<W: Write>(self, writer: &mut W) -> std::io::Result<()>;
+    fn read_le<R: Read>(reader: &mut R) -> std::io::Result<Self>;
+}
+
+macro_rules! binary_weight {
+    ($($t:ty => $v:ident),*) => {
+        $(impl BinaryWeight for $t {
+            const TYPE: WeightType = WeightType::$v;
+
+            fn write_le<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+
+            fn read_le<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+                let mut buf = [0; std::mem::size_of::<$t>()];
+                reader.read_exact(&mut buf)?;
+                Ok(<$t>::from_le_bytes(buf))
+            }
+        })*
+    };
+}
+
+binary_weight!(u32 => U32, u64 => U64, i32 => I32, i64 => I64, f32 => F32, f64 => F64);
+
+/**
+ * Header of a binary snapshot, returned by [`read_header`].
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Header {
+    /**
+     * Version of the format the snapshot was written with.
+     */
+    pub version: u16,
+    /**
+     * Whether each edge is stored once and stands for both directions.
+     */
+    pub undirected: bool,
+    /**
+     * Whether the edges are grouped by source, in the layout written by
+     * [`BinaryWriter::csr`].
+     */
+    pub csr: bool,
+    /**
+     * Encoding of the edge weights, if the snapshot has any.
+     */
+    pub weight: Option<WeightType>,
+    /**
+     * Number of nodes.
+     */
+    pub node_count: u64,
+    /**
+     * Number of stored edges, each standing for two in undirected snapshots.
+     */
+    pub edge_count: u64,
+}
+
+/**
+ * Writer of binary snapshots, for the options not covered by [`save_binary`].
+ *
+ * The format is a magic number, the version and feature flags as little-endian
+ * `u16`, the weight type as a byte, three reserved bytes, the node and edge counts
+ * as little-endian `u64` and then each edge as a pair of little-endian `u32` node
+ * positions, followed by its weight in weighted snapshots. Wrap the writer in a
+ * [`std::io::BufWriter`] when writing to a file.
+ *
+ * In the layout chosen with [`csr`](BinaryWriter::csr), the counts are followed by
+ * `node_count + 1` little-endian `u64` offsets and then, for each node in turn, its
+ * sorted targets as `u32` positions with their weights; the edges leaving the node
+ * at position `i` are the ones between offsets `i` and `i + 1`.
+ */
+pub struct BinaryWriter<'a, G> {
+    graph: &'a G,
+    undirected: bool,
+    csr: bool,
+}
+
+impl<'a, G> BinaryWriter<'a, G>
+where
+    G: GraphRead,
+    G::Index: Copy + Hash + Eq,
+{
+    /**
+     * Creates a writer of directed snapshots.
+     */
+    pub fn new(graph: &'a G) -> Self {
+        BinaryWriter {
+            graph,
+            undirected: false,
+            csr: false,
+        }
+    }
+
+    /**
+     * Sets whether the graph is undirected, in which case only the edges from a
+     * lower to a higher index (or loops) are written and loading adds both directions.
+     */
+    pub fn undirected(mut self, undirected: bool) -> Self {
+        self.undirected = undirected;
+        self
+    }
+
+    /**
+     * Sets whether the edges are written grouped by source, so that the snapshot can
+     * be queried in place by `MmapCsrGraph`, behind the `mmap` feature.
+     *
+     * The edges of undirected graphs are written in both directions in this layout,
+     * so the snapshot is never marked as undirected.
+     */
+    pub fn csr(mut self, csr: bool) -> Self {
+        self.csr = csr;
+        self
+    }
+
+    /**
+     * Writes an unweighted snapshot.
+     */
+    pub fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.write_edges(writer, None, |_, _, _| Ok(()))
+    }
+
+    /**
+     * Writes a snapshot including the edge weights.
+     */
+    pub fn write_weighted<W: Write>(&self, writer: W) -> std::io::Result<()>
+    where
+        G: WeightedGraph,
+        G::Weight: BinaryWeight,
+    {
+        self.write_edges(writer, Some(G::Weight::TYPE), |w, f, t| {
+            match self.graph.edge_weight(f, t) {
+                Some(weight) => weight.write_le(w),
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "edge without a weight",
+                )),
+            }
+        })
+    }
+
+    fn write_edges<W, F>(
+        &self,
+        mut writer: W,
+        weight: Option<WeightType>,
+        mut write_weight: F,
+    ) -> std::io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut W, G::Index, G::Index) -> std::io::Result<()>,
+    {
+        let graph = self.graph;
+        let mut nodes: Vec<_> = graph.iter_nodes().collect();
+        nodes.sort();
+        if u32::try_from(nodes.len()).is_err() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "too many nodes for the binary format",
+            ));
+        }
+        let positions: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (n, i as u32))
+            .collect();
+        let edges = || {
+            graph
+                .iter_edges()
+                .map(|(f, t)| (f, t, positions[&f], positions[&t]))
+                .filter(|&(_, _, pf, pt)| !self.undirected || pf <= pt)
+        };
+
+        let mut flags = 0;
+        if self.csr {
+            flags |= CSR;
+        } else if self.undirected {
+            flags |= UNDIRECTED;
+        }
+        if weight.is_some() {
+            flags |= WEIGHTED;
+        }
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&flags.to_le_bytes())?;
+        writer.write_all(&[weight.map_or(0, WeightType::tag), 0, 0, 0])?;
+        writer.write_all(&(nodes.len() as u64).to_le_bytes())?;
+
+        if self.csr {
+            let mut sorted: Vec<_> = edges().collect();
+            if self.undirected {
+                let mirrored: Vec<_> = sorted
+                    .iter()
+                    .filter(|&&(_, _, pf, pt)| pf != pt)
+                    .map(|&(f, t, pf, pt)| (t, f, pt, pf))
+                    .collect();
+                sorted.extend(mirrored);
+            }
+            sorted.sort_unstable_by_key(|&(_, _, pf, pt)| (pf, pt));
+            let mut offsets = vec![0u64; nodes.len() + 1];
+            for &(_, _, pf, _) in &sorted {
+                offsets[pf as usize + 1] += 1;
+            }
+            for i in 1..offsets.len() {
+                offsets[i] += offsets[i - 1];
+            }
+
+            writer.write_all(&(sorted.len() as u64).to_le_bytes())?;
+            for offset in offsets {
+                writer.write_all(&offset.to_le_bytes())?;
+            }
+            for (f, t, _, pt) in sorted {
+                writer.write_all(&pt.to_le_bytes())?;
+                write_weight(&mut writer, f, t)?;
+            }
+            return writer.flush();
+        }
+
+        // counted rather than taken from edge_count() so the header always matches the payload
+        writer.write_all(&(edges().count() as u64).to_le_bytes())?;
+
+        for (f, t, pf, pt) in edges() {
+            writer.write_all(&pf.to_le_bytes())?;
+            writer.write_all(&pt.to_le_bytes())?;
+            write_weight(&mut writer, f, t)?;
+        }
+        writer.flush()
+    }
+}
+
+/**
+ * Saves a snapshot of a directed graph in a compact binary format, described in [`BinaryWriter`].
+ */
+pub fn save_binary<G, W>(graph: &G, writer: W) -> std::io::Result<()>
+where
+    G: GraphRead,
+    G::Index: Copy + Hash + Eq,
+    W: Write,
+{
+    BinaryWriter::new(graph).write(writer)
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, IoError> {
+    let mut buf = [0; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/**
+ * Reads the header of a binary snapshot, leaving the reader at the first edge.
+ *
+ * Snapshots of any version up to [`VERSION`] are accepted; newer versions and
+ * unknown features are reported as [`IoError::Format`].
+ */
+pub fn read_header<R: Read>(reader: &mut R) -> Result<Header, IoError> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    let (version, flags, weight) = if &magic == LEGACY_MAGIC {
+        (1, 0, None)
+    } else if &magic == MAGIC {
+        let mut buf = [0; 8];
+        reader.read_exact(&mut buf)?;
+        let version = u16::from_le_bytes([buf[0], buf[1]]);
+        let flags = u16::from_le_bytes([buf[2], buf[3]]);
+        if version > VERSION {
+            return Err(IoError::Format(format!(
+                "version {} is newer than the supported version {}",
+                version, VERSION
+            )));
+        }
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(IoError::Format(format!(
+                "unsupported feature flags {:#06x}",
+                flags & !KNOWN_FLAGS
+            )));
+        }
+        let weight = match (flags & WEIGHTED != 0, buf[4]) {
+            (false, _) => None,
+            (true, tag) => Some(
+                WeightType::ALL
+                    .into_iter()
+                    .find(|t| t.tag() == tag)
+                    .ok_or_else(|| IoError::Format(format!("unknown weight type {}", tag)))?,
+            ),
+        };
+        (version, flags, weight)
+    } else {
+        return Err(IoError::Format("not a binary graph snapshot".to_string()));
+    };
+
+    let node_count = read_u64(reader)?;
+    let edge_count = read_u64(reader)?;
+    if node_count > u32::MAX as u64 {
+        return Err(IoError::Format(format!(
+            "invalid node count {}",
+            node_count
+        )));
+    }
+
+    Ok(Header {
+        version,
+        undirected: flags & UNDIRECTED != 0,
+        csr: flags & CSR != 0,
+        weight,
+        node_count,
+        edge_count,
+    })
+}
+
+/**
+ * Checks that the offsets of a CSR snapshot start at zero, never decrease and end at
+ * the number of edges.
+ */
+pub(crate) fn check_offsets<I>(offsets: I, edge_count: u64) -> Result<(), IoError>
+where
+    I: IntoIterator<Item = u64>,
+{
+    let mut last = None;
+    for offset in offsets {
+        if last.map_or(offset != 0, |last| offset < last) {
+            return Err(IoError::Format("invalid edge offsets".to_string()));
+        }
+        last = Some(offset);
+    }
+    if last != Some(edge_count) {
+        return Err(IoError::Format("invalid edge offsets".to_string()));
+    }
+    Ok(())
+}
+
+/**
+ * Nodes of a snapshot being loaded, created in order as the edges refer to them, so
+ * that the counts in the header are only trusted as far as the input backs them.
+ */
+struct LazyNodes<I> {
+    nodes: Vec<I>,
+    count: u64,
+}
+
+impl<I: Copy> LazyNodes<I> {
+    /**
+     * Returns the node at the position, creating it and the ones before it if needed.
+     */
+    fn get<G: Graph<Index = I>>(&mut self, graph: &mut G, pos: usize) -> Option<I> {
+        if pos as u64 >= self.count {
+            return None;
+        }
+        while self.nodes.len() <= pos {
+            self.nodes.push(graph.add_node());
+        }
+        Some(self.nodes[pos])
+    }
+
+    /**
+     * Creates the nodes without edges after the last one referenced.
+     */
+    fn finish<G: Graph<Index = I>>(mut self, graph: &mut G) {
+        if let Some(last) = self.count.checked_sub(1) {
+            self.get(graph, last as usize);
+        }
+    }
+}
+
+fn load<G, R, F>(mut reader: R, mut add: F) -> Result<G, IoError>
+where
+    G: Graph + Default,
+    G::Index: Copy,
+    R: Read,
+    F: FnMut(&mut G, &mut R, &Header, G::Index, G::Index) -> Result<(), IoError>,
+{
+    let header = read_header(&mut reader)?;
+    let mut graph = G::default();
+    let mut nodes = LazyNodes {
+        nodes: Vec::new(),
+        count: header.node_count,
+    };
+
+    if header.csr {
+        // collected from a fallible iterator, so the vector grows with the input read
+        let offsets = (0..=header.node_count)
+            .map(|_| read_u64(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        check_offsets(offsets.iter().copied(), header.edge_count)?;
+        let mut buf = [0; 4];
+        for i in 0..header.node_count as usize {
+            let f = nodes.get(&mut graph, i).unwrap();
+            for _ in offsets[i]..offsets[i + 1] {
+                reader.read_exact(&mut buf)?;
+                let t = u32::from_le_bytes(buf) as usize;
+                let Some(t) = nodes.get(&mut graph, t) else {
+                    return Err(IoError::Format(format!("edge {} -> {} out of range", i, t)));
+                };
+                add(&mut graph, &mut reader, &header, f, t)?;
+            }
+        }
+        return Ok(graph);
+    }
+
+    let mut buf = [0; 8];
+    for _ in 0..header.edge_count {
+        reader.read_exact(&mut buf)?;
+        let f = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        let t = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+        if f.max(t) as u64 >= header.node_count {
+            return Err(IoError::Format(format!("edge {} -> {} out of range", f, t)));
+        }
+        let (f, t) = (
+            nodes.get(&mut graph, f).unwrap(),
+            nodes.get(&mut graph, t).unwrap(),
+        );
+        add(&mut graph, &mut reader, &header, f, t)?;
+    }
+    nodes.finish(&mut graph);
+
+    Ok(graph)
+}
+
+/**
+ * Loads a graph saved with [`save_binary`] or [`BinaryWriter`], ignoring any weights.
+ *
+ * Nodes are created in ascending order of their original index, so graphs that
+ * assign indices sequentially get back the same numbering if no node was ever removed.
+ * The edges of undirected snapshots are added in both directions.
+ *
+ * Nodes are only created as the edges read refer to them, and the ones without edges
+ * once all the edges have been read, so a header claiming more than the input holds
+ * fails with an error before the nodes are allocated.
+ */
+pub fn load_binary<G, R>(reader: R) -> Result<G, IoError>
+where
+    G: Graph + Default,
+    G::Index: Copy,
+    R: Read,
+{
+    load(reader, |graph: &mut G, reader, header, f, t| {
+        if let Some(weight) = header.weight {
+            std::io::copy(&mut reader.take(weight.size() as u64), &mut std::io::sink())?;
+        }
+        graph.add_edge(f, t);
+        if header.undirected && f != t {
+            graph.add_edge(t, f);
+        }
+        Ok(())
+    })
+}
+
+/**
+ * Loads a weighted graph saved with [`BinaryWriter::write_weighted`]; the weights
+ * in the snapshot must have the same type as those of the graph.
+ */
+pub fn load_weighted_binary<G, R>(reader: R) -> Result<G, IoError>
+where
+    G: WeightedGraph + Default,
+    G::Index: Copy,
+    G::Weight: BinaryWeight,
+    R: Read,
+{
+    load(reader, |graph: &mut G, reader, header, f, t| {
+        match header.weight {
+            Some(weight) if weight == G::Weight::TYPE => {}
+            Some(weight) => {
+                return Err(IoError::Format(format!(
+                    "the snapshot has {} weights, expected {}",
+                    weight,
+                    G::Weight::TYPE
+                )))
+            }
+            None => return Err(IoError::Format("the snapshot has no weights".to_string())),
+        }
+        let w = G::Weight::read_le(reader)?;
+        graph.add_weighted_edge(f, t, w);
+        if header.undirected && f != t {
+            graph.add_weighted_edge(t, f, w);
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph};
+
+    #[test]
+    fn test_roundtrip() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id1);
+
+        let mut out = Vec::new();
+        save_binary(&g, &mut out).unwrap();
+        assert_eq!(out.len(), 12 + 16 + 3 * 8);
+
+        let loaded: AdjListGraph = load_binary(out.as_slice()).unwrap();
+        assert_eq!(loaded.node_count(), 3);
+        assert_eq!(loaded.edge_count(), 3);
+        assert!(loaded.has_edge(id1, id2));
+        assert!(loaded.has_edge(id2, id3));
+        assert!(loaded.has_edge(id3, id1));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let res = load_binary::<AdjListGraph, _>(b"NOPE".as_slice());
+        assert!(matches!(res, Err(IoError::Format(_))));
+
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        g.add_edge(id1, id1);
+        let mut out = Vec::new();
+        save_binary(&g, &mut out).unwrap();
+        out.pop();
+        let res = load_binary::<AdjListGraph, _>(out.as_slice());
+        assert!(matches!(res, Err(IoError::Io(_))));
+    }
+
+    #[test]
+    fn test_oversized_header() {
+        let mut huge = [b"RGBH".as_slice(), &[2, 0, 0, 0, 0, 0, 0, 0]].concat();
+        huge.extend((u32::MAX as u64).to_le_bytes());
+        huge.extend(u64::MAX.to_le_bytes());
+        huge.extend([0, 0, 0, 0, 1, 0, 0, 0]);
+        let res = load_binary::<AdjListGraph, _>(huge.as_slice());
+        assert!(matches!(res, Err(IoError::Io(_))));
+
+        huge[19] = 0x80;
+        let res = load_binary::<AdjListGraph, _>(huge.as_slice());
+        assert!(matches!(res, Err(IoError::Format(m)) if m.contains("node count")));
+
+        let mut csr = [b"RGBH".as_slice(), &[2, 0, 4, 0, 0, 0, 0, 0]].concat();
+        csr.extend((u32::MAX as u64).to_le_bytes());
+        csr.extend(0u64.to_le_bytes());
+        csr.extend(0u64.to_le_bytes());
+        let res = load_binary::<AdjListGraph, _>(csr.as_slice());
+        assert!(matches!(res, Err(IoError::Io(_))));
+    }
+
+    #[test]
+    fn test_weighted_undirected() {
+        let mut g = UndirectedWeightedGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        g.add_weighted_edge(a, b, 1.5);
+        g.add_weighted_edge(c, b, -2.0);
+
+        let mut out = Vec::new();
+        let writer = BinaryWriter::new(&g).undirected(true);
+        writer.write_weighted(&mut out).unwrap();
+        let header = read_header(&mut out.as_slice()).unwrap();
+        assert!(header.undirected);
+        assert_eq!(header.weight, Some(WeightType::F64));
+        assert_eq!(header.edge_count, 2);
+
+        let loaded: UndirectedWeightedGraph<f64> = load_weighted_binary(out.as_slice()).unwrap();
+        assert_eq!(loaded.edge_weight(b, c), Some(-2.0));
+        let directed: AdjListGraph = load_binary(out.as_slice()).unwrap();
+        assert_eq!(directed.edge_count(), 4);
+
+        let res = load_weighted_binary::<UndirectedWeightedGraph<u32>, _>(out.as_slice());
+        assert!(matches!(res, Err(IoError::Format(m)) if m.contains("f64 weights")));
+    }
+
+    #[test]
+    fn test_csr_layout() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_edge(ids[2], ids[0]);
+        g.add_edge(ids[0], ids[2]);
+        g.add_edge(ids[0], ids[1]);
+
+        let mut out = Vec::new();
+        BinaryWriter::new(&g).csr(true).write(&mut out).unwrap();
+        assert_eq!(out.len(), 28 + 4 * 8 + 3 * 4);
+        let header = read_header(&mut out.as_slice()).unwrap();
+        assert!(header.csr && header.weight.is_none());
+
+        let loaded: AdjListGraph = load_binary(out.as_slice()).unwrap();
+        assert_eq!(loaded.edge_count(), 3);
+        assert!(loaded.has_edge(ids[2], ids[0]) && loaded.has_edge(ids[0], ids[1]));
+
+        // offsets ending before the last edge
+        out[28 + 3 * 8] = 2;
+        let res = load_binary::<AdjListGraph, _>(out.as_slice());
+        assert!(matches!(res, Err(IoError::Format(m)) if m.contains("offsets")));
+    }
+
+    #[test]
+    fn test_versions() {
+        let mut legacy = b"RGBN".to_vec();
+        legacy.extend(2u64.to_le_bytes());
+        legacy.extend(1u64.to_le_bytes());
+        legacy.extend([1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(read_header(&mut legacy.as_slice()).unwrap().version, 1);
+        let loaded: AdjListGraph = load_binary(legacy.as_slice()).unwrap();
+        assert_eq!((loaded.node_count(), loaded.edge_count()), (2, 1));
+
+        let newer = [b"RGBH".as_slice(), &[3, 0, 0, 0, 0, 0, 0, 0]].concat();
+        let res = load_binary::<AdjListGraph, _>(newer.as_slice());
+        assert!(matches!(res, Err(IoError::Format(m)) if m.contains("version 3")));
+
+        let unknown = [b"RGBH".as_slice(), &[2, 0, 0, 1, 0, 0, 0, 0]].concat();
+        let res = load_binary::<AdjListGraph, _>(unknown.as_slice());
+        assert!(matches!(res, Err(IoError::Format(_))));
+    }
+}
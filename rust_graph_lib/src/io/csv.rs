@@ -0,0 +1,382 @@
+use super::IoError;
+use crate::graph::{Graph, WeightedGraph};
+use std::{collections::HashMap, hash::Hash, io::Read, str::FromStr};
+
+/**
+ * Column of a CSV file, by header name or by 0-based position.
+ */
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Column {
+    Name(String),
+    Position(usize),
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<usize> for Column {
+    fn from(position: usize) -> Self {
+        Column::Position(position)
+    }
+}
+
+/**
+ * A graph read from a CSV edge list by [`CsvReader`].
+ */
+pub struct CsvGraph<G: Graph> {
+    /**
+     * The graph.
+     */
+    pub graph: G,
+    /**
+     * Maps the node names found in the file to the indices in the graph.
+     */
+    pub nodes: HashMap<String, G::Index>,
+    /**
+     * Edge labels found in the label column, if one was configured.
+     */
+    pub edge_labels: HashMap<(G::Index, G::Index), String>,
+}
+
+/**
+ * Reader of edge lists in CSV format, with one edge per record and configurable columns.
+ *
+ * Fields may be quoted with `"`, in which case they can contain the delimiter, line
+ * breaks and doubled quotes. Nodes are identified by the text of their field, and
+ * created in order of first appearance.
+ *
+ * ```
+ * use rust_graph_lib::{graph::{GraphRead, WeightedGraph}, impls::undirected_weighted::UndirectedWeightedGraph, io::csv::CsvReader};
+ *
+ * let input = "km,from,to\n12,\"Rome, IT\",Naples\n";
+ * let loaded = CsvReader::new()
+ *     .source("from")
+ *     .target("to")
+ *     .weight("km")
+ *     .read_weighted::<UndirectedWeightedGraph<u32>, _>(input.as_bytes())
+ *     .unwrap();
+ * let (rome, naples) = (loaded.nodes["Rome, IT"], loaded.nodes["Naples"]);
+ * assert_eq!(loaded.graph.edge_weight(rome, naples), Some(12));
+ * ```
+ */
+pub struct CsvReader {
+    delimiter: char,
+    has_header: bool,
+    source: Column,
+    target: Column,
+    weight: Option<Column>,
+    label: Option<Column>,
+}
+
+struct Record {
+    fields: Vec<String>,
+    line: usize,
+}
+
+struct Columns {
+    source: usize,
+    target: usize,
+    weight: Option<usize>,
+    label: Option<usize>,
+}
+
+impl CsvReader {
+    /**
+     * Creates a reader of comma-separated files with a header, taking the source
+     * from the first column and the target from the second.
+     */
+    pub fn new() -> Self {
+        CsvReader {
+            delimiter: ',',
+            has_header: true,
+            source: Column::Position(0),
+            target: Column::Position(1),
+            weight: None,
+            label: None,
+        }
+    }
+
+    /**
+     * Sets the field delimiter.
+     */
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /**
+     * Sets whether the first record is a header; columns can only be named if it is.
+     */
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /**
+     * Sets the column of the source nodes.
+     */
+    pub fn source(mut self, column: impl Into<Column>) -> Self {
+        self.source = column.into();
+        self
+    }
+
+    /**
+     * Sets the column of the target nodes.
+     */
+    pub fn target(mut self, column: impl Into<Column>) -> Self {
+        self.target = column.into();
+        self
+    }
+
+    /**
+     * Sets the column of the edge weights, used by [`CsvReader::read_weighted`].
+     */
+    pub fn weight(mut self, column: impl Into<Column>) -> Self {
+        self.weight = Some(column.into());
+        self
+    }
+
+    /**
+     * Sets the column of the edge labels.
+     */
+    pub fn label(mut self, column: impl Into<Column>) -> Self {
+        self.label = Some(column.into());
+        self
+    }
+
+    /**
+     * Reads an unweighted graph, ignoring the weight column.
+     */
+    pub fn read<G, R>(&self, reader: R) -> Result<CsvGraph<G>, IoError>
+    where
+        G: Graph + Default,
+        G::Index: Copy + Hash + Eq,
+        R: Read,
+    {
+        self.load(reader, false, |graph: &mut G, f, t, _: Option<&str>, _| {
+            graph.add_edge(f, t);
+            Ok(())
+        })
+    }
+
+    /**
+     * Reads a weighted graph; the weight column is required.
+     */
+    pub fn read_weighted<G, R>(&self, reader: R) -> Result<CsvGraph<G>, IoError>
+    where
+        G: WeightedGraph + Default,
+        G::Index: Copy + Hash + Eq,
+        G::Weight: FromStr,
+        R: Read,
+    {
+        if self.weight.is_none() {
+            return Err(IoError::Format("no weight column configured".to_string()));
+        }
+
+        self.load(reader, true, |graph: &mut G, f, t, weight, line| {
+            let weight = weight.unwrap_or_default();
+            let w = weight
+                .trim()
+                .parse()
+                .map_err(|_| IoError::parse(line, format!("invalid weight `{}`", weight)))?;
+            graph.add_weighted_edge(f, t, w);
+            Ok(())
+        })
+    }
+
+    fn load<G, R, F>(
+        &self,
+        mut reader: R,
+        weighted: bool,
+        mut add: F,
+    ) -> Result<CsvGraph<G>, IoError>
+    where
+        G: Graph + Default,
+        G::Index: Copy + Hash + Eq,
+        R: Read,
+        F: FnMut(&mut G, G::Index, G::Index, Option<&str>, usize) -> Result<(), IoError>,
+    {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        let mut records = parse_records(&input, self.delimiter)?.into_iter();
+
+        let header = if self.has_header {
+            records.next()
+        } else {
+            None
+        };
+        let columns = self.resolve(header.as_ref())?;
+
+        let mut graph = G::default();
+        let mut nodes = HashMap::new();
+        let mut edge_labels = HashMap::new();
+
+        for record in records {
+            if record.fields.iter().all(|f| f.is_empty()) {
+                continue;
+            }
+            let field =
+                |i: usize| {
+                    record.fields.get(i).map(String::as_str).ok_or_else(|| {
+                        IoError::parse(record.line, format!("missing column {}", i + 1))
+                    })
+                };
+
+            let mut node = |name: &str| {
+                *nodes
+                    .entry(name.to_string())
+                    .or_insert_with(|| graph.add_node())
+            };
+            let f = node(field(columns.source)?);
+            let t = node(field(columns.target)?);
+            let weight = columns.weight.filter(|_| weighted).map(field).transpose()?;
+            add(&mut graph, f, t, weight, record.line)?;
+
+            if let Some(label) = columns.label {
+                edge_labels.insert((f, t), field(label)?.to_string());
+            }
+        }
+
+        Ok(CsvGraph {
+            graph,
+            nodes,
+            edge_labels,
+        })
+    }
+
+    fn resolve(&self, header: Option<&Record>) -> Result<Columns, IoError> {
+        let position = |column: &Column| match (column, header) {
+            (Column::Position(i), _) => Ok(*i),
+            (Column::Name(name), Some(header)) => {
+                header.fields.iter().position(|f| f == name).ok_or_else(|| {
+                    IoError::parse(header.line, format!("no column named `{}`", name))
+                })
+            }
+            (Column::Name(name), None) => Err(IoError::Format(format!(
+                "column `{}` is named but the file has no header",
+                name
+            ))),
+        };
+
+        Ok(Columns {
+            source: position(&self.source)?,
+            target: position(&self.target)?,
+            weight: self.weight.as_ref().map(position).transpose()?,
+            label: self.label.as_ref().map(position).transpose()?,
+        })
+    }
+}
+
+impl Default for CsvReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_records(input: &str, delimiter: char) -> Result<Vec<Record>, IoError> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    let mut start = 1;
+
+    loop {
+        match chars.next() {
+            Some('"') if field.is_empty() => {
+                let open = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(IoError::parse(open, "unterminated quoted field")),
+                    }
+                }
+            }
+            Some(c) if c == delimiter => fields.push(std::mem::take(&mut field)),
+            Some('\r') if chars.peek() == Some(&'\n') => {}
+            Some('\n') | None => {
+                let done = chars.peek().is_none();
+                if !fields.is_empty() || !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                    records.push(Record {
+                        fields: std::mem::take(&mut fields),
+                        line: start,
+                    });
+                }
+                line += 1;
+                start = line;
+                if done {
+                    return Ok(records);
+                }
+            }
+            Some(c) => field.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::GraphRead, impls::adj_list::AdjListGraph,
+        impls::undirected_weighted::UndirectedWeightedGraph,
+    };
+
+    #[test]
+    fn test_quoting() {
+        let input = "a,\"b \"\"x\"\"\",\"multi\nline\"\r\nc,d,e\n\n";
+        let records = parse_records(input, ',').unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].fields, vec!["a", "b \"x\"", "multi\nline"]);
+        assert_eq!((records[1].line, records[1].fields.len()), (3, 3));
+
+        let res = parse_records("a,\"b\n", ',');
+        assert!(matches!(res, Err(IoError::Parse { line: 1, .. })));
+    }
+
+    #[test]
+    fn test_columns_by_name() {
+        let input = "id;to;from;name\n1;b;a;first\n2;c;a;second\n";
+        let loaded: CsvGraph<AdjListGraph> = CsvReader::new()
+            .delimiter(';')
+            .source("from")
+            .target("to")
+            .label("name")
+            .read(input.as_bytes())
+            .unwrap();
+        let n = |name: &str| loaded.nodes[name];
+
+        assert_eq!(loaded.graph.node_count(), 3);
+        assert!(loaded.graph.has_edge(n("a"), n("b")));
+        assert_eq!(loaded.edge_labels[&(n("a"), n("c"))], "second");
+    }
+
+    #[test]
+    fn test_errors() {
+        let reader = CsvReader::new().has_header(false).weight(2);
+        let res =
+            reader.read_weighted::<UndirectedWeightedGraph<u32>, _>("a,b,1\nb,c,x\n".as_bytes());
+        assert!(matches!(res, Err(IoError::Parse { line: 2, .. })));
+
+        let res = reader.read::<AdjListGraph, _>("a,b\nc\n".as_bytes());
+        assert!(matches!(res, Err(IoError::Parse { line: 2, .. })));
+
+        let res = CsvReader::new()
+            .source("src")
+            .read::<AdjListGraph, _>("from,to\n".as_bytes());
+        assert!(matches!(res, Err(IoError::Parse { line: 1, .. })));
+    }
+}
@@ -0,0 +1,190 @@
+use super::json::{members, JsonValue};
+use crate::graph::{GraphRead, WeightedGraph};
+use std::{fmt::Display, io::Write};
+
+type NodeDataFn<'a, I> = Box<dyn Fn(&I) -> JsonValue + 'a>;
+type EdgeDataFn<'a, I> = Box<dyn Fn(&I, &I) -> JsonValue + 'a>;
+
+/**
+ * Writer of graphs as Cytoscape.js elements JSON, in the form
+ * `{"elements": {"nodes": [{"data": {...}}], "edges": [{"data": {...}}]}}`.
+ *
+ * Each node has an `id`, its index as a string; each edge has an `id` of the form
+ * `e<position>`, numbering the edges in order so that the parallel edges of a
+ * multigraph get distinct ids, and refers to its endpoints by id. Additional data fields are
+ * added with [`CytoscapeWriter::node_data`] and [`CytoscapeWriter::edge_data`].
+ *
+ * ```
+ * use rust_graph_lib::{graph::{Graph, WeightedGraph}, impls::undirected_weighted::UndirectedWeightedGraph, io::cytoscape::CytoscapeWriter};
+ *
+ * let mut g = UndirectedWeightedGraph::new();
+ * let a = g.add_node();
+ * let b = g.add_node();
+ * g.add_weighted_edge(a, b, 2.5);
+ *
+ * let mut out = Vec::new();
+ * CytoscapeWriter::new(&g).node_data("label", |n| format!("n{}", n).into()).weighted().write(&mut out).unwrap();
+ * assert_eq!(
+ *     String::from_utf8(out).unwrap(),
+ *     r#"{"elements":{"nodes":[{"data":{"id":"1","label":"n1"}},{"data":{"id":"2","label":"n2"}}],"edges":[{"data":{"id":"e0","source":"1","target":"2","weight":2.5}}]}}"#
+ * );
+ * ```
+ */
+pub struct CytoscapeWriter<'a, G: GraphRead> {
+    graph: &'a G,
+    node_data: Vec<(&'static str, NodeDataFn<'a, G::Index>)>,
+    edge_data: Vec<(&'static str, EdgeDataFn<'a, G::Index>)>,
+}
+
+impl<'a, G: GraphRead> CytoscapeWriter<'a, G> {
+    /**
+     * Creates a writer producing only node ids and edge endpoints.
+     */
+    pub fn new(graph: &'a G) -> Self {
+        CytoscapeWriter {
+            graph,
+            node_data: Vec::new(),
+            edge_data: Vec::new(),
+        }
+    }
+
+    /**
+     * Adds the `name` field to the data of each node, after the fields added before.
+     */
+    pub fn node_data<F: Fn(&G::Index) -> JsonValue + 'a>(
+        mut self,
+        name: &'static str,
+        f: F,
+    ) -> Self {
+        self.node_data.push((name, Box::new(f)));
+        self
+    }
+
+    /**
+     * Adds the `name` field to the data of each edge, after the fields added before.
+     */
+    pub fn edge_data<F: Fn(&G::Index, &G::Index) -> JsonValue + 'a>(
+        mut self,
+        name: &'static str,
+        f: F,
+    ) -> Self {
+        self.edge_data.push((name, Box::new(f)));
+        self
+    }
+
+    /**
+     * Writes the graph on a single line, with nodes and edges sorted by index.
+     */
+    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        G::Index: Display,
+    {
+        let mut nodes: Vec<_> = self.graph.iter_nodes().collect();
+        nodes.sort();
+        let nodes: Vec<_> = nodes
+            .iter()
+            .map(|n| {
+                let mut data = vec![("id", JsonValue::String(n.to_string()))];
+                data.extend(self.node_data.iter().map(|(k, f)| (*k, f(n))));
+                element(&data)
+            })
+            .collect();
+
+        let mut edges: Vec<_> = self.graph.iter_edges().collect();
+        edges.sort();
+        let edges: Vec<_> = edges
+            .iter()
+            .enumerate()
+            .map(|(i, (f, t))| {
+                let mut data = vec![
+                    ("id", JsonValue::String(format!("e{}", i))),
+                    ("source", JsonValue::String(f.to_string())),
+                    ("target", JsonValue::String(t.to_string())),
+                ];
+                data.extend(self.edge_data.iter().map(|(k, d)| (*k, d(f, t))));
+                element(&data)
+            })
+            .collect();
+
+        write!(
+            writer,
+            "{{\"elements\":{{\"nodes\":[{}],\"edges\":[{}]}}}}",
+            nodes.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+impl<'a, G> CytoscapeWriter<'a, G>
+where
+    G: WeightedGraph,
+    G::Index: Copy,
+    G::Weight: Into<JsonValue>,
+{
+    /**
+     * Adds the weight of each edge as its `weight` field.
+     */
+    pub fn weighted(self) -> Self {
+        let graph = self.graph;
+        self.edge_data("weight", move |&f, &t| graph.edge_weight(f, t).into())
+    }
+}
+
+fn element(data: &[(&str, JsonValue)]) -> String {
+    format!(
+        "{{\"data\":{{{}}}}}",
+        members(data.iter().map(|(k, v)| (*k, v)))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::Graph,
+        impls::{adj_list::AdjListGraph, multi::MultiGraph},
+    };
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_write() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id2, id1);
+        g.add_edge(id1, id1);
+
+        let mut out = Vec::new();
+        CytoscapeWriter::new(&g)
+            .edge_data("loop", |f, t| (f == t).into())
+            .edge_data("kind", |_, _| "link".into())
+            .write(&mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"elements\":{\"nodes\":[{\"data\":{\"id\":\"1\"}},{\"data\":{\"id\":\"2\"}}],\"edges\":[\
+             {\"data\":{\"id\":\"e0\",\"source\":\"1\",\"target\":\"1\",\"loop\":true,\"kind\":\"link\"}},\
+             {\"data\":{\"id\":\"e1\",\"source\":\"2\",\"target\":\"1\",\"loop\":false,\"kind\":\"link\"}}]}}"
+        );
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let mut g = MultiGraph::new();
+        let (a, b) = (g.add_node(), g.add_node());
+        g.add_edge(a, b);
+        g.add_edge(a, b);
+        g.add_edge(b, a);
+
+        let mut out = Vec::new();
+        CytoscapeWriter::new(&g).write(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let ids: HashSet<_> = out
+            .split("\"id\":\"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+        assert_eq!(ids.len(), 5);
+        assert_eq!(out.matches("\"source\":\"1\",\"target\":\"2\"").count(), 2);
+    }
+}
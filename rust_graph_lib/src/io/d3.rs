@@ -0,0 +1,158 @@
+use super::json::{members, JsonValue};
+use crate::graph::{GraphRead, WeightedGraph};
+use std::{fmt::Display, io::Write};
+
+type NodeValueFn<'a, I> = Box<dyn Fn(&I) -> JsonValue + 'a>;
+type EdgeValueFn<'a, I> = Box<dyn Fn(&I, &I) -> JsonValue + 'a>;
+
+/**
+ * Writer of graphs in the `{"nodes": [...], "links": [...]}` JSON structure used by d3-force.
+ *
+ * Each node has an `id`, its index as a string, and links refer to nodes by id.
+ *
+ * ```
+ * use rust_graph_lib::{graph::{Graph, WeightedGraph}, impls::undirected_weighted::UndirectedWeightedGraph, io::d3::D3Writer};
+ *
+ * let mut g = UndirectedWeightedGraph::new();
+ * let a = g.add_node();
+ * let b = g.add_node();
+ * g.add_weighted_edge(a, b, 3u32);
+ *
+ * let mut out = Vec::new();
+ * D3Writer::new(&g).group(|n| (n.to_string() == "1").into()).weighted().write(&mut out).unwrap();
+ * assert_eq!(
+ *     String::from_utf8(out).unwrap(),
+ *     r#"{"nodes":[{"id":"1","group":true},{"id":"2","group":false}],"links":[{"source":"1","target":"2","value":3}]}"#
+ * );
+ * ```
+ */
+pub struct D3Writer<'a, G: GraphRead> {
+    graph: &'a G,
+    group: Option<NodeValueFn<'a, G::Index>>,
+    node_weight: Option<NodeValueFn<'a, G::Index>>,
+    value: Option<EdgeValueFn<'a, G::Index>>,
+}
+
+impl<'a, G: GraphRead> D3Writer<'a, G> {
+    /**
+     * Creates a writer producing only node ids and link endpoints.
+     */
+    pub fn new(graph: &'a G) -> Self {
+        D3Writer {
+            graph,
+            group: None,
+            node_weight: None,
+            value: None,
+        }
+    }
+
+    /**
+     * Sets the `group` attribute of each node, typically used for coloring.
+     */
+    pub fn group<F: Fn(&G::Index) -> JsonValue + 'a>(mut self, f: F) -> Self {
+        self.group = Some(Box::new(f));
+        self
+    }
+
+    /**
+     * Sets the `weight` attribute of each node, typically used for sizing.
+     */
+    pub fn node_weight<F: Fn(&G::Index) -> JsonValue + 'a>(mut self, f: F) -> Self {
+        self.node_weight = Some(Box::new(f));
+        self
+    }
+
+    /**
+     * Sets the `value` attribute of each link.
+     */
+    pub fn link_value<F: Fn(&G::Index, &G::Index) -> JsonValue + 'a>(mut self, f: F) -> Self {
+        self.value = Some(Box::new(f));
+        self
+    }
+
+    /**
+     * Writes the graph on a single line, with nodes and links sorted by index.
+     */
+    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        G::Index: Display,
+    {
+        let mut nodes: Vec<_> = self.graph.iter_nodes().collect();
+        nodes.sort();
+        let nodes: Vec<_> = nodes
+            .iter()
+            .map(|n| {
+                let mut attrs = vec![("id", JsonValue::String(n.to_string()))];
+                if let Some(f) = &self.group {
+                    attrs.push(("group", f(n)));
+                }
+                if let Some(f) = &self.node_weight {
+                    attrs.push(("weight", f(n)));
+                }
+                format!("{{{}}}", members(attrs.iter().map(|(k, v)| (*k, v))))
+            })
+            .collect();
+
+        let mut edges: Vec<_> = self.graph.iter_edges().collect();
+        edges.sort();
+        let links: Vec<_> = edges
+            .iter()
+            .map(|(f, t)| {
+                let mut attrs = vec![
+                    ("source", JsonValue::String(f.to_string())),
+                    ("target", JsonValue::String(t.to_string())),
+                ];
+                if let Some(value) = &self.value {
+                    attrs.push(("value", value(f, t)));
+                }
+                format!("{{{}}}", members(attrs.iter().map(|(k, v)| (*k, v))))
+            })
+            .collect();
+
+        write!(
+            writer,
+            "{{\"nodes\":[{}],\"links\":[{}]}}",
+            nodes.join(","),
+            links.join(",")
+        )
+    }
+}
+
+impl<'a, G> D3Writer<'a, G>
+where
+    G: WeightedGraph,
+    G::Index: Copy,
+    G::Weight: Into<JsonValue>,
+{
+    /**
+     * Writes the weight of each edge as the `value` of its link.
+     */
+    pub fn weighted(self) -> Self {
+        let graph = self.graph;
+        self.link_value(move |&f, &t| graph.edge_weight(f, t).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_write() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id2, id1);
+
+        let mut out = Vec::new();
+        D3Writer::new(&g)
+            .node_weight(|n| (n.to_string().len() * 10).into())
+            .write(&mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"nodes\":[{\"id\":\"1\",\"weight\":10},{\"id\":\"2\",\"weight\":10}],\"links\":[{\"source\":\"2\",\"target\":\"1\"}]}"
+        );
+    }
+}
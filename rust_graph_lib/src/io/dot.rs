@@ -0,0 +1,212 @@
+use crate::graph::{GraphRead, WeightedGraph};
+use std::{fmt::Display, io::Write};
+
+/**
+ * Graphviz attributes of a node or an edge, as `(name, value)` pairs.
+ */
+pub type Attributes = Vec<(&'static str, String)>;
+
+type NodeAttrFn<'a, I> = Box<dyn Fn(&I) -> Attributes + 'a>;
+type EdgeAttrFn<'a, I> = Box<dyn Fn(&I, &I) -> Attributes + 'a>;
+
+/**
+ * Writer of graphs in the Graphviz DOT format.
+ *
+ * ```
+ * use rust_graph_lib::{graph::{Graph, WeightedGraph}, impls::undirected_weighted::UndirectedWeightedGraph, io::dot::DotWriter};
+ *
+ * let mut g = UndirectedWeightedGraph::new();
+ * let a = g.add_node();
+ * let b = g.add_node();
+ * g.add_weighted_edge(a, b, 3);
+ *
+ * let mut out = Vec::new();
+ * DotWriter::new(&g)
+ *     .undirected(true)
+ *     .weighted()
+ *     .node_attributes(|_| vec![("shape", "box".to_string())])
+ *     .write(&mut out)
+ *     .unwrap();
+ * assert_eq!(
+ *     String::from_utf8(out).unwrap(),
+ *     "graph {\n  \"1\" [shape=\"box\"];\n  \"2\" [shape=\"box\"];\n  \"1\" -- \"2\" [label=\"3\", weight=\"3\"];\n}\n"
+ * );
+ * ```
+ */
+pub struct DotWriter<'a, G: GraphRead> {
+    graph: &'a G,
+    undirected: bool,
+    node_attrs: Option<NodeAttrFn<'a, G::Index>>,
+    weight_attrs: Option<EdgeAttrFn<'a, G::Index>>,
+    edge_attrs: Option<EdgeAttrFn<'a, G::Index>>,
+}
+
+impl<'a, G: GraphRead> DotWriter<'a, G> {
+    /**
+     * Creates a writer producing a `digraph` with no attributes.
+     */
+    pub fn new(graph: &'a G) -> Self {
+        DotWriter {
+            graph,
+            undirected: false,
+            node_attrs: None,
+            weight_attrs: None,
+            edge_attrs: None,
+        }
+    }
+
+    /**
+     * Writes a `graph` with `--` edges instead of a `digraph`, for graphs listing
+     * each undirected edge once.
+     */
+    pub fn undirected(mut self, undirected: bool) -> Self {
+        self.undirected = undirected;
+        self
+    }
+
+    /**
+     * Sets the attributes of each node.
+     */
+    pub fn node_attributes<F: Fn(&G::Index) -> Attributes + 'a>(mut self, f: F) -> Self {
+        self.node_attrs = Some(Box::new(f));
+        self
+    }
+
+    /**
+     * Sets the attributes of each edge, written after the weight attributes if any.
+     */
+    pub fn edge_attributes<F: Fn(&G::Index, &G::Index) -> Attributes + 'a>(mut self, f: F) -> Self {
+        self.edge_attrs = Some(Box::new(f));
+        self
+    }
+
+    /**
+     * Writes the graph, with nodes and edges sorted by index.
+     */
+    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        G::Index: Display,
+    {
+        let (kind, arrow) = if self.undirected {
+            ("graph", "--")
+        } else {
+            ("digraph", "->")
+        };
+
+        let mut nodes: Vec<_> = self.graph.iter_nodes().collect();
+        nodes.sort();
+        writeln!(writer, "{} {{", kind)?;
+        for n in &nodes {
+            let attrs = self.node_attrs.as_ref().map(|f| f(n)).unwrap_or_default();
+            writeln!(
+                writer,
+                "  {}{};",
+                quote(&n.to_string()),
+                format_attrs(&attrs)
+            )?;
+        }
+
+        let mut edges: Vec<_> = self.graph.iter_edges().collect();
+        edges.sort();
+        for (f, t) in &edges {
+            let mut attrs = Attributes::new();
+            for attr_fn in [&self.weight_attrs, &self.edge_attrs].into_iter().flatten() {
+                attrs.extend(attr_fn(f, t));
+            }
+            writeln!(
+                writer,
+                "  {} {} {}{};",
+                quote(&f.to_string()),
+                arrow,
+                quote(&t.to_string()),
+                format_attrs(&attrs)
+            )?;
+        }
+        writeln!(writer, "}}")
+    }
+}
+
+impl<'a, G> DotWriter<'a, G>
+where
+    G: WeightedGraph,
+    G::Index: Copy,
+    G::Weight: Display,
+{
+    /**
+     * Writes the weight of each edge as its `label` and `weight` attributes.
+     */
+    pub fn weighted(mut self) -> Self {
+        let graph = self.graph;
+        self.weight_attrs = Some(Box::new(move |&f, &t| match graph.edge_weight(f, t) {
+            Some(w) => vec![("label", w.to_string()), ("weight", w.to_string())],
+            None => Vec::new(),
+        }));
+        self
+    }
+}
+
+pub(crate) fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub(crate) fn format_attrs(attrs: &Attributes) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    let attrs: Vec<_> = attrs
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, quote(value)))
+        .collect();
+    format!(" [{}]", attrs.join(", "))
+}
+
+/**
+ * Writes a graph in DOT format, without attributes; see [`DotWriter`] for more options.
+ */
+pub fn write_dot<G, W>(graph: &G, writer: W) -> std::io::Result<()>
+where
+    G: GraphRead,
+    G::Index: Display,
+    W: Write,
+{
+    DotWriter::new(graph).write(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_write_dot() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id2, id1);
+
+        let mut out = Vec::new();
+        write_dot(&g, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "digraph {\n  \"1\";\n  \"2\";\n  \"2\" -> \"1\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_attributes_are_escaped() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        g.add_edge(id1, id1);
+
+        let mut out = Vec::new();
+        DotWriter::new(&g)
+            .node_attributes(|n| vec![("label", format!("say \"{}\"", n))])
+            .edge_attributes(|_, _| vec![("color", "red".to_string())])
+            .write(&mut out)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "digraph {\n  \"1\" [label=\"say \\\"1\\\"\"];\n  \"1\" -> \"1\" [color=\"red\"];\n}\n"
+        );
+    }
+}
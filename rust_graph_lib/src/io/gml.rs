@@ -0,0 +1,408 @@
+use super::{IoError, LoadedGraph};
+use crate::graph::{Graph, GraphRead};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hash::Hash,
+    io::{Read, Write},
+    iter::Peekable,
+    str::Chars,
+};
+
+enum Token {
+    Key(String),
+    Int(i64),
+    Float,
+    Str(String),
+    Open,
+    Close,
+}
+
+enum Value {
+    Int(i64),
+    Float,
+    Str(String),
+    List(Vec<Entry>),
+}
+
+struct Entry {
+    key: String,
+    value: Value,
+    pos: Pos,
+}
+
+/**
+ * 1-based position in the input.
+ */
+#[derive(Clone, Copy)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+impl Pos {
+    fn error(self, message: impl Into<String>) -> IoError {
+        IoError::parse_at(self.line, self.column, message)
+    }
+}
+
+struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    pos: Pos,
+}
+
+impl Cursor<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next_if(&mut self, f: impl FnOnce(&char) -> bool) -> Option<char> {
+        let c = self.chars.next_if(f)?;
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        self.next_if(|_| true)
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, Pos)>, IoError> {
+    let mut tokens = Vec::new();
+    let mut cursor = Cursor {
+        chars: input.chars().peekable(),
+        pos: Pos { line: 1, column: 1 },
+    };
+
+    while let Some(c) = cursor.peek() {
+        let start = cursor.pos;
+        match c {
+            c if c.is_whitespace() => {
+                cursor.next();
+            }
+            '#' => while cursor.next_if(|&c| c != '\n').is_some() {},
+            '[' => {
+                cursor.next();
+                tokens.push((Token::Open, start));
+            }
+            ']' => {
+                cursor.next();
+                tokens.push((Token::Close, start));
+            }
+            '"' => {
+                cursor.next();
+                let mut s = String::new();
+                loop {
+                    match cursor.next() {
+                        Some('"') => break,
+                        Some(c) => s.push(c),
+                        None => return Err(start.error("unterminated string")),
+                    }
+                }
+                tokens.push((Token::Str(unescape(&s)), start));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut s = String::new();
+                while let Some(c) = cursor.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    s.push(c);
+                }
+                tokens.push((Token::Key(s), start));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let mut s = String::new();
+                while let Some(c) = cursor
+                    .next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                {
+                    s.push(c);
+                }
+                let token = match s.parse::<i64>() {
+                    Ok(i) => Token::Int(i),
+                    Err(_) => s
+                        .parse::<f64>()
+                        .map(|_| Token::Float)
+                        .map_err(|_| start.error(format!("invalid number `{}`", s)))?,
+                };
+                tokens.push((token, start));
+            }
+            c => return Err(start.error(format!("unexpected character `{}`", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/**
+ * Maximum nesting of lists, bounding the recursion of the parser on hostile input.
+ */
+const MAX_DEPTH: usize = 64;
+
+fn parse_list<I: Iterator<Item = (Token, Pos)>>(
+    tokens: &mut I,
+    open: Option<Pos>,
+    depth: usize,
+) -> Result<Vec<Entry>, IoError> {
+    let mut entries = Vec::new();
+
+    loop {
+        let (key, pos) = match tokens.next() {
+            Some((Token::Key(k), pos)) => (k, pos),
+            Some((Token::Close, _)) if open.is_some() => return Ok(entries),
+            Some((_, pos)) => return Err(pos.error("expected a key")),
+            None => match open {
+                Some(open) => return Err(open.error("unclosed `[`")),
+                None => return Ok(entries),
+            },
+        };
+
+        let value = match tokens.next() {
+            Some((Token::Int(i), _)) => Value::Int(i),
+            Some((Token::Float, _)) => Value::Float,
+            Some((Token::Str(s), _)) => Value::Str(s),
+            Some((Token::Open, open)) if depth == MAX_DEPTH => {
+                return Err(open.error(format!("lists nested deeper than {}", MAX_DEPTH)))
+            }
+            Some((Token::Open, open)) => Value::List(parse_list(tokens, Some(open), depth + 1)?),
+            _ => return Err(pos.error(format!("missing value for `{}`", key))),
+        };
+
+        entries.push(Entry { key, value, pos });
+    }
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&quot;", "\"").replace("&amp;", "&")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+/**
+ * Returns the integer value of `key`, reporting errors at the key or, if it is
+ * missing, at `pos`.
+ */
+fn get_int(entries: &[Entry], key: &str, pos: Pos) -> Result<(i64, Pos), IoError> {
+    match entries.iter().find(|e| e.key == key) {
+        Some(Entry {
+            value: Value::Int(i),
+            pos,
+            ..
+        }) => Ok((*i, *pos)),
+        Some(e) => Err(e.pos.error(format!("`{}` must be an integer", key))),
+        None => Err(pos.error(format!("missing `{}`", key))),
+    }
+}
+
+/**
+ * Reads a graph in GML format.
+ *
+ * Graphs not marked as `directed 1` are undirected by the GML specification,
+ * so each of their edges is added in both directions.
+ */
+pub fn read_gml<G, R>(mut reader: R) -> Result<LoadedGraph<G>, IoError>
+where
+    G: Graph + Default,
+    G::Index: Copy + Hash + Eq,
+    R: Read,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    let mut tokens = tokenize(&input)?.into_iter();
+    let top = parse_list(&mut tokens, None, 0)?;
+
+    let (entries, graph_pos) = top
+        .into_iter()
+        .find_map(|e| match e.value {
+            Value::List(l) if e.key == "graph" => Some((l, e.pos)),
+            _ => None,
+        })
+        .ok_or_else(|| IoError::parse(1, "missing `graph` section"))?;
+
+    let directed = match entries.iter().find(|e| e.key == "directed") {
+        Some(_) => get_int(&entries, "directed", graph_pos)?.0 != 0,
+        None => false,
+    };
+
+    let mut graph = G::default();
+    let mut ids = HashMap::new();
+    let mut labels = HashMap::new();
+
+    for entry in &entries {
+        if let (Value::List(attrs), "node") = (&entry.value, entry.key.as_str()) {
+            let (id, pos) = get_int(attrs, "id", entry.pos)?;
+            let id = u64::try_from(id).map_err(|_| pos.error("node ids must be non-negative"))?;
+            if ids.contains_key(&id) {
+                return Err(IoError::DuplicateNode {
+                    line: pos.line,
+                    id: id.to_string(),
+                });
+            }
+            let n = graph.add_node();
+            ids.insert(id, n);
+
+            if let Some(Value::Str(label)) =
+                attrs.iter().find(|e| e.key == "label").map(|e| &e.value)
+            {
+                labels.insert(n, label.clone());
+            }
+        }
+    }
+
+    for entry in &entries {
+        if let (Value::List(attrs), "edge") = (&entry.value, entry.key.as_str()) {
+            let endpoint = |key| {
+                let (id, pos) = get_int(attrs, key, entry.pos)?;
+                u64::try_from(id)
+                    .ok()
+                    .and_then(|id| ids.get(&id).copied())
+                    .ok_or_else(|| IoError::UndefinedNode {
+                        line: pos.line,
+                        column: Some(pos.column),
+                        id: id.to_string(),
+                    })
+            };
+            let f = endpoint("source")?;
+            let t = endpoint("target")?;
+
+            graph.add_edge(f, t);
+            if !directed && f != t {
+                graph.add_edge(t, f);
+            }
+        }
+    }
+
+    Ok(LoadedGraph { graph, ids, labels })
+}
+
+/**
+ * Writes a directed graph in GML format.
+ *
+ * Nodes get sequential ids starting from 1 and are labelled with their index.
+ */
+pub fn write_gml<G, W>(graph: &G, mut writer: W) -> std::io::Result<()>
+where
+    G: GraphRead,
+    G::Index: Copy + Hash + Eq + Display,
+    W: Write,
+{
+    let mut nodes: Vec<_> = graph.iter_nodes().collect();
+    nodes.sort();
+    let ids: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &n)| (n, i + 1)).collect();
+
+    writeln!(writer, "graph [")?;
+    writeln!(writer, "  directed 1")?;
+    for n in &nodes {
+        writeln!(writer, "  node [")?;
+        writeln!(writer, "    id {}", ids[n])?;
+        writeln!(writer, "    label \"{}\"", escape(&n.to_string()))?;
+        writeln!(writer, "  ]")?;
+    }
+
+    let mut edges: Vec<_> = graph.iter_edges().collect();
+    edges.sort();
+    for (f, t) in edges {
+        writeln!(writer, "  edge [")?;
+        writeln!(writer, "    source {}", ids[&f])?;
+        writeln!(writer, "    target {}", ids[&t])?;
+        writeln!(writer, "  ]")?;
+    }
+    writeln!(writer, "]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    #[test]
+    fn test_read_undirected() {
+        let input = r#"
+            # a comment
+            graph [
+              node [ id 10 label "a" ]
+              node [ id 20 label "b" weight 1.5 ]
+              edge [ source 10 target 20 ]
+            ]
+        "#;
+        let loaded: LoadedGraph<AdjListGraph> = read_gml(input.as_bytes()).unwrap();
+        let (a, b) = (loaded.ids[&10], loaded.ids[&20]);
+
+        assert_eq!(loaded.graph.node_count(), 2);
+        assert!(loaded.graph.has_edge(a, b));
+        assert!(loaded.graph.has_edge(b, a));
+        assert_eq!(loaded.labels[&a], "a");
+    }
+
+    #[test]
+    fn test_undefined_node() {
+        let input = "graph [\n directed 1\n node [ id 1 ]\n edge [ source 1 target 2 ]\n]";
+        let res = read_gml::<AdjListGraph, _>(input.as_bytes());
+        assert!(matches!(
+            res,
+            Err(IoError::UndefinedNode {
+                line: 4,
+                column: Some(18),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_checked_errors() {
+        let input = "graph [\n node [ id 1 ]\n node [ id 1 ]\n]";
+        let res = read_gml::<AdjListGraph, _>(input.as_bytes());
+        assert!(matches!(res, Err(IoError::DuplicateNode { line: 3, .. })));
+
+        let res = read_gml::<AdjListGraph, _>("graph [\n  node [ id 1 @ ]\n]".as_bytes());
+        let err = res.err().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(15)));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 15: unexpected character `@`"
+        );
+
+        let res = read_gml::<AdjListGraph, _>("graph [\n  node [ id 1 ]".as_bytes());
+        assert!(matches!(
+            res,
+            Err(IoError::Parse {
+                line: 1,
+                column: Some(7),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let input = "graph [ a [ ".repeat(100_000);
+        match read_gml::<AdjListGraph, _>(input.as_bytes()) {
+            Err(err) => assert!(err.to_string().contains("nested deeper than 64")),
+            Ok(_) => panic!("deep nesting accepted"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+
+        let mut out = Vec::new();
+        write_gml(&g, &mut out).unwrap();
+        let loaded: LoadedGraph<AdjListGraph> = read_gml(out.as_slice()).unwrap();
+
+        assert_eq!(loaded.graph.node_count(), 3);
+        assert_eq!(loaded.graph.edge_count(), 2);
+        assert!(loaded.graph.has_edge(loaded.ids[&1], loaded.ids[&2]));
+        assert!(loaded.graph.has_edge(loaded.ids[&2], loaded.ids[&3]));
+    }
+}
@@ -0,0 +1,159 @@
+use super::{column_of, IoError};
+use crate::history::GraphOp;
+use std::{
+    fmt::Display,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+/**
+ * Writes a log of mutations as text, one per line, oldest first:
+ *
+ * ```text
+ * add_node 1
+ * add_node 2
+ * add_edge 1 2
+ * add_weighted_edge 2 1 5
+ * remove_edge 1 2
+ * remove_node 2
+ * ```
+ */
+pub fn write_history<I, W, Wr>(ops: &[GraphOp<I, W>], mut writer: Wr) -> std::io::Result<()>
+where
+    I: Display,
+    W: Display,
+    Wr: Write,
+{
+    for op in ops {
+        match op {
+            GraphOp::AddNode(n) => writeln!(writer, "add_node {}", n)?,
+            GraphOp::RemoveNode(n) => writeln!(writer, "remove_node {}", n)?,
+            GraphOp::AddEdge(f, t) => writeln!(writer, "add_edge {} {}", f, t)?,
+            GraphOp::AddWeightedEdge(f, t, w) => {
+                writeln!(writer, "add_weighted_edge {} {} {}", f, t, w)?
+            }
+            GraphOp::RemoveEdge(f, t) => writeln!(writer, "remove_edge {} {}", f, t)?,
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Reads a log of mutations written by [`write_history`], skipping blank lines.
+ *
+ * Nodes are read as `I`, which needs not be the index type of the recorded graph:
+ * the log is replayed with [`replay`](crate::history::replay), which maps them to
+ * new indices, so e.g. `usize` reads the logs of most graphs. Logs without weighted
+ * edges can be read with any weight type.
+ */
+pub fn read_history<I, W, R>(reader: R) -> Result<Vec<GraphOp<I, W>>, IoError>
+where
+    I: FromStr,
+    W: FromStr,
+    R: BufRead,
+{
+    let mut ops = Vec::new();
+    for (i, raw) in reader.lines().enumerate() {
+        let raw = raw?;
+        let line_no = i + 1;
+        let mut parts = raw.split_whitespace();
+        let Some(name) = parts.next() else {
+            continue;
+        };
+
+        let fields: Vec<_> = parts.collect();
+        let arity = match name {
+            "add_node" | "remove_node" => 1,
+            "add_edge" | "remove_edge" => 2,
+            "add_weighted_edge" => 3,
+            _ => {
+                return Err(IoError::parse_at(
+                    line_no,
+                    column_of(&raw, name),
+                    format!("unknown operation `{}`", name),
+                ))
+            }
+        };
+        if fields.len() != arity {
+            return Err(IoError::parse(
+                line_no,
+                format!("`{}` takes {} values, found {}", name, arity, fields.len()),
+            ));
+        }
+
+        let parse_node = |field: &str| {
+            field.parse::<I>().map_err(|_| {
+                IoError::parse_at(
+                    line_no,
+                    column_of(&raw, field),
+                    format!("invalid node `{}`", field),
+                )
+            })
+        };
+        let op = match name {
+            "add_node" => GraphOp::AddNode(parse_node(fields[0])?),
+            "remove_node" => GraphOp::RemoveNode(parse_node(fields[0])?),
+            "add_edge" => GraphOp::AddEdge(parse_node(fields[0])?, parse_node(fields[1])?),
+            "remove_edge" => GraphOp::RemoveEdge(parse_node(fields[0])?, parse_node(fields[1])?),
+            _ => {
+                let w = fields[2].parse::<W>().map_err(|_| {
+                    IoError::parse_at(
+                        line_no,
+                        column_of(&raw, fields[2]),
+                        format!("invalid weight `{}`", fields[2]),
+                    )
+                })?;
+                GraphOp::AddWeightedEdge(parse_node(fields[0])?, parse_node(fields[1])?, w)
+            }
+        };
+        ops.push(op);
+    }
+    Ok(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, GraphRead, WeightedGraph},
+        history::{replay_weighted, RecordingGraph},
+        impls::undirected_weighted::UndirectedWeightedGraph,
+    };
+
+    #[test]
+    fn test_round_trip() {
+        let mut g = RecordingGraph::weighted(UndirectedWeightedGraph::new());
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_weighted_edge(ids[0], ids[1], 2u32);
+        g.add_weighted_edge(ids[1], ids[2], 7);
+        g.remove_edge(ids[0], ids[1]);
+        g.remove_node(ids[0]);
+
+        let mut out = Vec::new();
+        write_history(g.ops(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("add_node 1\n"));
+        assert!(text.ends_with("remove_edge 1 2\nremove_node 1\n"));
+
+        let ops = read_history::<usize, u32, _>(text.as_bytes()).unwrap();
+        assert_eq!(ops.len(), g.ops().len());
+        let mut copy = UndirectedWeightedGraph::new();
+        let mapping = replay_weighted(&ops, &mut copy).unwrap();
+        assert_eq!((copy.node_count(), copy.edge_count()), (2, 1));
+        assert_eq!(copy.edge_weight(mapping[&2], mapping[&3]), Some(7));
+    }
+
+    #[test]
+    fn test_errors() {
+        let read = |text: &str| read_history::<usize, u32, _>(text.as_bytes());
+        assert_eq!(read("\nadd_node 1\n\n").unwrap(), vec![GraphOp::AddNode(1)]);
+
+        let err = read("add_node 1\n  rename 1").unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(3)));
+        let err = read("add_edge 1").unwrap_err();
+        assert_eq!((err.line(), err.column()), (Some(1), None));
+        let err = read("add_weighted_edge 1 2 x").unwrap_err();
+        assert_eq!(err.column(), Some(23));
+        assert!(read("remove_node a").is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter, Write};
+
+/**
+ * Attribute value written by the JSON exporters.
+ */
+#[derive(PartialEq, Clone, Debug)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+macro_rules! number_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for JsonValue {
+            fn from(n: $t) -> Self {
+                JsonValue::Number(n.into())
+            }
+        })*
+    };
+}
+
+number_from!(f64, f32, i32, u32, i16, u16, i8, u8);
+
+macro_rules! lossy_number_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for JsonValue {
+            /**
+             * Values beyond 2^53 lose precision, as in JavaScript.
+             */
+            fn from(n: $t) -> Self {
+                JsonValue::Number(n as f64)
+            }
+        })*
+    };
+}
+
+lossy_number_from!(i64, u64, isize, usize);
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::String(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::String(s)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            // JSON has no representation for NaN and infinities
+            JsonValue::Number(n) if !n.is_finite() => write!(f, "null"),
+            JsonValue::Number(n) => write!(f, "{}", n),
+            JsonValue::String(s) => write!(f, "{}", quote(s)),
+        }
+    }
+}
+
+/**
+ * Returns `s` as a JSON string literal.
+ */
+pub(crate) fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/**
+ * Formats `(key, value)` pairs as the members of a JSON object, without the braces.
+ */
+pub(crate) fn members<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a JsonValue)>) -> String {
+    let pairs: Vec<_> = pairs
+        .into_iter()
+        .map(|(k, v)| format!("{}:{}", quote(k), v))
+        .collect();
+    pairs.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values() {
+        assert_eq!(JsonValue::from(3u32).to_string(), "3");
+        assert_eq!(JsonValue::from(0.5).to_string(), "0.5");
+        assert_eq!(JsonValue::from(f64::INFINITY).to_string(), "null");
+        assert_eq!(JsonValue::from(None::<u8>).to_string(), "null");
+        assert_eq!(
+            JsonValue::from("a \"b\"\n\u{1}").to_string(),
+            "\"a \\\"b\\\"\\n\\u0001\""
+        );
+    }
+}
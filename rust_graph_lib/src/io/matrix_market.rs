@@ -0,0 +1,316 @@
+use super::{column_of, IoError, LoadedGraph};
+use crate::graph::{Graph, WeightedGraph};
+use std::{collections::HashMap, hash::Hash, io::BufRead, str::FromStr};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Field {
+    Real,
+    Integer,
+    Complex,
+    Pattern,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+    Hermitian,
+}
+
+struct Header {
+    field: Field,
+    symmetry: Symmetry,
+}
+
+fn parse_header(line: &str) -> Result<Header, IoError> {
+    let parts: Vec<_> = line.split_whitespace().collect();
+    if parts.first().map(|p| p.to_ascii_lowercase()) != Some("%%matrixmarket".to_string()) {
+        return Err(IoError::parse(1, "missing `%%MatrixMarket` header"));
+    }
+    let word = |i: usize, what: &str| {
+        parts
+            .get(i)
+            .map(|p| p.to_ascii_lowercase())
+            .ok_or_else(|| IoError::parse(1, format!("missing {} in header", what)))
+    };
+
+    if word(1, "object")? != "matrix" {
+        return Err(IoError::Format("only matrices are supported".to_string()));
+    }
+    if word(2, "format")? != "coordinate" {
+        return Err(IoError::Format(
+            "only the sparse coordinate format is supported".to_string(),
+        ));
+    }
+    let field = match word(3, "field")?.as_str() {
+        "real" => Field::Real,
+        "integer" => Field::Integer,
+        "complex" => Field::Complex,
+        "pattern" => Field::Pattern,
+        other => return Err(IoError::parse(1, format!("unknown field `{}`", other))),
+    };
+    let symmetry = match word(4, "symmetry")?.as_str() {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        "skew-symmetric" => Symmetry::SkewSymmetric,
+        "hermitian" => Symmetry::Hermitian,
+        other => return Err(IoError::parse(1, format!("unknown symmetry `{}`", other))),
+    };
+    Ok(Header { field, symmetry })
+}
+
+/**
+ * Reads a sparse matrix in Matrix Market coordinate format as an unweighted graph,
+ * where each nonzero entry `(i, j)` is an edge `i -> j`.
+ *
+ * Each row and column in an entry gets a node, with its 1-based number as id; rows
+ * and columns without entries have none, so the declared size bounds the ids but
+ * not the memory used, however large it is. Symmetric, skew-symmetric and hermitian matrices only store one
+ * triangle, so each entry off the diagonal also adds the edge `j -> i`. The values
+ * of the entries are ignored.
+ */
+pub fn read_matrix_market<G, R>(reader: R) -> Result<LoadedGraph<G>, IoError>
+where
+    G: Graph + Default,
+    G::Index: Copy + Hash + Eq,
+    R: BufRead,
+{
+    load(reader, |graph: &mut G, _, f, t, _, _| {
+        graph.add_edge(f, t);
+        Ok(())
+    })
+}
+
+/**
+ * Like [`read_matrix_market`], taking the value of each entry as the weight of its
+ * edge.
+ *
+ * The mirrored edges of a skew-symmetric matrix get the opposite value, so the
+ * weights must accept a leading `-`. Pattern matrices have no values and complex
+ * ones have two per entry, so they cannot be read as weighted graphs.
+ */
+pub fn read_weighted_matrix_market<G, R>(reader: R) -> Result<LoadedGraph<G>, IoError>
+where
+    G: WeightedGraph + Default,
+    G::Index: Copy + Hash + Eq,
+    G::Weight: FromStr,
+    R: BufRead,
+{
+    load(reader, |graph: &mut G, header, f, t, value, line| {
+        let value = match header.field {
+            Field::Pattern => {
+                return Err(IoError::Format(
+                    "a pattern matrix has no weights".to_string(),
+                ))
+            }
+            Field::Complex => {
+                return Err(IoError::Format(
+                    "complex weights are not supported".to_string(),
+                ))
+            }
+            Field::Real | Field::Integer => {
+                value.ok_or_else(|| IoError::parse(line, "missing value"))?
+            }
+        };
+        let w = value
+            .parse()
+            .map_err(|_| IoError::parse(line, format!("invalid weight `{}`", value)))?;
+        graph.add_weighted_edge(f, t, w);
+        Ok(())
+    })
+}
+
+fn load<G, R, F>(reader: R, mut add: F) -> Result<LoadedGraph<G>, IoError>
+where
+    G: Graph + Default,
+    G::Index: Copy + Hash + Eq,
+    R: BufRead,
+    F: FnMut(&mut G, &Header, G::Index, G::Index, Option<&str>, usize) -> Result<(), IoError>,
+{
+    let mut lines = reader.lines().enumerate();
+    let header = match lines.next() {
+        Some((_, line)) => parse_header(&line?)?,
+        None => return Err(IoError::parse(1, "missing `%%MatrixMarket` header")),
+    };
+
+    let mut graph = G::default();
+    let mut ids = HashMap::new();
+    let mut expected = None;
+    let mut entries = 0;
+    let mut dim = 0;
+
+    for (i, raw) in lines {
+        let raw = raw?;
+        let line_no = i + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+
+        let Some(nnz) = expected else {
+            let mut size = || {
+                parts
+                    .next()
+                    .and_then(|p| p.parse::<u64>().ok())
+                    .ok_or_else(|| IoError::parse(line_no, "invalid size line"))
+            };
+            let (rows, cols, nnz) = (size()?, size()?, size()?);
+            dim = rows.max(cols);
+            expected = Some(nnz);
+            continue;
+        };
+
+        if entries == nnz {
+            return Err(IoError::parse(
+                line_no,
+                format!("more entries than the {} declared", nnz),
+            ));
+        }
+        let mut node = || {
+            let part = parts
+                .next()
+                .ok_or_else(|| IoError::parse(line_no, "missing entry coordinate"))?;
+            part.parse::<u64>()
+                .ok()
+                .filter(|id| (1..=dim).contains(id))
+                .map(|id| *ids.entry(id).or_insert_with(|| graph.add_node()))
+                .ok_or_else(|| IoError::UndefinedNode {
+                    line: line_no,
+                    column: Some(column_of(&raw, part)),
+                    id: part.to_string(),
+                })
+        };
+        let (f, t) = (node()?, node()?);
+        let value = parts.next();
+
+        add(&mut graph, &header, f, t, value, line_no)?;
+        if header.symmetry != Symmetry::General && f != t {
+            let mirrored = match value {
+                Some(v) if header.symmetry == Symmetry::SkewSymmetric => Some(
+                    v.strip_prefix('-')
+                        .map_or_else(|| format!("-{}", v), str::to_string),
+                ),
+                v => v.map(str::to_string),
+            };
+            add(&mut graph, &header, t, f, mirrored.as_deref(), line_no)?;
+        }
+        entries += 1;
+    }
+
+    match expected {
+        None => Err(IoError::Format("missing size line".to_string())),
+        Some(nnz) if entries < nnz => Err(IoError::Format(format!(
+            "{} entries declared, {} found",
+            nnz, entries
+        ))),
+        Some(_) => Ok(LoadedGraph {
+            graph,
+            ids,
+            labels: HashMap::new(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::GraphRead,
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_read_general() {
+        let input =
+            "%%MatrixMarket matrix coordinate pattern general\n% comment\n4 3 3\n1 2\n2 3\n4 1\n";
+        let loaded: LoadedGraph<AdjListGraph> = read_matrix_market(input.as_bytes()).unwrap();
+        let g = &loaded.graph;
+        let n = |id| loaded.ids[&id];
+
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 3);
+        assert!(g.has_edge(n(1), n(2)) && !g.has_edge(n(2), n(1)));
+        assert!(g.has_edge(n(4), n(1)));
+    }
+
+    #[test]
+    fn test_read_weighted_symmetric() {
+        let input =
+            "%%MatrixMarket matrix coordinate real symmetric\n3 3 3\n1 1 0.5\n2 1 2.5\n3 2 -1\n";
+        let loaded: LoadedGraph<AdjListGraph> = read_matrix_market(input.as_bytes()).unwrap();
+        assert_eq!(loaded.graph.edge_count(), 5);
+
+        let loaded: LoadedGraph<UndirectedWeightedGraph<f64>> =
+            read_weighted_matrix_market(input.as_bytes()).unwrap();
+        let n = |id| loaded.ids[&id];
+        assert_eq!(loaded.graph.edge_weight(n(1), n(2)), Some(2.5));
+        assert_eq!(loaded.graph.edge_weight(n(2), n(3)), Some(-1.0));
+        assert_eq!(loaded.graph.edge_weight(n(1), n(1)), Some(0.5));
+
+        let input = "%%MatrixMarket matrix coordinate pattern general\n2 2 1\n1 2\n";
+        let res = read_weighted_matrix_market::<UndirectedWeightedGraph<f64>, _>(input.as_bytes());
+        assert!(matches!(res, Err(IoError::Format(_))));
+    }
+
+    #[test]
+    fn test_skew_symmetric_weights() {
+        let input = "%%MatrixMarket matrix coordinate integer skew-symmetric\n2 2 1\n2 1 -3\n";
+        let mut seen = Vec::new();
+        load(
+            input.as_bytes(),
+            |_: &mut AdjListGraph, _, f, t, value, _| {
+                seen.push((f, t, value.unwrap().to_string()));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(seen[0].2, "-3");
+        assert_eq!(seen[1].2, "3");
+        assert_eq!((seen[0].0, seen[0].1), (seen[1].1, seen[1].0));
+    }
+
+    #[test]
+    fn test_huge_size() {
+        let input =
+            "%%MatrixMarket matrix coordinate pattern general\n18446744073709551615 1 1\n5 1\n";
+        let loaded: LoadedGraph<AdjListGraph> = read_matrix_market(input.as_bytes()).unwrap();
+        assert_eq!(
+            (loaded.graph.node_count(), loaded.graph.edge_count()),
+            (2, 1)
+        );
+        assert!(loaded.graph.has_edge(loaded.ids[&5], loaded.ids[&1]));
+
+        let input = "%%MatrixMarket matrix coordinate pattern general\n18446744073709551615 1 0\n";
+        let loaded: LoadedGraph<AdjListGraph> = read_matrix_market(input.as_bytes()).unwrap();
+        assert_eq!(loaded.graph.node_count(), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let read = |input: &str| read_matrix_market::<AdjListGraph, _>(input.as_bytes());
+
+        assert!(matches!(
+            read("%%MatrixMarket matrix array real general\n2 2\n"),
+            Err(IoError::Format(_))
+        ));
+        assert!(matches!(
+            read("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 3 1.0\n"),
+            Err(IoError::UndefinedNode {
+                line: 3,
+                column: Some(3),
+                ..
+            })
+        ));
+        assert!(matches!(
+            read("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 2 1.0\n"),
+            Err(IoError::Format(_))
+        ));
+        assert!(matches!(
+            read("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 2 1\n2 1 1\n"),
+            Err(IoError::Parse { line: 4, .. })
+        ));
+        assert!(matches!(read("1 2\n"), Err(IoError::Parse { line: 1, .. })));
+    }
+}
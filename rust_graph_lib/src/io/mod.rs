@@ -0,0 +1,167 @@
+use crate::graph::Graph;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+pub mod binary;
+pub mod csv;
+pub mod cytoscape;
+pub mod d3;
+pub mod dot;
+pub mod gml;
+pub mod history;
+pub mod json;
+pub mod matrix_market;
+pub mod pajek;
+mod stream;
+
+pub use stream::{stream_edges, EdgeStream};
+
+/**
+ * Error returned when reading a graph.
+ *
+ * Errors found in textual formats carry the 1-based line of the problem and, when
+ * it can be pinpointed, the 1-based column (counted in characters).
+ */
+#[derive(Debug)]
+pub enum IoError {
+    /**
+     * The underlying reader failed.
+     */
+    Io(std::io::Error),
+    /**
+     * The input is malformed.
+     */
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        message: String,
+    },
+    /**
+     * An edge references a node that was not defined.
+     */
+    UndefinedNode {
+        line: usize,
+        column: Option<usize>,
+        id: String,
+    },
+    /**
+     * The same node id is defined twice.
+     */
+    DuplicateNode { line: usize, id: String },
+    /**
+     * The input is not valid for a binary format.
+     */
+    Format(String),
+}
+
+impl IoError {
+    pub(crate) fn parse(line: usize, message: impl Into<String>) -> Self {
+        IoError::Parse {
+            line,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn parse_at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        IoError::Parse {
+            line,
+            column: Some(column),
+            message: message.into(),
+        }
+    }
+
+    /**
+     * Returns the line of the problem, if the error points to one.
+     */
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            IoError::Parse { line, .. }
+            | IoError::UndefinedNode { line, .. }
+            | IoError::DuplicateNode { line, .. } => Some(*line),
+            IoError::Io(_) | IoError::Format(_) => None,
+        }
+    }
+
+    /**
+     * Returns the column of the problem, if the error points to one.
+     */
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            IoError::Parse { column, .. } | IoError::UndefinedNode { column, .. } => *column,
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Returns the 1-based column at which `part`, a slice of `line`, starts.
+ */
+pub(crate) fn column_of(line: &str, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+impl Display for IoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let at = |f: &mut Formatter<'_>, line: &usize, column: &Option<usize>| match column {
+            Some(column) => write!(f, "line {}, column {}", line, column),
+            None => write!(f, "line {}", line),
+        };
+        match self {
+            IoError::Io(e) => write!(f, "I/O error: {}", e),
+            IoError::Parse {
+                line,
+                column,
+                message,
+            } => {
+                at(f, line, column)?;
+                write!(f, ": {}", message)
+            }
+            IoError::UndefinedNode { line, column, id } => {
+                at(f, line, column)?;
+                write!(f, ": undefined node `{}`", id)
+            }
+            IoError::DuplicateNode { line, id } => {
+                write!(f, "line {}: duplicate node `{}`", line, id)
+            }
+            IoError::Format(message) => write!(f, "invalid format: {}", message),
+        }
+    }
+}
+
+impl Error for IoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for IoError {
+    fn from(e: std::io::Error) -> Self {
+        IoError::Io(e)
+    }
+}
+
+/**
+ * A graph read from a file, along with the file-level node information.
+ */
+pub struct LoadedGraph<G: Graph> {
+    /**
+     * The graph.
+     */
+    pub graph: G,
+    /**
+     * Maps the node ids used in the file to the indices in the graph.
+     */
+    pub ids: HashMap<u64, G::Index>,
+    /**
+     * Node labels found in the file.
+     */
+    pub labels: HashMap<G::Index, String>,
+}
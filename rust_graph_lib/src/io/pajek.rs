@@ -0,0 +1,274 @@
+use super::{column_of, IoError, LoadedGraph};
+use crate::graph::{Graph, GraphRead};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    io::{BufRead, Write},
+};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Section {
+    None,
+    Vertices,
+    Arcs,
+    Edges,
+    ArcsList,
+    EdgesList,
+}
+
+fn split_label(rest: &str) -> Option<String> {
+    let rest = rest.trim_start();
+    let Some(quoted) = rest.strip_prefix('"') else {
+        return rest.split_whitespace().next().map(str::to_string);
+    };
+    let mut label = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(label),
+            '\\' if chars.as_str().starts_with(['"', '\\']) => label.extend(chars.next()),
+            c => label.push(c),
+        }
+    }
+    None
+}
+
+fn quote_label(label: &str) -> String {
+    format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/**
+ * Reads a graph in Pajek `.net` format.
+ *
+ * `*Arcs` and `*Arcslist` sections add directed edges, while `*Edges` and
+ * `*Edgeslist` sections add each edge in both directions. Anything after the
+ * endpoints of an edge (weights, drawing attributes) is ignored. Within quoted
+ * labels, `\"` and `\\` stand for a quote and a backslash.
+ *
+ * Each vertex gets a node when it is first listed or used by an edge, so `*Vertices`
+ * only bounds the ids, however large its count; vertices declared by the count but
+ * never mentioned have no node.
+ */
+pub fn read_pajek<G, R>(reader: R) -> Result<LoadedGraph<G>, IoError>
+where
+    G: Graph + Default,
+    G::Index: Copy + Hash + Eq,
+    R: BufRead,
+{
+    let mut graph = G::default();
+    let mut ids = HashMap::new();
+    let mut labels = HashMap::new();
+    let mut section = Section::None;
+    let mut listed = HashSet::new();
+    let mut declared = 0;
+
+    for (i, raw) in reader.lines().enumerate() {
+        let raw = raw?;
+        let line_no = i + 1;
+        let line = raw.trim();
+
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('*') {
+            let mut parts = header.split_whitespace();
+            let name = parts.next().unwrap_or_default().to_ascii_lowercase();
+            section = match name.as_str() {
+                "vertices" => Section::Vertices,
+                "arcs" => Section::Arcs,
+                "edges" => Section::Edges,
+                "arcslist" => Section::ArcsList,
+                "edgeslist" => Section::EdgesList,
+                _ => {
+                    return Err(IoError::parse(
+                        line_no,
+                        format!("unknown section `*{}`", name),
+                    ))
+                }
+            };
+
+            if section == Section::Vertices {
+                declared = parts
+                    .next()
+                    .and_then(|c| c.parse().ok())
+                    .ok_or_else(|| IoError::parse(line_no, "missing vertex count"))?;
+            }
+            continue;
+        }
+
+        let mut parts = line.splitn(2, char::is_whitespace);
+        let first = parts.next().unwrap_or_default();
+        let rest = parts.next().unwrap_or_default();
+        let mut node = |id: &str| {
+            id.parse::<u64>()
+                .ok()
+                .filter(|id| (1..=declared).contains(id))
+                .map(|id| *ids.entry(id).or_insert_with(|| graph.add_node()))
+                .ok_or_else(|| IoError::UndefinedNode {
+                    line: line_no,
+                    column: Some(column_of(&raw, id)),
+                    id: id.to_string(),
+                })
+        };
+
+        match section {
+            Section::None => return Err(IoError::parse(line_no, "data outside of any section")),
+            Section::Vertices => {
+                let n = node(first)?;
+                if !listed.insert(n) {
+                    return Err(IoError::DuplicateNode {
+                        line: line_no,
+                        id: first.to_string(),
+                    });
+                }
+                if let Some(label) = split_label(rest) {
+                    labels.insert(n, label);
+                }
+            }
+            Section::Arcs | Section::Edges => {
+                let f = node(first)?;
+                let t = rest
+                    .split_whitespace()
+                    .next()
+                    .ok_or_else(|| IoError::parse(line_no, "missing edge target"))
+                    .and_then(node)?;
+                graph.add_edge(f, t);
+                if section == Section::Edges && f != t {
+                    graph.add_edge(t, f);
+                }
+            }
+            Section::ArcsList | Section::EdgesList => {
+                let f = node(first)?;
+                let targets = rest
+                    .split_whitespace()
+                    .map(&mut node)
+                    .collect::<Result<Vec<_>, _>>()?;
+                for t in targets {
+                    graph.add_edge(f, t);
+                    if section == Section::EdgesList && f != t {
+                        graph.add_edge(t, f);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(LoadedGraph { graph, ids, labels })
+}
+
+/**
+ * Writes a graph in Pajek `.net` format, with all edges in an `*Arcs` section.
+ *
+ * Vertices are numbered from 1 and labelled with their index, quoting any `"` and
+ * `\\` in it with a backslash.
+ */
+pub fn write_pajek<G, W>(graph: &G, mut writer: W) -> std::io::Result<()>
+where
+    G: GraphRead,
+    G::Index: Copy + Hash + Eq + Display,
+    W: Write,
+{
+    let mut nodes: Vec<_> = graph.iter_nodes().collect();
+    nodes.sort();
+    let ids: HashMap<_, _> = nodes.iter().enumerate().map(|(i, &n)| (n, i + 1)).collect();
+
+    writeln!(writer, "*Vertices {}", nodes.len())?;
+    for n in &nodes {
+        writeln!(writer, "{} {}", ids[n], quote_label(&n.to_string()))?;
+    }
+
+    let mut edges: Vec<_> = graph.iter_edges().collect();
+    edges.sort();
+    writeln!(writer, "*Arcs")?;
+    for (f, t) in edges {
+        writeln!(writer, "{} {}", ids[&f], ids[&t])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_read_sections() {
+        let input = "% sample\n*Vertices 4\n1 \"first node\"\n2 b 0.1 0.2\n*Arcs\n1 2 1.5\n*Edges\n2 3\n*Arcslist\n4 1 2\n";
+        let loaded: LoadedGraph<AdjListGraph> = read_pajek(input.as_bytes()).unwrap();
+        let g = &loaded.graph;
+        let n = |id| loaded.ids[&id];
+
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 5);
+        assert!(g.has_edge(n(1), n(2)));
+        assert!(!g.has_edge(n(2), n(1)));
+        assert!(g.has_edge(n(2), n(3)) && g.has_edge(n(3), n(2)));
+        assert!(g.has_edge(n(4), n(1)) && g.has_edge(n(4), n(2)));
+        assert_eq!(loaded.labels[&n(1)], "first node");
+        assert_eq!(loaded.labels[&n(2)], "b");
+    }
+
+    #[test]
+    fn test_undefined_node() {
+        let input = "*Vertices 2\n*Arcs\n1 3\n";
+        let res = read_pajek::<AdjListGraph, _>(input.as_bytes());
+        assert!(matches!(
+            res,
+            Err(IoError::UndefinedNode {
+                line: 3,
+                column: Some(3),
+                ..
+            })
+        ));
+
+        let input = "*Vertices 2\n1 a\n 01 b\n";
+        let res = read_pajek::<AdjListGraph, _>(input.as_bytes());
+        assert!(matches!(res, Err(IoError::DuplicateNode { line: 3, .. })));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id3, id1);
+
+        let mut out = Vec::new();
+        write_pajek(&g, &mut out).unwrap();
+        let loaded: LoadedGraph<AdjListGraph> = read_pajek(out.as_slice()).unwrap();
+
+        assert_eq!(loaded.graph.node_count(), 3);
+        assert_eq!(loaded.graph.edge_count(), 2);
+        assert!(loaded.graph.has_edge(loaded.ids[&1], loaded.ids[&2]));
+        assert!(loaded.graph.has_edge(loaded.ids[&3], loaded.ids[&1]));
+    }
+
+    #[test]
+    fn test_quoted_labels() {
+        let names = ["say \"hi\"", "back\\slash\\", "plain"];
+        let g: BTreeMap<_, _> = [(names[0], vec![names[1]]), (names[2], vec![])].into();
+
+        let mut out = Vec::new();
+        write_pajek(&g, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("\"back\\\\slash\\\\\"\n"));
+        let loaded: LoadedGraph<AdjListGraph> = read_pajek(text.as_bytes()).unwrap();
+        let mut labels: Vec<_> = loaded.labels.values().map(String::as_str).collect();
+        labels.sort();
+        assert_eq!(labels, ["back\\slash\\", "plain", "say \"hi\""]);
+        assert_eq!(loaded.graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_huge_vertex_count() {
+        let input = "*Vertices 18446744073709551615\n*Arcs\n7 18446744073709551615\n";
+        let loaded: LoadedGraph<AdjListGraph> = read_pajek(input.as_bytes()).unwrap();
+        assert_eq!(loaded.graph.node_count(), 2);
+        assert!(loaded.graph.has_edge(loaded.ids[&7], loaded.ids[&u64::MAX]));
+    }
+}
@@ -0,0 +1,139 @@
+use super::{column_of, IoError};
+use std::io::BufRead;
+
+/**
+ * Iterator over the edges of a textual edge list, returned by [`stream_edges`].
+ */
+pub struct EdgeStream<R: BufRead> {
+    reader: R,
+    buf: String,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> EdgeStream<R> {
+    /**
+     * Returns the number of lines read so far.
+     */
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    fn parse_line(&self) -> Option<Result<(u64, u64), IoError>> {
+        let content = self.buf.trim();
+        if content.is_empty() || content.starts_with('#') || content.starts_with('%') {
+            return None;
+        }
+
+        let mut parts = content
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty());
+        let mut node = |what| {
+            let part = parts
+                .next()
+                .ok_or_else(|| IoError::parse(self.line, format!("missing {} node", what)))?;
+            part.parse::<u64>().map_err(|_| {
+                let column = column_of(&self.buf, part);
+                IoError::parse_at(self.line, column, format!("invalid node id `{}`", part))
+            })
+        };
+
+        Some(node("source").and_then(|f| node("target").map(|t| (f, t))))
+    }
+}
+
+impl<R: BufRead> Iterator for EdgeStream<R> {
+    type Item = Result<(u64, u64), IoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    if let Some(edge) = self.parse_line() {
+                        return Some(edge);
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+        None
+    }
+}
+
+/**
+ * Lazily parses an edge list with one `source target` pair per line.
+ *
+ * Node ids are unsigned integers separated by whitespace or commas; anything after
+ * the target (e.g. a weight) is ignored, as are empty lines and lines starting with
+ * `#` or `%`. Only one line is kept in memory at a time, so files of any size can be
+ * fed to [`Graph::extend_from_stream`](crate::graph::Graph::extend_from_stream).
+ */
+pub fn stream_edges<R: BufRead>(reader: R) -> EdgeStream<R> {
+    EdgeStream {
+        reader,
+        buf: String::new(),
+        line: 0,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::{Graph, GraphRead},
+        impls::adj_list::AdjListGraph,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_stream_edges() {
+        let input = "# comment\n1 2\n\n2,3 0.5\n3\t1\n";
+        let edges: Vec<_> = stream_edges(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(edges, vec![(1, 2), (2, 3), (3, 1)]);
+    }
+
+    #[test]
+    fn test_error_position() {
+        let input = "1 2\n2 x\n";
+        let mut it = stream_edges(input.as_bytes());
+        assert!(it.next().unwrap().is_ok());
+        assert!(matches!(
+            it.next(),
+            Some(Err(IoError::Parse {
+                line: 2,
+                column: Some(3),
+                ..
+            }))
+        ));
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_extend_from_stream() {
+        let input = "1 2\n2 3\n3 1\n";
+        let mut g = AdjListGraph::new();
+        let mut ids = HashMap::new();
+        let mut progress = Vec::new();
+
+        let count = g
+            .extend_from_stream(stream_edges(input.as_bytes()), &mut ids, |n| {
+                progress.push(n)
+            })
+            .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(progress, vec![1, 2, 3]);
+        assert_eq!(g.node_count(), 3);
+        assert!(g.has_edge(ids[&1], ids[&2]));
+        assert!(g.has_edge(ids[&3], ids[&1]));
+    }
+}
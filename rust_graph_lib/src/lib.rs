@@ -1,3 +1,19 @@
 pub mod algorithms;
+pub mod format;
 pub mod graph;
+pub mod history;
 pub mod impls;
+pub mod invariant;
+pub mod io;
+pub mod memory;
+pub mod observer;
+pub mod ops;
+pub mod property;
+pub mod service;
+#[cfg(feature = "spectral")]
+pub mod spectral;
+pub mod stats;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod utils;
+pub mod views;
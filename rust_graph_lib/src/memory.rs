@@ -0,0 +1,80 @@
+use std::{
+    fmt::{Display, Formatter},
+    mem,
+};
+
+/**
+ * Estimated memory footprint of a graph, in bytes.
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct MemoryReport {
+    /**
+     * Bytes used to store the nodes.
+     */
+    pub nodes: usize,
+    /**
+     * Bytes used to store the adjacency information (edges).
+     */
+    pub adjacency: usize,
+    /**
+     * Bytes used by auxiliary mappings, such as index or payload lookups.
+     */
+    pub mappings: usize,
+    /**
+     * Bytes used by the graph struct itself.
+     */
+    pub other: usize,
+}
+
+impl MemoryReport {
+    /**
+     * Returns the total estimated bytes.
+     */
+    pub fn total(&self) -> usize {
+        self.nodes + self.adjacency + self.mappings + self.other
+    }
+}
+
+impl Display for MemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "nodes: {} B, adjacency: {} B, mappings: {} B, other: {} B, total: {} B",
+            self.nodes,
+            self.adjacency,
+            self.mappings,
+            self.other,
+            self.total()
+        )
+    }
+}
+
+/**
+ * Trait for types able to estimate their memory usage.
+ */
+pub trait MemoryUsage {
+    /**
+     * Returns an estimate of the bytes used, including heap allocations.
+     */
+    fn memory_usage(&self) -> MemoryReport;
+}
+
+/**
+ * Estimates the heap bytes of a hash table holding `capacity` entries of type `T`,
+ * following the layout of the standard library implementation (one control byte per bucket).
+ */
+pub(crate) fn hash_table_bytes<T>(capacity: usize) -> usize {
+    if capacity == 0 {
+        return 0;
+    }
+
+    let buckets = if capacity < 4 {
+        4
+    } else if capacity < 8 {
+        8
+    } else {
+        (capacity * 8 / 7).next_power_of_two()
+    };
+    // control bytes are padded by one SIMD group
+    buckets * mem::size_of::<T>() + buckets + 16
+}
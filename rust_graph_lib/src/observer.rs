@@ -0,0 +1,283 @@
+use crate::graph::{EdgeIterator, Graph, GraphRead, IncidentIterator, NodeIterator, WeightedGraph};
+use std::{
+    cell::RefCell,
+    iter::Sum,
+    rc::{Rc, Weak},
+};
+
+/**
+ * Mutation of a graph, as reported to a [`GraphObserver`].
+ */
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GraphEvent<I> {
+    NodeAdded(I),
+    /**
+     * A node was removed, along with all its incident edges (which are not reported separately).
+     */
+    NodeRemoved(I),
+    EdgeAdded(I, I),
+    EdgeRemoved(I, I),
+    /**
+     * The weight of an edge already in the graph was changed.
+     */
+    EdgeWeightChanged(I, I),
+}
+
+/**
+ * Receives the mutations performed on an [`ObservedGraph`].
+ */
+pub trait GraphObserver<I> {
+    fn notify(&mut self, event: &GraphEvent<I>);
+}
+
+type ObserverRef<I> = Weak<RefCell<dyn GraphObserver<I>>>;
+
+/**
+ * Wrapper around a [`Graph`] notifying the subscribed observers of every mutation.
+ *
+ * Observers are held weakly, so dropping the last [`Rc`] to an observer unsubscribes it.
+ */
+pub struct ObservedGraph<G: Graph> {
+    graph: G,
+    observers: Vec<ObserverRef<G::Index>>,
+}
+
+impl<G: Graph> ObservedGraph<G> {
+    /**
+     * Wraps the graph.
+     */
+    pub fn new(graph: G) -> Self {
+        ObservedGraph {
+            graph,
+            observers: Vec::new(),
+        }
+    }
+
+    /**
+     * Subscribes an observer to the mutations of the graph.
+     */
+    pub fn subscribe<O: GraphObserver<G::Index> + 'static>(&mut self, observer: &Rc<RefCell<O>>) {
+        let observer = Rc::downgrade(observer);
+        self.observers.push(observer);
+    }
+
+    /**
+     * Returns the wrapped graph.
+     */
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /**
+     * Unwraps the graph, dropping the subscriptions.
+     */
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+
+    fn emit(&mut self, event: GraphEvent<G::Index>) {
+        self.observers.retain(|o| match o.upgrade() {
+            Some(o) => {
+                o.borrow_mut().notify(&event);
+                true
+            }
+            None => false,
+        });
+    }
+}
+
+impl<G: Graph> GraphRead for ObservedGraph<G>
+where
+    G::Index: Copy,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.has_edge(f, t)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        self.graph.iter_adj(n)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        self.graph.iter_edges()
+    }
+
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>> {
+        self.graph.iter_incident(n)
+    }
+}
+
+impl<G: Graph> Graph for ObservedGraph<G>
+where
+    G::Index: Copy,
+{
+    fn add_node(&mut self) -> Self::Index {
+        let n = self.graph.add_node();
+        self.emit(GraphEvent::NodeAdded(n));
+        n
+    }
+
+    fn add_edge(&mut self, f: Self::Index, t: Self::Index) {
+        self.graph.add_edge(f, t);
+        self.emit(GraphEvent::EdgeAdded(f, t));
+    }
+
+    fn remove_node(&mut self, n: Self::Index) {
+        let present = self.graph.iter_adj(n).is_some();
+        self.graph.remove_node(n);
+        if present {
+            self.emit(GraphEvent::NodeRemoved(n));
+        }
+    }
+
+    fn remove_edge(&mut self, f: Self::Index, t: Self::Index) {
+        let present = self.graph.has_edge(f, t);
+        self.graph.remove_edge(f, t);
+        if present {
+            self.emit(GraphEvent::EdgeRemoved(f, t));
+        }
+    }
+}
+
+impl<G: WeightedGraph> WeightedGraph for ObservedGraph<G>
+where
+    G::Index: Copy,
+{
+    type Weight = G::Weight;
+
+    fn add_weighted_edge(&mut self, f: Self::Index, t: Self::Index, w: Self::Weight) {
+        let present = self.graph.has_edge(f, t);
+        self.graph.add_weighted_edge(f, t, w);
+        self.emit(if present {
+            GraphEvent::EdgeWeightChanged(f, t)
+        } else {
+            GraphEvent::EdgeAdded(f, t)
+        });
+    }
+
+    fn edge_weight(&self, f: Self::Index, t: Self::Index) -> Option<Self::Weight> {
+        self.graph.edge_weight(f, t)
+    }
+
+    fn set_edge_weight(&mut self, f: Self::Index, t: Self::Index, w: Self::Weight) -> bool {
+        let changed = self.graph.set_edge_weight(f, t, w);
+        if changed {
+            self.emit(GraphEvent::EdgeWeightChanged(f, t));
+        }
+        changed
+    }
+
+    fn out_strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Weight: Sum,
+    {
+        self.graph.out_strength(n)
+    }
+
+    fn in_strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Weight: Sum,
+    {
+        self.graph.in_strength(n)
+    }
+
+    fn strength(&self, n: Self::Index) -> Option<Self::Weight>
+    where
+        Self::Weight: Sum,
+    {
+        self.graph.strength(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph};
+
+    #[derive(Default)]
+    struct Recorder(Vec<GraphEvent<<AdjListGraph as GraphRead>::Index>>);
+
+    impl GraphObserver<<AdjListGraph as GraphRead>::Index> for Recorder {
+        fn notify(&mut self, event: &GraphEvent<<AdjListGraph as GraphRead>::Index>) {
+            self.0.push(*event);
+        }
+    }
+
+    #[test]
+    fn test_events() {
+        let mut g = ObservedGraph::new(AdjListGraph::new());
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        g.subscribe(&recorder);
+
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.add_edge(id1, id2);
+        g.remove_edge(id2, id1);
+        g.remove_edge(id1, id2);
+        g.remove_node(id2);
+        g.remove_node(id2);
+
+        assert_eq!(
+            recorder.borrow().0,
+            vec![
+                GraphEvent::NodeAdded(id1),
+                GraphEvent::NodeAdded(id2),
+                GraphEvent::EdgeAdded(id1, id2),
+                GraphEvent::EdgeRemoved(id1, id2),
+                GraphEvent::NodeRemoved(id2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weight_changes() {
+        let mut g = ObservedGraph::new(UndirectedWeightedGraph::new());
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        g.subscribe(&recorder);
+
+        g.add_weighted_edge(id1, id2, 3u32);
+        g.add_weighted_edge(id1, id2, 4);
+        assert!(g.set_edge_weight(id2, id1, 5));
+        assert!(g.update_edge_weight(id1, id2, |w| w * 2));
+        assert!(!g.set_edge_weight(id1, id1, 1));
+        assert!(!g.update_edge_weight(id1, id1, |w| w + 1));
+        assert_eq!(g.edge_weight(id1, id2), Some(10));
+
+        assert_eq!(
+            recorder.borrow().0,
+            vec![
+                GraphEvent::EdgeAdded(id1, id2),
+                GraphEvent::EdgeWeightChanged(id1, id2),
+                GraphEvent::EdgeWeightChanged(id2, id1),
+                GraphEvent::EdgeWeightChanged(id1, id2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dropped_observer_is_unsubscribed() {
+        let mut g = ObservedGraph::new(AdjListGraph::new());
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        g.subscribe(&recorder);
+        drop(recorder);
+
+        g.add_node();
+        assert!(g.observers.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{graph::GraphRead, impls::keyed::KeyedGraph, utils::SlotKey};
+use std::{collections::HashMap, hash::Hash, ops::Add};
+
+/**
+ * Summary of a graph where the nodes sharing a key are collapsed into a supernode,
+ * returned by [`aggregate_by`] and [`aggregate_weighted_by`].
+ */
+pub struct Aggregation<K, I, W> {
+    /**
+     * The summary graph, with a supernode for each key holding the nodes it stands
+     * for, in ascending order.
+     */
+    pub graph: KeyedGraph<K, Vec<I>>,
+    /**
+     * The total weight of the edges between each pair of supernodes.
+     */
+    pub weights: HashMap<(SlotKey, SlotKey), W>,
+    /**
+     * The supernode of each node of the original graph.
+     */
+    pub membership: HashMap<I, SlotKey>,
+}
+
+impl<K: Hash + Eq + Clone, I, W: Copy> Aggregation<K, I, W> {
+    /**
+     * Returns the total weight of the edges from the nodes with key `f` to the nodes
+     * with key `t`, if there is any such edge.
+     */
+    pub fn weight(&self, f: &K, t: &K) -> Option<W> {
+        let (f, t) = (self.graph.node_index(f)?, self.graph.node_index(t)?);
+        self.weights.get(&(f, t)).copied()
+    }
+}
+
+/**
+ * Collapses the nodes with the same `key(n)` into a supernode, with an edge between
+ * two supernodes weighing the number of edges joining their nodes.
+ *
+ * Edges between nodes with the same key become a loop on their supernode.
+ * Supernodes are created in ascending order of their smallest node.
+ */
+pub fn aggregate_by<G, K, F>(graph: &G, key: F) -> Aggregation<K, G::Index, usize>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    K: Hash + Eq + Clone,
+    F: FnMut(G::Index) -> K,
+{
+    aggregate_weighted_by(graph, key, |_, _| 1)
+}
+
+/**
+ * Like [`aggregate_by`], where the edge `(f, t)` weighs `weight(f, t)` and the edges
+ * between two supernodes weigh the sum of the weights of the edges they stand for.
+ */
+pub fn aggregate_weighted_by<G, K, W, F, WF>(
+    graph: &G,
+    mut key: F,
+    weight: WF,
+) -> Aggregation<K, G::Index, W>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    K: Hash + Eq + Clone,
+    W: Copy + Add<Output = W>,
+    F: FnMut(G::Index) -> K,
+    WF: Fn(G::Index, G::Index) -> W,
+{
+    let mut nodes: Vec<_> = graph.iter_nodes().collect();
+    nodes.sort();
+
+    let mut summary = KeyedGraph::new();
+    let mut membership = HashMap::with_capacity(nodes.len());
+    for n in nodes {
+        let k = key(n);
+        let s = match summary.node_index(&k) {
+            Some(s) => s,
+            None => summary.insert_node(k, Vec::new()),
+        };
+        summary.payload_mut(s).unwrap().push(n);
+        membership.insert(n, s);
+    }
+
+    let mut weights = HashMap::new();
+    for (f, t) in graph.iter_edges() {
+        let (Some(&sf), Some(&st)) = (membership.get(&f), membership.get(&t)) else {
+            continue;
+        };
+        let w = weight(f, t);
+        weights
+            .entry((sf, st))
+            .and_modify(|total| *total = *total + w)
+            .or_insert(w);
+        summary.add_edge(sf, st);
+    }
+
+    Aggregation {
+        graph: summary,
+        weights,
+        membership,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_aggregate_by() {
+        // a call graph of five functions in the modules "a" and "b"
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        let module = |n| if ids[..3].contains(&n) { "a" } else { "b" };
+        for (f, t) in [(0, 1), (1, 2), (0, 3), (2, 4), (3, 4), (4, 1)] {
+            g.add_edge(ids[f], ids[t]);
+        }
+
+        let summary = aggregate_by(&g, module);
+        assert_eq!(summary.graph.node_count(), 2);
+        assert_eq!(summary.graph.edge_count(), 4);
+        assert_eq!(summary.weight(&"a", &"a"), Some(2));
+        assert_eq!(summary.weight(&"a", &"b"), Some(2));
+        assert_eq!(summary.weight(&"b", &"a"), Some(1));
+        assert_eq!(summary.weight(&"b", &"c"), None);
+
+        let b = summary.graph.node_index(&"b").unwrap();
+        assert_eq!(summary.graph.payload(b), Some(&vec![ids[3], ids[4]]));
+        assert_eq!(summary.membership[&ids[4]], b);
+
+        let weighted =
+            aggregate_weighted_by(&g, module, |f, _| if f == ids[0] { 0.5 } else { 2.0 });
+        assert_eq!(weighted.weight(&"a", &"a"), Some(2.5));
+        assert_eq!(weighted.weight(&"b", &"b"), Some(2.0));
+    }
+}
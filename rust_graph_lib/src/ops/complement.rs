@@ -0,0 +1,56 @@
+use crate::{
+    graph::{Graph, GraphRead},
+    impls::inc_matrix::IncMatrixGraph,
+};
+
+/**
+ * Returns the complement of the graph: the graph on the same nodes with an edge `a -> b`
+ * for each pair of distinct nodes not connected by `a -> b` in the original graph.
+ *
+ * The result is dense, so this takes `O(n²)` time and memory. Nodes are renumbered
+ * from 1 in ascending order of their original index.
+ */
+pub fn complement<G>(graph: &G) -> IncMatrixGraph
+where
+    G: GraphRead,
+    G::Index: Copy,
+{
+    let mut nodes: Vec<_> = graph.iter_nodes().collect();
+    nodes.sort();
+
+    let mut result = IncMatrixGraph::with_nodes(nodes.len());
+    let ids: Vec<_> = result.iter_nodes().collect();
+    for (i, &f) in nodes.iter().enumerate() {
+        for (j, &t) in nodes.iter().enumerate() {
+            if i != j && !graph.has_edge(f, t) {
+                result.add_edge(ids[i], ids[j]);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    #[test]
+    fn test_complement() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id3);
+
+        let c = complement(&g);
+        assert_eq!(c.node_count(), 3);
+        assert_eq!(c.edge_count(), 4);
+        assert!(!c.has_edge(id1, id2) && !c.has_edge(id2, id3));
+        assert!(c.has_edge(id2, id1) && c.has_edge(id1, id3));
+        assert!(c.has_edge(id3, id1) && c.has_edge(id3, id2));
+        assert!(!c.has_edge(id1, id1));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{graph::Graph, utils::UnionFind};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/**
+ * Contracts all the given edges at once, turning the graph into its quotient graph.
+ *
+ * Each group of nodes connected by the contracted edges is merged into its smallest node,
+ * which inherits the edges of the whole group; edges inside a group are dropped.
+ * Runs in `O((n + m) α(n))` regardless of the number of contracted edges.
+ *
+ * Returns the mapping from each node of the original graph to its node in the quotient.
+ */
+pub fn contract_edges<G, I>(graph: &mut G, edges: I) -> HashMap<G::Index, G::Index>
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+    I: IntoIterator<Item = (G::Index, G::Index)>,
+{
+    let mut uf = UnionFind::new();
+    for (f, t) in edges {
+        if graph.iter_adj(f).is_some() && graph.iter_adj(t).is_some() {
+            uf.union(f, t);
+        }
+    }
+
+    // representatives are the smallest node of each group, independently of the union order
+    let mut smallest: HashMap<G::Index, G::Index> = HashMap::new();
+    let nodes: Vec<_> = graph.iter_nodes().collect();
+    for &n in &nodes {
+        let root = uf.find(n);
+        let min = smallest.entry(root).or_insert(n);
+        if n < *min {
+            *min = n;
+        }
+    }
+    let mapping: HashMap<_, _> = nodes.iter().map(|&n| (n, smallest[&uf.find(n)])).collect();
+
+    let moved: HashSet<_> = graph
+        .iter_edges()
+        .filter(|(f, t)| mapping[f] != *f || mapping[t] != *t)
+        .map(|(f, t)| (mapping[&f], mapping[&t]))
+        .filter(|(f, t)| f != t)
+        .collect();
+
+    for (&n, &r) in &mapping {
+        if n != r {
+            graph.remove_node(n);
+        }
+    }
+    for (f, t) in moved {
+        if !graph.has_edge(f, t) {
+            graph.add_edge(f, t);
+        }
+    }
+
+    mapping
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::GraphRead, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_contract_edges() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[2], ids[3]);
+        g.add_edge(ids[3], ids[4]);
+        g.add_edge(ids[4], ids[0]);
+
+        let mapping = contract_edges(&mut g, [(ids[2], ids[1]), (ids[3], ids[4])]);
+
+        assert_eq!(mapping[&ids[2]], ids[1]);
+        assert_eq!(mapping[&ids[4]], ids[3]);
+        assert_eq!(mapping[&ids[0]], ids[0]);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 3);
+        assert!(g.has_edge(ids[0], ids[1]));
+        assert!(g.has_edge(ids[1], ids[3]));
+        assert!(g.has_edge(ids[3], ids[0]));
+    }
+}
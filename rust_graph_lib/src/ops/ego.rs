@@ -0,0 +1,86 @@
+use super::induced_subgraph;
+use crate::{
+    algorithms::{Dijkstra, Measure},
+    graph::GraphRead,
+    impls::{adj_list::Index, adj_vec::AdjVecGraph},
+};
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Returns the subgraph induced by the nodes at most `radius` edges away from `center`,
+ * following the edges in their direction, with the mapping from each of those nodes
+ * to its node in the subgraph.
+ *
+ * Nodes are renumbered from 1 in ascending order of their original index. If `center`
+ * is not in the graph, the subgraph is empty.
+ */
+pub fn ego_graph<G>(
+    graph: &G,
+    center: G::Index,
+    radius: usize,
+) -> (AdjVecGraph, HashMap<G::Index, Index>)
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    ego_graph_weighted(graph, center, radius, |_, _| 1)
+}
+
+/**
+ * Like [`ego_graph`], keeping the nodes whose shortest path from `center` costs at
+ * most `radius`, where the edge `(f, t)` costs `weight(f, t)`.
+ */
+pub fn ego_graph_weighted<G, W, F>(
+    graph: &G,
+    center: G::Index,
+    radius: W,
+    weight: F,
+) -> (AdjVecGraph, HashMap<G::Index, Index>)
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    W: Measure,
+    F: Fn(G::Index, G::Index) -> W,
+{
+    if graph.iter_adj(center).is_none() {
+        return (AdjVecGraph::new(), HashMap::new());
+    }
+
+    let tree = Dijkstra::with_weights(graph, weight).run_within(center, radius);
+    induced_subgraph(graph, tree.iter_reachable().map(|(n, _)| n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_ego_graph() {
+        // a path 0 -> 1 -> 2 -> 3 with a chord 2 -> 0 and a node pointing at the center
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[2], ids[3]);
+        g.add_edge(ids[2], ids[0]);
+        g.add_edge(ids[4], ids[0]);
+
+        let (ego, mapping) = ego_graph(&g, ids[0], 2);
+        assert_eq!(ego.node_count(), 3);
+        assert_eq!(ego.edge_count(), 3);
+        assert!(ego.has_edge(mapping[&ids[2]], mapping[&ids[0]]));
+        assert!(!mapping.contains_key(&ids[3]) && !mapping.contains_key(&ids[4]));
+
+        let (ego, _) = ego_graph(&g, ids[0], 0);
+        assert_eq!((ego.node_count(), ego.edge_count()), (1, 0));
+
+        let (ego, mapping) =
+            ego_graph_weighted(&g, ids[0], 3u32, |f, _| if f == ids[0] { 3 } else { 1 });
+        assert_eq!(ego.node_count(), 2);
+        assert!(ego.has_edge(mapping[&ids[0]], mapping[&ids[1]]));
+
+        let (ego, mapping) = ego_graph(&g, ids[4].next(), 5);
+        assert!(ego.is_empty() && mapping.is_empty());
+    }
+}
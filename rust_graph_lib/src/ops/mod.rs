@@ -0,0 +1,16 @@
+mod aggregate;
+mod complement;
+mod contract;
+mod ego;
+pub mod product;
+mod rewire;
+mod sample;
+mod subgraph;
+
+pub use aggregate::{aggregate_by, aggregate_weighted_by, Aggregation};
+pub use complement::complement;
+pub use contract::contract_edges;
+pub use ego::{ego_graph, ego_graph_weighted};
+pub use rewire::rewire_preserving_degrees;
+pub use sample::{reservoir_sample_edges, sample_edges, sample_nodes, EdgeReservoir};
+pub use subgraph::induced_subgraph;
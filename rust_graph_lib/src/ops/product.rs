@@ -0,0 +1,134 @@
+use crate::{
+    graph::{Graph, GraphRead},
+    utils::DoubleMapping,
+};
+use std::hash::Hash;
+
+/**
+ * Product graph along with the mapping from pairs of nodes of the factors to its nodes.
+ */
+pub type Product<G, I1, I2> = (G, DoubleMapping<(I1, I2), <G as GraphRead>::Index>);
+
+fn product_nodes<G1, G2, G>(g1: &G1, g2: &G2) -> Product<G, G1::Index, G2::Index>
+where
+    G1: GraphRead,
+    G2: GraphRead,
+    G: Graph + Default,
+    G1::Index: Hash + Copy,
+    G2::Index: Hash + Copy,
+    G::Index: Hash + Copy,
+{
+    let mut graph = G::default();
+    let mut mapping = DoubleMapping::new();
+    for u in g1.iter_nodes() {
+        for v in g2.iter_nodes() {
+            mapping.insert((u, v), graph.add_node());
+        }
+    }
+    (graph, mapping)
+}
+
+/**
+ * Builds the cartesian product of two graphs.
+ *
+ * There is a node for each pair `(u, v)` and an edge `(u, v) -> (u', v)` for each edge
+ * `u -> u'` of `g1`, plus an edge `(u, v) -> (u, v')` for each edge `v -> v'` of `g2`.
+ * Returns the product along with the mapping from pairs to its nodes.
+ */
+pub fn cartesian<G1, G2, G>(g1: &G1, g2: &G2) -> Product<G, G1::Index, G2::Index>
+where
+    G1: GraphRead,
+    G2: GraphRead,
+    G: Graph + Default,
+    G1::Index: Hash + Copy,
+    G2::Index: Hash + Copy,
+    G::Index: Hash + Copy,
+{
+    let (mut graph, mapping) = product_nodes::<G1, G2, G>(g1, g2);
+    let node = |u, v| *mapping.get_left(&(u, v)).unwrap();
+
+    for (u, u2) in g1.iter_edges() {
+        for v in g2.iter_nodes() {
+            graph.add_edge(node(u, v), node(u2, v));
+        }
+    }
+    for (v, v2) in g2.iter_edges() {
+        for u in g1.iter_nodes() {
+            graph.add_edge(node(u, v), node(u, v2));
+        }
+    }
+    (graph, mapping)
+}
+
+/**
+ * Builds the tensor (categorical) product of two graphs.
+ *
+ * There is a node for each pair `(u, v)` and an edge `(u, v) -> (u', v')` for each pair
+ * of edges `u -> u'` of `g1` and `v -> v'` of `g2`.
+ * Returns the product along with the mapping from pairs to its nodes.
+ */
+pub fn tensor<G1, G2, G>(g1: &G1, g2: &G2) -> Product<G, G1::Index, G2::Index>
+where
+    G1: GraphRead,
+    G2: GraphRead,
+    G: Graph + Default,
+    G1::Index: Hash + Copy,
+    G2::Index: Hash + Copy,
+    G::Index: Hash + Copy,
+{
+    let (mut graph, mapping) = product_nodes::<G1, G2, G>(g1, g2);
+    let node = |u, v| *mapping.get_left(&(u, v)).unwrap();
+
+    for (u, u2) in g1.iter_edges() {
+        for (v, v2) in g2.iter_edges() {
+            graph.add_edge(node(u, v), node(u2, v2));
+        }
+    }
+    (graph, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    fn edge() -> AdjListGraph {
+        let mut g = AdjListGraph::new();
+        let a = g.add_node();
+        let b = g.add_node();
+        g.add_edge(a, b);
+        g
+    }
+
+    #[test]
+    fn test_cartesian() {
+        let (g1, g2) = (edge(), edge());
+        let (p, m): Product<AdjListGraph, _, _> = cartesian(&g1, &g2);
+        let (a, b) = {
+            let mut n: Vec<_> = g1.iter_nodes().collect();
+            n.sort();
+            (n[0], n[1])
+        };
+        let node = |u, v| *m.get_left(&(u, v)).unwrap();
+
+        assert_eq!(p.node_count(), 4);
+        assert_eq!(p.edge_count(), 4);
+        assert!(p.has_edge(node(a, a), node(b, a)));
+        assert!(p.has_edge(node(a, a), node(a, b)));
+        assert!(p.has_edge(node(b, a), node(b, b)));
+        assert!(!p.has_edge(node(a, a), node(b, b)));
+    }
+
+    #[test]
+    fn test_tensor() {
+        let (g1, g2) = (edge(), edge());
+        let (p, m): Product<AdjListGraph, _, _> = tensor(&g1, &g2);
+        let mut n: Vec<_> = g1.iter_nodes().collect();
+        n.sort();
+        let node = |u, v| *m.get_left(&(u, v)).unwrap();
+
+        assert_eq!(p.node_count(), 4);
+        assert_eq!(p.edge_count(), 1);
+        assert!(p.has_edge(node(n[0], n[0]), node(n[1], n[1])));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{graph::Graph, utils::Rng};
+use std::hash::Hash;
+
+/**
+ * Randomizes the graph with double edge swaps, which keep the in and out degree of
+ * every node, to build null models with the same degree sequence as a real network.
+ *
+ * Each swap picks two edges `a -> b` and `c -> d` uniformly and replaces them with
+ * `a -> d` and `c -> b`, unless that would create a self loop or an edge already in
+ * the graph. Makes up to `swaps` swaps, giving up after `10 * swaps` attempts, and
+ * returns how many were made; the result is reproducible for a given `seed`.
+ *
+ * Edges are treated as directed, and the new ones are added with [`Graph::add_edge`].
+ */
+pub fn rewire_preserving_degrees<G>(graph: &mut G, swaps: usize, seed: u64) -> usize
+where
+    G: Graph,
+    G::Index: Hash + Copy,
+{
+    let mut edges: Vec<_> = graph.iter_edges().collect();
+    if edges.len() < 2 {
+        return 0;
+    }
+    edges.sort();
+
+    let mut rng = Rng::new(seed);
+    let mut done = 0;
+    for _ in 0..swaps.saturating_mul(10) {
+        if done == swaps {
+            break;
+        }
+        let (i, j) = (rng.below(edges.len()), rng.below(edges.len()));
+        let ((a, b), (c, d)) = (edges[i], edges[j]);
+        if a == c || b == d || a == d || c == b || graph.has_edge(a, d) || graph.has_edge(c, b) {
+            continue;
+        }
+
+        graph.remove_edge(a, b);
+        graph.remove_edge(c, d);
+        graph.add_edge(a, d);
+        graph.add_edge(c, b);
+        edges[i] = (a, d);
+        edges[j] = (c, b);
+        done += 1;
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::GraphRead,
+        impls::{adj_list::AdjListGraph, adj_vec::AdjVecGraph},
+    };
+    use std::collections::HashMap;
+
+    fn degrees<G: GraphRead>(graph: &G) -> (HashMap<G::Index, usize>, HashMap<G::Index, usize>)
+    where
+        G::Index: Hash + Copy,
+    {
+        let (mut out, mut inc) = (HashMap::new(), HashMap::new());
+        for (f, t) in graph.iter_edges() {
+            *out.entry(f).or_insert(0) += 1;
+            *inc.entry(t).or_insert(0) += 1;
+        }
+        (out, inc)
+    }
+
+    #[test]
+    fn test_keeps_degrees() {
+        let mut g = AdjVecGraph::new();
+        let ids: Vec<_> = (0..12).map(|_| g.add_node()).collect();
+        for i in 0..12 {
+            g.add_edge(ids[i], ids[(i + 1) % 12]);
+            g.add_edge(ids[i], ids[(i + 5) % 12]);
+        }
+        let before = degrees(&g);
+        let edges_before: Vec<_> = g.iter_edges().collect();
+
+        assert_eq!(rewire_preserving_degrees(&mut g, 30, 5), 30);
+        assert_eq!(degrees(&g), before);
+        assert_eq!(g.edge_count(), 24);
+        assert!(g.iter_edges().all(|(f, t)| f != t));
+        assert_ne!(g.iter_edges().collect::<Vec<_>>(), edges_before);
+    }
+
+    #[test]
+    fn test_gives_up() {
+        // the only swap of a two-edge path would create a self loop
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        assert_eq!(rewire_preserving_degrees(&mut g, 5, 1), 0);
+        assert!(g.has_edge(ids[0], ids[1]) && g.has_edge(ids[1], ids[2]));
+
+        assert_eq!(rewire_preserving_degrees(&mut AdjListGraph::new(), 5, 1), 0);
+    }
+}
@@ -0,0 +1,266 @@
+use super::induced_subgraph;
+use crate::{
+    graph::{Graph, GraphRead},
+    impls::{adj_list::Index, adj_vec::AdjVecGraph},
+    utils::Rng,
+};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+/**
+ * Returns the subgraph induced by `k` nodes sampled uniformly without replacement, or
+ * by all the nodes if there are fewer, with the mapping from each of them to its node
+ * in the subgraph.
+ *
+ * The sampling is reproducible for a given `seed`, and nodes are renumbered as in
+ * [`induced_subgraph`].
+ */
+pub fn sample_nodes<G>(graph: &G, k: usize, seed: u64) -> (AdjVecGraph, HashMap<G::Index, Index>)
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let mut nodes: Vec<_> = graph.iter_nodes().collect();
+    nodes.sort();
+    Rng::new(seed).partial_shuffle(&mut nodes, k);
+    nodes.truncate(k);
+    induced_subgraph(graph, nodes)
+}
+
+/**
+ * Returns the subgraph made of `k` edges sampled uniformly without replacement, or of
+ * all the edges if there are fewer, and of their endpoints, with the mapping from each
+ * endpoint to its node in the subgraph.
+ *
+ * Unlike [`sample_nodes`], the other edges between the endpoints are not included,
+ * so that the degrees are sampled proportionally. The sampling is reproducible for a
+ * given `seed`, and nodes are renumbered as in [`induced_subgraph`].
+ */
+pub fn sample_edges<G>(graph: &G, k: usize, seed: u64) -> (AdjVecGraph, HashMap<G::Index, Index>)
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let mut edges: Vec<_> = graph.iter_edges().collect();
+    edges.sort();
+    Rng::new(seed).partial_shuffle(&mut edges, k);
+    edges.truncate(k);
+
+    let mut nodes: Vec<_> = edges.iter().flat_map(|&(f, t)| [f, t]).collect();
+    nodes.sort();
+    nodes.dedup();
+    let mut result = AdjVecGraph::with_nodes(nodes.len());
+    let mapping: HashMap<_, _> = nodes.iter().copied().zip(result.iter_nodes()).collect();
+    for (f, t) in edges {
+        result.add_edge(mapping[&f], mapping[&t]);
+    }
+    (result, mapping)
+}
+
+/**
+ * Edge in an [`EdgeReservoir`] with its key, ordered by the key only.
+ */
+struct Key<K>(f64, (K, K));
+
+impl<K> PartialEq for Key<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K> Eq for Key<K> {}
+
+impl<K> PartialOrd for Key<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K> Ord for Key<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/**
+ * Weighted sample of `k` edges out of a stream seen one edge at a time, for instance
+ * from [`stream_edges`](crate::io::stream_edges), keeping only the sample in memory.
+ *
+ * Each edge is sampled without replacement with probability proportional to its
+ * weight, with the A-Res algorithm of Efraimidis and Spirakis: edges get the key
+ * `ln(u) / weight` for a uniform `u` and the `k` largest keys are kept, in
+ * `O(log k)` time per edge. Edges with a weight that is not positive and finite are
+ * never sampled. The sample is reproducible for a given `seed`.
+ */
+pub struct EdgeReservoir<K> {
+    capacity: usize,
+    heap: BinaryHeap<Reverse<Key<K>>>,
+    rng: Rng,
+    seen: usize,
+}
+
+impl<K> EdgeReservoir<K> {
+    /**
+     * Creates an empty reservoir keeping up to `k` edges.
+     */
+    pub fn new(k: usize, seed: u64) -> Self {
+        EdgeReservoir {
+            capacity: k,
+            heap: BinaryHeap::with_capacity(k),
+            rng: Rng::new(seed),
+            seen: 0,
+        }
+    }
+
+    /**
+     * Offers an edge with the given weight to the sample.
+     */
+    pub fn offer(&mut self, edge: (K, K), weight: f64) {
+        self.seen += 1;
+        if self.capacity == 0 || !(weight > 0.0 && weight.is_finite()) {
+            return;
+        }
+
+        let key = self.rng.unit().ln() / weight;
+        if self.heap.len() < self.capacity {
+            self.heap.push(Reverse(Key(key, edge)));
+        } else if self.heap.peek().is_some_and(|min| key > min.0 .0) {
+            self.heap.pop();
+            self.heap.push(Reverse(Key(key, edge)));
+        }
+    }
+
+    /**
+     * Returns the number of edges offered so far.
+     */
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /**
+     * Returns the number of edges in the sample.
+     */
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /**
+     * Checks if the sample is empty.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /**
+     * Returns the sampled edges, from the one with the largest key.
+     */
+    pub fn into_edges(self) -> Vec<(K, K)> {
+        let keys = self.heap.into_sorted_vec();
+        keys.into_iter().map(|Reverse(Key(_, edge))| edge).collect()
+    }
+}
+
+/**
+ * Samples `k` edges of a stream with an [`EdgeReservoir`], where each edge weighs
+ * `weight(edge)`, stopping at the first error.
+ *
+ * The stream has the same form as the one taken by
+ * [`Graph::extend_from_stream`], so the sample can be loaded into a graph afterwards.
+ */
+pub fn reservoir_sample_edges<K, E, I, F>(
+    edges: I,
+    k: usize,
+    seed: u64,
+    mut weight: F,
+) -> Result<Vec<(K, K)>, E>
+where
+    I: IntoIterator<Item = Result<(K, K), E>>,
+    F: FnMut(&(K, K)) -> f64,
+{
+    let mut reservoir = EdgeReservoir::new(k, seed);
+    for edge in edges {
+        let edge = edge?;
+        let w = weight(&edge);
+        reservoir.offer(edge, w);
+    }
+    Ok(reservoir.into_edges())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{impls::adj_list::AdjListGraph, io::stream_edges};
+
+    fn complete(n: usize) -> (AdjListGraph, Vec<Index>) {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| g.add_node()).collect();
+        for &f in &ids {
+            for &t in &ids {
+                if f != t {
+                    g.add_edge(f, t);
+                }
+            }
+        }
+        (g, ids)
+    }
+
+    #[test]
+    fn test_sample_nodes() {
+        let (g, _) = complete(10);
+        let (sample, mapping) = sample_nodes(&g, 4, 7);
+        assert_eq!(sample.node_count(), 4);
+        assert_eq!(sample.edge_count(), 12);
+        assert_eq!(sample_nodes(&g, 4, 7).1, mapping);
+        assert_ne!(sample_nodes(&g, 4, 8).1, mapping);
+        assert_eq!(sample_nodes(&g, 20, 7).0.node_count(), 10);
+    }
+
+    #[test]
+    fn test_sample_edges() {
+        let (g, _) = complete(10);
+        let (sample, mapping) = sample_edges(&g, 5, 3);
+        assert_eq!(sample.edge_count(), 5);
+        assert!(sample.node_count() <= 10 && sample.node_count() == mapping.len());
+        for (f, t) in sample.iter_edges() {
+            assert!(f != t);
+        }
+        assert_eq!(sample_edges(&g, 5, 3).1, mapping);
+        assert_eq!(sample_edges(&g, 100, 3).0.edge_count(), 90);
+    }
+
+    #[test]
+    fn test_edge_reservoir() {
+        let mut reservoir = EdgeReservoir::new(3, 1);
+        for i in 0..10u64 {
+            reservoir.offer((i, i + 1), if i == 4 { 0.0 } else { 1.0 });
+        }
+        assert_eq!((reservoir.seen(), reservoir.len()), (10, 3));
+        assert!(!reservoir.into_edges().contains(&(4, 5)));
+
+        // the heavier edge is kept with probability 0.9
+        let heavy = (0..2000)
+            .filter(|&seed| {
+                let mut reservoir = EdgeReservoir::new(1, seed);
+                reservoir.offer((1, 2), 9.0);
+                reservoir.offer((2, 3), 1.0);
+                reservoir.into_edges() == [(1, 2)]
+            })
+            .count();
+        assert!((1700..1900).contains(&heavy), "{}", heavy);
+    }
+
+    #[test]
+    fn test_reservoir_sample_stream() {
+        let input = "1 2\n2 3\n3 1\n";
+        let edges = stream_edges(input.as_bytes());
+        let mut sample = reservoir_sample_edges(edges, 5, 0, |_| 1.0).unwrap();
+        sample.sort();
+        assert_eq!(sample, vec![(1, 2), (2, 3), (3, 1)]);
+
+        let edges = stream_edges("1 2\nx\n".as_bytes());
+        assert!(reservoir_sample_edges(edges, 5, 0, |_| 1.0).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    graph::{Graph, GraphRead},
+    impls::{adj_list::Index, adj_vec::AdjVecGraph},
+};
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Returns the subgraph induced by `nodes`, made of them and of all the edges between
+ * them, with the mapping from each of them to its node in the subgraph.
+ *
+ * Nodes are renumbered from 1 in ascending order of their original index; nodes not
+ * in the graph and repeated nodes are ignored.
+ */
+pub fn induced_subgraph<G, N>(graph: &G, nodes: N) -> (AdjVecGraph, HashMap<G::Index, Index>)
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+    N: IntoIterator<Item = G::Index>,
+{
+    let mut nodes: Vec<_> = nodes
+        .into_iter()
+        .filter(|&n| graph.iter_adj(n).is_some())
+        .collect();
+    nodes.sort();
+    nodes.dedup();
+
+    let mut result = AdjVecGraph::with_nodes(nodes.len());
+    let mapping: HashMap<_, _> = nodes.iter().copied().zip(result.iter_nodes()).collect();
+    for &f in &nodes {
+        for t in graph.iter_adj(f).into_iter().flatten() {
+            if let Some(&t) = mapping.get(&t) {
+                result.add_edge(mapping[&f], t);
+            }
+        }
+    }
+    (result, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impls::adj_list::AdjListGraph;
+
+    #[test]
+    fn test_induced_subgraph() {
+        let mut g = AdjListGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+        g.add_edge(ids[0], ids[1]);
+        g.add_edge(ids[1], ids[2]);
+        g.add_edge(ids[2], ids[0]);
+        g.add_edge(ids[2], ids[3]);
+
+        let (sub, mapping) = induced_subgraph(&g, [ids[2], ids[0], ids[2], ids[3].next()]);
+        assert_eq!(sub.node_count(), 2);
+        assert_eq!(sub.edge_count(), 1);
+        assert_eq!(mapping[&ids[0]], Index(1));
+        assert!(sub.has_edge(mapping[&ids[2]], mapping[&ids[0]]));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::observer::{GraphEvent, GraphObserver};
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Per-node data stored outside of the graph, keyed by node index.
+ *
+ * When subscribed to an [`ObservedGraph`](crate::observer::ObservedGraph),
+ * the value of a node is dropped as soon as the node is removed.
+ */
+pub struct PropertyMap<I, T> {
+    values: HashMap<I, T>,
+}
+
+impl<I: Hash + Eq, T> PropertyMap<I, T> {
+    /**
+     * Creates an empty map.
+     */
+    pub fn new() -> Self {
+        PropertyMap {
+            values: HashMap::new(),
+        }
+    }
+
+    /**
+     * Returns the value of the node.
+     */
+    pub fn get(&self, n: &I) -> Option<&T> {
+        self.values.get(n)
+    }
+
+    /**
+     * Returns a mutable reference to the value of the node.
+     */
+    pub fn get_mut(&mut self, n: &I) -> Option<&mut T> {
+        self.values.get_mut(n)
+    }
+
+    /**
+     * Sets the value of the node, returning the previous one.
+     */
+    pub fn set(&mut self, n: I, value: T) -> Option<T> {
+        self.values.insert(n, value)
+    }
+
+    /**
+     * Removes the value of the node, returning it.
+     */
+    pub fn remove(&mut self, n: &I) -> Option<T> {
+        self.values.remove(n)
+    }
+
+    /**
+     * Returns the number of nodes with a value.
+     */
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /**
+     * Checks if no node has a value.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /**
+     * Returns an iterator over the nodes with a value and their values.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (&I, &T)> {
+        self.values.iter()
+    }
+}
+
+impl<I: Hash + Eq, T> Default for PropertyMap<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Hash + Eq, T> GraphObserver<I> for PropertyMap<I, T> {
+    fn notify(&mut self, event: &GraphEvent<I>) {
+        if let GraphEvent::NodeRemoved(n) = event {
+            self.values.remove(n);
+        }
+    }
+}
+
+/**
+ * Per-edge data stored outside of the graph, keyed by the edge endpoints.
+ *
+ * When subscribed to an [`ObservedGraph`](crate::observer::ObservedGraph), the value
+ * of an edge is dropped as soon as the edge or one of its endpoints is removed.
+ */
+pub struct EdgePropertyMap<I, T> {
+    values: HashMap<(I, I), T>,
+}
+
+impl<I: Hash + Eq + Copy, T> EdgePropertyMap<I, T> {
+    /**
+     * Creates an empty map.
+     */
+    pub fn new() -> Self {
+        EdgePropertyMap {
+            values: HashMap::new(),
+        }
+    }
+
+    /**
+     * Returns the value of the edge.
+     */
+    pub fn get(&self, f: I, t: I) -> Option<&T> {
+        self.values.get(&(f, t))
+    }
+
+    /**
+     * Returns a mutable reference to the value of the edge.
+     */
+    pub fn get_mut(&mut self, f: I, t: I) -> Option<&mut T> {
+        self.values.get_mut(&(f, t))
+    }
+
+    /**
+     * Sets the value of the edge, returning the previous one.
+     */
+    pub fn set(&mut self, f: I, t: I, value: T) -> Option<T> {
+        self.values.insert((f, t), value)
+    }
+
+    /**
+     * Removes the value of the edge, returning it.
+     */
+    pub fn remove(&mut self, f: I, t: I) -> Option<T> {
+        self.values.remove(&(f, t))
+    }
+
+    /**
+     * Returns the number of edges with a value.
+     */
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /**
+     * Checks if no edge has a value.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /**
+     * Returns an iterator over the edges with a value and their values.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (&(I, I), &T)> {
+        self.values.iter()
+    }
+}
+
+impl<I: Hash + Eq + Copy, T> Default for EdgePropertyMap<I, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Hash + Eq + Copy, T> GraphObserver<I> for EdgePropertyMap<I, T> {
+    fn notify(&mut self, event: &GraphEvent<I>) {
+        match *event {
+            GraphEvent::EdgeRemoved(f, t) => {
+                self.values.remove(&(f, t));
+            }
+            // incident edges are not reported, so they have to be looked up
+            GraphEvent::NodeRemoved(n) => self.values.retain(|&(f, t), _| f != n && t != n),
+            GraphEvent::NodeAdded(_)
+            | GraphEvent::EdgeAdded(..)
+            | GraphEvent::EdgeWeightChanged(..) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph, observer::ObservedGraph};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn test_node_values_cleaned_up() {
+        let mut g = ObservedGraph::new(AdjListGraph::new());
+        let names = Rc::new(RefCell::new(PropertyMap::new()));
+        g.subscribe(&names);
+
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        names.borrow_mut().set(id1, "a");
+        names.borrow_mut().set(id2, "b");
+        assert_eq!(names.borrow().get(&id1), Some(&"a"));
+
+        g.remove_node(id1);
+        assert_eq!(names.borrow().get(&id1), None);
+        assert_eq!(names.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_edge_values_cleaned_up() {
+        let mut g = ObservedGraph::new(AdjListGraph::new());
+        let costs = Rc::new(RefCell::new(EdgePropertyMap::new()));
+        g.subscribe(&costs);
+
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id2, id3);
+        g.add_edge(id3, id1);
+        costs.borrow_mut().set(id1, id2, 1.5);
+        costs.borrow_mut().set(id2, id3, 2.5);
+        costs.borrow_mut().set(id3, id1, 3.5);
+
+        g.remove_edge(id1, id2);
+        assert_eq!(costs.borrow().get(id1, id2), None);
+
+        g.remove_node(id3);
+        assert!(costs.borrow().is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use crate::algorithms::{reachable_from, Algorithm, CostedPath, Dijkstra, Measure};
+use crate::graph::{GraphRead, WeightedGraph};
+use std::{
+    hash::Hash,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+/**
+ * Graph shared behind a lock, with queries returning owned results.
+ *
+ * Every method takes the lock only for its own duration and never returns a borrow
+ * of the graph, so the service can be cloned into request handlers and its results
+ * kept across `.await` points. Clones share the same graph. Queries block the calling
+ * thread while they run, so long searches are best moved off the async executor,
+ * for instance with the `spawn_blocking` of the runtime.
+ *
+ * ```
+ * use rust_graph_lib::{graph::Graph, impls::adj_list::AdjListGraph, service::GraphService};
+ *
+ * let service = GraphService::new(AdjListGraph::new());
+ * let (a, b) = service.write(|g| {
+ *     let (a, b) = (g.add_node(), g.add_node());
+ *     g.add_edge(a, b);
+ *     (a, b)
+ * });
+ *
+ * let handler = service.clone();
+ * let path = std::thread::spawn(move || handler.shortest_path(a, b)).join().unwrap();
+ * assert_eq!(path, Some(vec![a, b]));
+ * ```
+ */
+pub struct GraphService<G> {
+    graph: Arc<RwLock<G>>,
+}
+
+impl<G> GraphService<G> {
+    /**
+     * Wraps the graph.
+     */
+    pub fn new(graph: G) -> Self {
+        GraphService {
+            graph: Arc::new(RwLock::new(graph)),
+        }
+    }
+
+    fn lock_read(&self) -> RwLockReadGuard<'_, G> {
+        self.graph.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_write(&self) -> RwLockWriteGuard<'_, G> {
+        self.graph.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /**
+     * Runs a query on the graph, holding the read lock until it returns.
+     */
+    pub fn read<R, F: FnOnce(&G) -> R>(&self, query: F) -> R {
+        query(&self.lock_read())
+    }
+
+    /**
+     * Mutates the graph, holding the write lock until `update` returns.
+     */
+    pub fn write<R, F: FnOnce(&mut G) -> R>(&self, update: F) -> R {
+        update(&mut self.lock_write())
+    }
+}
+
+impl<G> Clone for GraphService<G> {
+    fn clone(&self) -> Self {
+        GraphService {
+            graph: Arc::clone(&self.graph),
+        }
+    }
+}
+
+impl<G> GraphService<G>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    /**
+     * Returns the number of nodes.
+     */
+    pub fn node_count(&self) -> usize {
+        self.lock_read().node_count()
+    }
+
+    /**
+     * Returns the number of edges.
+     */
+    pub fn edge_count(&self) -> usize {
+        self.lock_read().edge_count()
+    }
+
+    /**
+     * Returns whether the edge `(f, t)` is in the graph.
+     */
+    pub fn has_edge(&self, f: G::Index, t: G::Index) -> bool {
+        self.lock_read().has_edge(f, t)
+    }
+
+    /**
+     * Returns the neighbors of a node in ascending order, if the node is in the graph.
+     */
+    pub fn neighbors(&self, n: G::Index) -> Option<Vec<G::Index>> {
+        let mut adj: Vec<_> = self.lock_read().iter_adj(n)?.collect();
+        adj.sort();
+        Some(adj)
+    }
+
+    /**
+     * Returns the nodes reachable from `start`, including it, in ascending order.
+     */
+    pub fn reachable_from(&self, start: G::Index) -> Vec<G::Index> {
+        let mut nodes: Vec<_> = reachable_from(&*self.lock_read(), start)
+            .into_iter()
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    /**
+     * Returns a path from `from` to `to` with the fewest edges, if there is one.
+     */
+    pub fn shortest_path(&self, from: G::Index, to: G::Index) -> Option<Vec<G::Index>> {
+        Dijkstra::new(&*self.lock_read()).run(from, to)
+    }
+}
+
+impl<G> GraphService<G>
+where
+    G: WeightedGraph,
+    G::Index: Hash + Copy,
+    G::Weight: Measure,
+{
+    /**
+     * Returns the path from `from` to `to` minimizing the weights of the graph, along
+     * with its cost, if there is one.
+     */
+    pub fn weighted_shortest_path(
+        &self,
+        from: G::Index,
+        to: G::Index,
+    ) -> Option<CostedPath<G::Index, G::Weight>> {
+        Dijkstra::weighted(&*self.lock_read()).run_with_cost(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::Graph,
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_queries() {
+        let service = GraphService::new(AdjListGraph::new());
+        let ids = service.write(|g| {
+            let ids: Vec<_> = (0..4).map(|_| g.add_node()).collect();
+            g.add_edge(ids[0], ids[2]);
+            g.add_edge(ids[0], ids[1]);
+            g.add_edge(ids[1], ids[2]);
+            ids
+        });
+
+        assert_eq!((service.node_count(), service.edge_count()), (4, 3));
+        assert!(service.has_edge(ids[0], ids[1]));
+        assert_eq!(service.neighbors(ids[0]), Some(vec![ids[1], ids[2]]));
+        assert_eq!(service.neighbors(ids[3].next()), None);
+        assert_eq!(service.reachable_from(ids[1]), vec![ids[1], ids[2]]);
+        assert_eq!(
+            service.shortest_path(ids[0], ids[2]),
+            Some(vec![ids[0], ids[2]])
+        );
+        assert_eq!(service.shortest_path(ids[2], ids[0]), None);
+        assert_eq!(service.read(|g| g.iter_edges().count()), 3);
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let service = GraphService::new(UndirectedWeightedGraph::new());
+        let ids: Vec<_> = service.write(|g| (0..3).map(|_| g.add_node()).collect());
+
+        let handles: Vec<_> = (0..2)
+            .map(|i| {
+                let service = service.clone();
+                let ids = ids.clone();
+                std::thread::spawn(move || {
+                    service.write(|g| g.add_weighted_edge(ids[i], ids[i + 1], 2u32))
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let path = service.weighted_shortest_path(ids[2], ids[0]).unwrap();
+        assert_eq!(path.path, vec![ids[2], ids[1], ids[0]]);
+        assert_eq!(path.cost, 4);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{algorithms::DenseIndex, graph::GraphRead};
+use std::hash::Hash;
+
+/**
+ * Sparse matrix in triplet (COO) format, with one row and one column per node.
+ *
+ * The `rows`, `cols` and `values` vectors have the same length and can be fed directly
+ * to the triplet constructors of linear algebra crates such as `nalgebra-sparse`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct CooMatrix<I> {
+    /**
+     * The node corresponding to each row and column, in ascending order.
+     */
+    pub nodes: Vec<I>,
+    /**
+     * Row of each entry.
+     */
+    pub rows: Vec<usize>,
+    /**
+     * Column of each entry.
+     */
+    pub cols: Vec<usize>,
+    /**
+     * Value of each entry.
+     */
+    pub values: Vec<f64>,
+}
+
+impl<I> CooMatrix<I> {
+    /**
+     * Returns the number of rows, which is also the number of columns.
+     */
+    pub fn dim(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /**
+     * Converts the matrix to a dense row-major representation, summing duplicate entries.
+     */
+    pub fn to_dense(&self) -> Vec<Vec<f64>> {
+        let mut dense = vec![vec![0.0; self.dim()]; self.dim()];
+        for ((&r, &c), &v) in self.rows.iter().zip(&self.cols).zip(&self.values) {
+            dense[r][c] += v;
+        }
+        dense
+    }
+
+    fn push(&mut self, r: usize, c: usize, v: f64) {
+        self.rows.push(r);
+        self.cols.push(c);
+        self.values.push(v);
+    }
+}
+
+/**
+ * Returns the adjacency matrix of the graph, with a `1` in row `a` and column `b`
+ * for each edge `a -> b`.
+ */
+pub fn adjacency_matrix<G>(graph: &G) -> CooMatrix<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let index = DenseIndex::new(graph);
+    let mut edges: Vec<_> = index.edges(graph).collect();
+    edges.sort();
+    let mut m = CooMatrix {
+        nodes: index.nodes().to_vec(),
+        rows: Vec::new(),
+        cols: Vec::new(),
+        values: Vec::new(),
+    };
+    for (f, t) in edges {
+        m.push(f, t, 1.0);
+    }
+    m
+}
+
+/**
+ * Returns the (out-degree) Laplacian matrix `L = D - A` of the graph, where `D` is the
+ * diagonal matrix of out-degrees and `A` the adjacency matrix.
+ *
+ * For undirected graphs storing each edge in both directions this is the usual
+ * symmetric Laplacian. Self loops cancel out on the diagonal.
+ */
+pub fn laplacian_matrix<G>(graph: &G) -> CooMatrix<G::Index>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let adjacency = adjacency_matrix(graph);
+    let mut m = CooMatrix {
+        nodes: adjacency.nodes,
+        rows: Vec::new(),
+        cols: Vec::new(),
+        values: Vec::new(),
+    };
+
+    // adjacency entries are sorted by row, so each row is a contiguous run
+    let mut i = 0;
+    for r in 0..m.nodes.len() {
+        let start = i;
+        while i < adjacency.rows.len() && adjacency.rows[i] == r {
+            i += 1;
+        }
+        let self_loops = (start..i).filter(|&k| adjacency.cols[k] == r).count();
+        let degree = (i - start - self_loops) as f64;
+        if degree != 0.0 {
+            m.push(r, r, degree);
+        }
+        for k in start..i {
+            if adjacency.cols[k] != r {
+                m.push(r, adjacency.cols[k], -1.0);
+            }
+        }
+    }
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    fn undirected_path() -> AdjListGraph {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        for (f, t) in [(id1, id2), (id2, id3)] {
+            g.add_edge(f, t);
+            g.add_edge(t, f);
+        }
+        g
+    }
+
+    #[test]
+    fn test_adjacency_matrix() {
+        let g = undirected_path();
+        let m = adjacency_matrix(&g);
+        assert_eq!(m.dim(), 3);
+        assert_eq!(m.rows, vec![0, 1, 1, 2]);
+        assert_eq!(m.cols, vec![1, 0, 2, 1]);
+        assert_eq!(
+            m.to_dense(),
+            vec![
+                vec![0.0, 1.0, 0.0],
+                vec![1.0, 0.0, 1.0],
+                vec![0.0, 1.0, 0.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_laplacian_matrix() {
+        let g = undirected_path();
+        let dense = laplacian_matrix(&g).to_dense();
+        assert_eq!(
+            dense,
+            vec![
+                vec![1.0, -1.0, 0.0],
+                vec![-1.0, 2.0, -1.0],
+                vec![0.0, -1.0, 1.0]
+            ]
+        );
+        assert!(dense.iter().all(|row| row.iter().sum::<f64>() == 0.0));
+    }
+}
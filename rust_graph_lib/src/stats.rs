@@ -0,0 +1,78 @@
+use crate::graph::GraphRead;
+use std::{collections::HashMap, hash::Hash, io::Write};
+
+/**
+ * Returns the number of nodes with each out-degree: the `d`-th element counts the
+ * nodes with `d` outgoing edges, up to the maximum degree in the graph.
+ */
+pub fn degree_histogram<G: GraphRead>(graph: &G) -> Vec<usize> {
+    let degrees = graph
+        .iter_nodes()
+        .map(|n| graph.neighbors_count(n).unwrap_or(0));
+    histogram(degrees)
+}
+
+/**
+ * Returns the number of nodes with each in-degree, like [`degree_histogram`].
+ */
+pub fn in_degree_histogram<G>(graph: &G) -> Vec<usize>
+where
+    G: GraphRead,
+    G::Index: Hash + Copy,
+{
+    let mut in_degree: HashMap<_, usize> = graph.iter_nodes().map(|n| (n, 0)).collect();
+    for (_, t) in graph.iter_edges() {
+        *in_degree.entry(t).or_default() += 1;
+    }
+    histogram(in_degree.into_values())
+}
+
+fn histogram(degrees: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut counts = Vec::new();
+    for d in degrees {
+        if d >= counts.len() {
+            counts.resize(d + 1, 0);
+        }
+        counts[d] += 1;
+    }
+    counts
+}
+
+/**
+ * Writes a histogram as CSV with a `degree,count` header, skipping the degrees no node has.
+ */
+pub fn write_histogram_csv<W: Write>(histogram: &[usize], mut writer: W) -> std::io::Result<()> {
+    writeln!(writer, "degree,count")?;
+    for (degree, count) in histogram.iter().enumerate().filter(|(_, &c)| c > 0) {
+        writeln!(writer, "{},{}", degree, count)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_histograms() {
+        let mut g = AdjListGraph::new();
+        let hub = g.add_node();
+        let leaves: Vec<_> = (0..3).map(|_| g.add_node()).collect();
+        for &l in &leaves {
+            g.add_edge(hub, l);
+        }
+        g.add_edge(leaves[0], leaves[1]);
+
+        assert_eq!(degree_histogram(&g), vec![2, 1, 0, 1]);
+        assert_eq!(in_degree_histogram(&g), vec![1, 2, 1]);
+        assert!(degree_histogram(&AdjListGraph::new()).is_empty());
+
+        let mut out = Vec::new();
+        write_histogram_csv(&degree_histogram(&g), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "degree,count\n0,2\n1,1\n3,1\n"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    graph::{Graph, WeightedGraph},
+    io::LoadedGraph,
+};
+use std::{collections::HashMap, fmt::Debug, hash::Hash, str::FromStr};
+
+fn parse_spec<G, F>(spec: &str, mut add_edge: F) -> LoadedGraph<G>
+where
+    G: Graph + Default,
+    G::Index: Copy + Hash + Eq,
+    F: FnMut(&mut G, G::Index, G::Index, Option<&str>),
+{
+    let mut graph = G::default();
+    let mut ids = HashMap::new();
+    let mut node = |graph: &mut G, id: &str| {
+        let id: u64 = id
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid node id `{}` in `{}`", id, spec));
+        *ids.entry(id).or_insert_with(|| graph.add_node())
+    };
+
+    for item in spec.split('|').map(str::trim).filter(|i| !i.is_empty()) {
+        match item.split_once('-') {
+            Some((f, rest)) => {
+                let (t, weight) = match rest.split_once(':') {
+                    Some((t, w)) => (t, Some(w.trim())),
+                    None => (rest, None),
+                };
+                let (f, t) = (node(&mut graph, f), node(&mut graph, t));
+                add_edge(&mut graph, f, t, weight);
+            }
+            None => {
+                for id in item.split(',') {
+                    node(&mut graph, id);
+                }
+            }
+        }
+    }
+
+    LoadedGraph {
+        graph,
+        ids,
+        labels: HashMap::new(),
+    }
+}
+
+/**
+ * Builds a graph from a compact description, for use in tests.
+ *
+ * The description is a `|`-separated list of directed edges `f-t` and of comma-separated
+ * node lists, such as `"1-2|2-3|4,5"`; nodes are created in order of first appearance and
+ * are mapped from their numeric ids through [`LoadedGraph::ids`].
+ *
+ * Panics if the description is malformed or has weights (see [`parse_weighted_graph`]).
+ */
+pub fn parse_graph<G>(spec: &str) -> LoadedGraph<G>
+where
+    G: Graph + Default,
+    G::Index: Copy + Hash + Eq,
+{
+    parse_spec(spec, |g: &mut G, f, t, weight| {
+        assert!(weight.is_none(), "unexpected weight in `{}`", spec);
+        g.add_edge(f, t);
+    })
+}
+
+/**
+ * Builds a weighted graph from a compact description like [`parse_graph`], where
+ * each edge may have a weight, such as `"1-2:3|2-4:1"`. Edges without one get the
+ * default weight.
+ *
+ * Panics if the description is malformed.
+ */
+pub fn parse_weighted_graph<G>(spec: &str) -> LoadedGraph<G>
+where
+    G: WeightedGraph + Default,
+    G::Index: Copy + Hash + Eq,
+    G::Weight: FromStr + Default,
+    <G::Weight as FromStr>::Err: Debug,
+{
+    parse_spec(spec, |g: &mut G, f, t, weight| {
+        let w = match weight {
+            Some(w) => w
+                .parse()
+                .unwrap_or_else(|e| panic!("invalid weight `{}` in `{}`: {:?}", w, spec, e)),
+            None => G::Weight::default(),
+        };
+        g.add_weighted_edge(f, t, w);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::GraphRead,
+        impls::{adj_list::AdjListGraph, undirected_weighted::UndirectedWeightedGraph},
+    };
+
+    #[test]
+    fn test_parse_graph() {
+        let loaded: LoadedGraph<AdjListGraph> = parse_graph("1-2 | 2-3|4,5");
+        let n = |id| loaded.ids[&id];
+
+        assert_eq!(loaded.graph.node_count(), 5);
+        assert_eq!(loaded.graph.edge_count(), 2);
+        assert!(loaded.graph.has_edge(n(1), n(2)));
+        assert!(loaded.graph.has_edge(n(2), n(3)));
+        assert_eq!(loaded.graph.neighbors_count(n(5)), Some(0));
+    }
+
+    #[test]
+    fn test_parse_weighted_graph() {
+        let loaded: LoadedGraph<UndirectedWeightedGraph<u32>> =
+            parse_weighted_graph("1-2:3|2-4:1|3-4");
+        let n = |id| loaded.ids[&id];
+
+        assert_eq!(loaded.graph.edge_weight(n(2), n(1)), Some(3));
+        assert_eq!(loaded.graph.edge_weight(n(2), n(4)), Some(1));
+        assert_eq!(loaded.graph.edge_weight(n(3), n(4)), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected weight")]
+    fn test_weights_rejected_for_unweighted() {
+        parse_graph::<AdjListGraph>("1-2:3");
+    }
+}
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Bidirectional one-to-one mapping between values of two types.
+ */
+pub struct DoubleMapping<L, R> {
+    left: HashMap<L, R>,
+    right: HashMap<R, L>,
+}
+
+impl<L: Hash + Eq + Clone, R: Hash + Eq + Clone> DoubleMapping<L, R> {
+    /**
+     * Creates an empty mapping.
+     */
+    pub fn new() -> Self {
+        DoubleMapping {
+            left: HashMap::new(),
+            right: HashMap::new(),
+        }
+    }
+
+    /**
+     * Returns the number of pairs in the mapping.
+     */
+    pub fn len(&self) -> usize {
+        self.left.len()
+    }
+
+    /**
+     * Checks if the mapping is empty.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /**
+     * Maps `l` to `r`, removing any pair previously involving either of them.
+     */
+    pub fn insert(&mut self, l: L, r: R) {
+        self.remove_left(&l);
+        self.remove_right(&r);
+        self.left.insert(l.clone(), r.clone());
+        self.right.insert(r, l);
+    }
+
+    /**
+     * Returns the value mapped to `l`.
+     */
+    pub fn get_left(&self, l: &L) -> Option<&R> {
+        self.left.get(l)
+    }
+
+    /**
+     * Returns the value mapped to `r`.
+     */
+    pub fn get_right(&self, r: &R) -> Option<&L> {
+        self.right.get(r)
+    }
+
+    /**
+     * Removes the pair involving `l`, returning the value it was mapped to.
+     */
+    pub fn remove_left(&mut self, l: &L) -> Option<R> {
+        let r = self.left.remove(l)?;
+        self.right.remove(&r);
+        Some(r)
+    }
+
+    /**
+     * Removes the pair involving `r`, returning the value it was mapped to.
+     */
+    pub fn remove_right(&mut self, r: &R) -> Option<L> {
+        let l = self.right.remove(r)?;
+        self.left.remove(&l);
+        Some(l)
+    }
+
+    /**
+     * Returns an iterator over all the pairs.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (&L, &R)> {
+        self.left.iter()
+    }
+}
+
+impl<L: Hash + Eq + Clone, R: Hash + Eq + Clone> Default for DoubleMapping<L, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_replaces_both_sides() {
+        let mut m = DoubleMapping::new();
+        m.insert(1, 'a');
+        m.insert(2, 'b');
+        assert_eq!(m.get_left(&1), Some(&'a'));
+        assert_eq!(m.get_right(&'b'), Some(&2));
+
+        m.insert(1, 'b');
+        assert_eq!(m.len(), 1);
+        assert_eq!(m.get_right(&'a'), None);
+        assert_eq!(m.get_left(&2), None);
+        assert_eq!(m.remove_right(&'b'), Some(1));
+        assert!(m.is_empty());
+    }
+}
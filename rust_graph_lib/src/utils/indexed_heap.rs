@@ -0,0 +1,240 @@
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Binary min-heap whose entries are addressable by key, supporting
+ * [`decrease_key`](IndexedBinaryHeap::decrease_key) in `O(log n)`.
+ *
+ * Each key is stored at most once, so searches using it never process stale entries.
+ */
+pub struct IndexedBinaryHeap<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedBinaryHeap<K, P> {
+    /**
+     * Creates an empty heap.
+     */
+    pub fn new() -> Self {
+        IndexedBinaryHeap {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /**
+     * Creates an empty heap with space for at least `capacity` entries.
+     */
+    pub fn with_capacity(capacity: usize) -> Self {
+        IndexedBinaryHeap {
+            heap: Vec::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /**
+     * Returns the number of entries in the heap.
+     */
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /**
+     * Checks if the heap is empty.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /**
+     * Checks if the key is in the heap.
+     */
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /**
+     * Returns the priority of the key, if present.
+     */
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|&i| &self.heap[i].1)
+    }
+
+    /**
+     * Returns the entry with the smallest priority without removing it.
+     */
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(k, p)| (k, p))
+    }
+
+    /**
+     * Inserts the key with the given priority, replacing the priority if the key
+     * is already present. Returns the previous priority.
+     */
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        match self.positions.get(&key) {
+            Some(&i) => {
+                let old = std::mem::replace(&mut self.heap[i].1, priority);
+                if self.heap[i].1 < old {
+                    self.sift_up(i);
+                } else {
+                    self.sift_down(i);
+                }
+                Some(old)
+            }
+            None => {
+                let i = self.heap.len();
+                self.positions.insert(key.clone(), i);
+                self.heap.push((key, priority));
+                self.sift_up(i);
+                None
+            }
+        }
+    }
+
+    /**
+     * Lowers the priority of the key, inserting it if not present.
+     * Returns `true` if the heap changed, `false` if the current priority is already lower or equal.
+     */
+    pub fn decrease_key(&mut self, key: K, priority: P) -> bool {
+        match self.positions.get(&key) {
+            Some(&i) if self.heap[i].1 <= priority => false,
+            Some(&i) => {
+                self.heap[i].1 = priority;
+                self.sift_up(i);
+                true
+            }
+            None => {
+                self.push(key, priority);
+                true
+            }
+        }
+    }
+
+    /**
+     * Removes and returns the entry with the smallest priority.
+     */
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+
+    /**
+     * Removes the key from the heap, returning its priority.
+     */
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = *self.positions.get(key)?;
+        Some(self.remove_at(i).1)
+    }
+
+    fn remove_at(&mut self, i: usize) -> (K, P) {
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (key, priority) = self.heap.pop().unwrap();
+        self.positions.remove(&key);
+
+        if i < self.heap.len() {
+            self.sift_down(i);
+            self.sift_up(i);
+        }
+        (key, priority)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        *self.positions.get_mut(&self.heap[i].0).unwrap() = i;
+        *self.positions.get_mut(&self.heap[j].0).unwrap() = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut smallest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedBinaryHeap<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pops_in_order() {
+        let mut heap = IndexedBinaryHeap::new();
+        for (k, p) in [("a", 5), ("b", 1), ("c", 3), ("d", 4), ("e", 2)] {
+            heap.push(k, p);
+        }
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some((&"b", &1)));
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(
+            order,
+            vec![("b", 1), ("e", 2), ("c", 3), ("d", 4), ("a", 5)]
+        );
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = IndexedBinaryHeap::new();
+        heap.push(1, 10);
+        heap.push(2, 20);
+        heap.push(3, 30);
+
+        assert!(heap.decrease_key(3, 5));
+        assert!(!heap.decrease_key(1, 15));
+        assert!(heap.decrease_key(4, 1));
+        assert_eq!(heap.priority(&3), Some(&5));
+        assert_eq!(heap.len(), 4);
+
+        assert_eq!(heap.pop(), Some((4, 1)));
+        assert_eq!(heap.pop(), Some((3, 5)));
+        assert_eq!(heap.pop(), Some((1, 10)));
+    }
+
+    #[test]
+    fn test_push_existing_and_remove() {
+        let mut heap = IndexedBinaryHeap::new();
+        heap.push('x', 1);
+        heap.push('y', 2);
+        heap.push('z', 3);
+
+        assert_eq!(heap.push('x', 4), Some(1));
+        assert_eq!(heap.remove(&'y'), Some(2));
+        assert_eq!(heap.remove(&'y'), None);
+        assert!(!heap.contains_key(&'y'));
+
+        assert_eq!(heap.pop(), Some(('z', 3)));
+        assert_eq!(heap.pop(), Some(('x', 4)));
+        assert_eq!(heap.pop(), None);
+    }
+}
@@ -0,0 +1,11 @@
+mod double_mapping;
+mod indexed_heap;
+mod rng;
+mod slab;
+mod union_find;
+
+pub use double_mapping::DoubleMapping;
+pub use indexed_heap::IndexedBinaryHeap;
+pub(crate) use rng::Rng;
+pub use slab::{Slab, SlotKey};
+pub use union_find::UnionFind;
@@ -0,0 +1,46 @@
+/**
+ * Small seeded pseudo-random generator (SplitMix64), used by the sampling algorithms
+ * so that their results are reproducible without external dependencies.
+ */
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /**
+     * Returns a number in the open interval `(0, 1)`.
+     */
+    pub(crate) fn unit(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /**
+     * Returns a number in `0..n`; `n` must not be 0.
+     */
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        // the modulo bias is negligible for the sizes we deal with
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /**
+     * Shuffles the first `k` elements of `items` so they are a uniform sample without replacement.
+     */
+    pub(crate) fn partial_shuffle<T>(&mut self, items: &mut [T], k: usize) {
+        for i in 0..k.min(items.len()) {
+            let j = i + self.below(items.len() - i);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::{Display, Formatter};
+
+/**
+ * Key of a value stored in a [`Slab`].
+ *
+ * Keys carry the generation of their slot, so a key whose value was removed stays
+ * invalid even after the slot is reused.
+ */
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+pub struct SlotKey {
+    slot: u32,
+    generation: u32,
+}
+
+impl SlotKey {
+    /**
+     * Returns the position of the slot, usable to index side tables.
+     */
+    pub fn slot(&self) -> usize {
+        self.slot as usize
+    }
+}
+
+impl Display for SlotKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}v{}", self.slot, self.generation)
+    }
+}
+
+enum Entry<T> {
+    Occupied { generation: u32, value: T },
+    Vacant { generation: u32 },
+}
+
+/**
+ * Arena storing values contiguously, addressed by generational [`SlotKey`]s.
+ *
+ * Lookups are a direct slot access; the slots of removed values are reused by later inserts.
+ */
+pub struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<T> Slab<T> {
+    /**
+     * Creates an empty slab.
+     */
+    pub fn new() -> Self {
+        Slab {
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /**
+     * Returns the number of values.
+     */
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /**
+     * Returns `true` if the slab holds no values.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /**
+     * Returns the number of slots, occupied or not.
+     */
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    /**
+     * Stores a value, returning its key.
+     */
+    pub fn insert(&mut self, value: T) -> SlotKey {
+        self.len += 1;
+        if let Some(slot) = self.free.pop() {
+            let entry = &mut self.entries[slot as usize];
+            let generation = match entry {
+                Entry::Vacant { generation } => *generation,
+                Entry::Occupied { .. } => unreachable!("free slot is occupied"),
+            };
+            *entry = Entry::Occupied { generation, value };
+            return SlotKey { slot, generation };
+        }
+
+        let slot = u32::try_from(self.entries.len()).expect("too many slots");
+        self.entries.push(Entry::Occupied {
+            generation: 0,
+            value,
+        });
+        SlotKey {
+            slot,
+            generation: 0,
+        }
+    }
+
+    /**
+     * Returns the value of a key, if it was not removed.
+     */
+    pub fn get(&self, key: SlotKey) -> Option<&T> {
+        match self.entries.get(key.slot())? {
+            Entry::Occupied { generation, value } if *generation == key.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /**
+     * Returns the value of a key mutably, if it was not removed.
+     */
+    pub fn get_mut(&mut self, key: SlotKey) -> Option<&mut T> {
+        match self.entries.get_mut(key.slot())? {
+            Entry::Occupied { generation, value } if *generation == key.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /**
+     * Returns `true` if the key refers to a value in the slab.
+     */
+    pub fn contains(&self, key: SlotKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /**
+     * Removes the value of a key, returning it.
+     */
+    pub fn remove(&mut self, key: SlotKey) -> Option<T> {
+        self.get(key)?;
+        let entry = std::mem::replace(
+            &mut self.entries[key.slot()],
+            Entry::Vacant {
+                generation: key.generation.wrapping_add(1),
+            },
+        );
+        self.free.push(key.slot);
+        self.len -= 1;
+        match entry {
+            Entry::Occupied { value, .. } => Some(value),
+            Entry::Vacant { .. } => None,
+        }
+    }
+
+    /**
+     * Iterates over the keys and values, in slot order.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = (SlotKey, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, entry)| match entry {
+                Entry::Occupied { generation, value } => Some((
+                    SlotKey {
+                        slot: slot as u32,
+                        generation: *generation,
+                    },
+                    value,
+                )),
+                Entry::Vacant { .. } => None,
+            })
+    }
+
+    /**
+     * Consumes the slab, iterating over the keys and values in slot order.
+     */
+    pub fn into_entries(self) -> impl Iterator<Item = (SlotKey, T)> {
+        self.entries
+            .into_iter()
+            .enumerate()
+            .filter_map(|(slot, entry)| match entry {
+                Entry::Occupied { generation, value } => Some((
+                    SlotKey {
+                        slot: slot as u32,
+                        generation,
+                    },
+                    value,
+                )),
+                Entry::Vacant { .. } => None,
+            })
+    }
+
+    /**
+     * Iterates mutably over the values, in slot order.
+     */
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            Entry::Occupied { value, .. } => Some(value),
+            Entry::Vacant { .. } => None,
+        })
+    }
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_reuse() {
+        let mut slab = Slab::new();
+        let a = slab.insert("a");
+        let b = slab.insert("b");
+        assert_eq!(slab.len(), 2);
+
+        assert_eq!(slab.remove(a), Some("a"));
+        assert_eq!(slab.remove(a), None);
+        let c = slab.insert("c");
+        assert_eq!(c.slot(), a.slot());
+        assert_eq!(slab.get(a), None);
+        assert_eq!(slab.get(c), Some(&"c"));
+
+        *slab.get_mut(b).unwrap() = "B";
+        let values: Vec<_> = slab.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec!["c", "B"]);
+    }
+}
@@ -0,0 +1,88 @@
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Disjoint-set forest over arbitrary keys, with path compression and union by size.
+ *
+ * Keys are added lazily, the first time they are looked up.
+ */
+pub struct UnionFind<K> {
+    parent: HashMap<K, K>,
+    size: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Copy> UnionFind<K> {
+    /**
+     * Creates an empty forest.
+     */
+    pub fn new() -> Self {
+        UnionFind {
+            parent: HashMap::new(),
+            size: HashMap::new(),
+        }
+    }
+
+    /**
+     * Returns the representative of the set containing `k`.
+     */
+    pub fn find(&mut self, k: K) -> K {
+        let mut root = k;
+        while let Some(&p) = self.parent.get(&root) {
+            if p == root {
+                break;
+            }
+            root = p;
+        }
+        if root == k {
+            self.parent.entry(k).or_insert(k);
+            return k;
+        }
+
+        let mut cur = k;
+        while cur != root {
+            let next = self.parent.insert(cur, root).unwrap();
+            cur = next;
+        }
+        root
+    }
+
+    /**
+     * Merges the sets containing `a` and `b`. Returns `false` if they were already the same set.
+     */
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+
+        let size_a = *self.size.get(&ra).unwrap_or(&1);
+        let size_b = *self.size.get(&rb).unwrap_or(&1);
+        let (root, child) = if size_a >= size_b { (ra, rb) } else { (rb, ra) };
+        self.parent.insert(child, root);
+        self.size.insert(root, size_a + size_b);
+        self.size.remove(&child);
+        true
+    }
+}
+
+impl<K: Hash + Eq + Copy> Default for UnionFind<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut uf = UnionFind::new();
+        assert!(uf.union(1, 2));
+        assert!(uf.union(3, 4));
+        assert!(uf.union(2, 4));
+        assert!(!uf.union(1, 3));
+        assert_eq!(uf.find(1), uf.find(4));
+        assert_ne!(uf.find(1), uf.find(5));
+        assert_eq!(uf.find(5), 5);
+    }
+}
@@ -0,0 +1,3 @@
+mod reversed;
+
+pub use reversed::Reversed;
@@ -0,0 +1,101 @@
+use crate::graph::{Direction, EdgeIterator, GraphRead, IncidentIterator, NodeIterator};
+use std::{collections::HashMap, hash::Hash};
+
+/**
+ * Read-only view of a graph with all its edges reversed.
+ *
+ * The incoming edges of each node are indexed when the view is created, in `O(n + m)`.
+ */
+pub struct Reversed<'a, G: GraphRead> {
+    graph: &'a G,
+    incoming: HashMap<G::Index, Vec<G::Index>>,
+}
+
+impl<'a, G: GraphRead> Reversed<'a, G>
+where
+    G::Index: Hash + Copy,
+{
+    /**
+     * Creates the reversed view of the graph.
+     */
+    pub fn new(graph: &'a G) -> Self {
+        let mut incoming: HashMap<_, Vec<_>> = HashMap::with_capacity(graph.node_count());
+        for (f, t) in graph.iter_edges() {
+            incoming.entry(t).or_default().push(f);
+        }
+        Reversed { graph, incoming }
+    }
+}
+
+impl<G: GraphRead> GraphRead for Reversed<'_, G>
+where
+    G::Index: Hash + Copy,
+{
+    type Index = G::Index;
+
+    fn has_edge(&self, f: Self::Index, t: Self::Index) -> bool {
+        self.graph.has_edge(t, f)
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn iter_nodes(&self) -> Box<NodeIterator<'_, Self::Index>> {
+        self.graph.iter_nodes()
+    }
+
+    fn iter_adj(&self, n: Self::Index) -> Option<Box<NodeIterator<'_, Self::Index>>> {
+        // only nodes of the graph have an adjacency, possibly empty
+        let _ = self.graph.iter_adj(n)?;
+        let it: Box<NodeIterator<'_, Self::Index>> = match self.incoming.get(&n) {
+            Some(incoming) => Box::new(incoming.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        };
+        Some(it)
+    }
+
+    fn iter_edges(&self) -> Box<EdgeIterator<'_, Self::Index>> {
+        Box::new(self.graph.iter_edges().map(|(f, t)| (t, f)))
+    }
+
+    fn iter_incident(&self, n: Self::Index) -> Box<IncidentIterator<'_, Self::Index>> {
+        let (Some(outgoing), Some(incoming)) = (self.iter_adj(n), self.graph.iter_adj(n)) else {
+            return Box::new(std::iter::empty());
+        };
+        let outgoing = outgoing.map(move |a| (n, a, Direction::Outgoing));
+        let incoming = incoming
+            .filter(move |&a| a != n)
+            .map(move |a| (a, n, Direction::Incoming));
+        Box::new(outgoing.chain(incoming))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::Graph, impls::adj_list::AdjListGraph};
+
+    #[test]
+    fn test_reversed_edges() {
+        let mut g = AdjListGraph::new();
+        let id1 = g.add_node();
+        let id2 = g.add_node();
+        let id3 = g.add_node();
+        g.add_edge(id1, id2);
+        g.add_edge(id3, id2);
+
+        let r = Reversed::new(&g);
+        assert!(r.has_edge(id2, id1) && !r.has_edge(id1, id2));
+        assert_eq!(r.edge_count(), 2);
+
+        let mut adj: Vec<_> = r.iter_adj(id2).unwrap().collect();
+        adj.sort();
+        assert_eq!(adj, vec![id1, id3]);
+        assert_eq!(r.iter_adj(id1).unwrap().count(), 0);
+    }
+}
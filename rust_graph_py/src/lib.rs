@@ -0,0 +1,215 @@
+use pyo3::{exceptions::PyKeyError, prelude::*};
+use rust_graph_lib::{
+    algorithms::{AStar, CostedPath, Dijkstra},
+    graph::GraphRead,
+    impls::keyed::KeyedGraph,
+    io::dot::DotWriter,
+    utils::SlotKey,
+};
+use std::{cell::RefCell, collections::HashMap};
+
+type PathResult = Option<(Vec<String>, u64)>;
+
+// Python docstrings are taken from these comments, so they use `///` rather than
+// the block comments of the other crates.
+
+/// Directed graph whose nodes are identified by strings, with integer edge weights.
+#[pyclass(name = "Graph")]
+#[derive(Default)]
+pub struct PyGraph {
+    graph: KeyedGraph<String, ()>,
+    weights: HashMap<(SlotKey, SlotKey), u64>,
+}
+
+impl PyGraph {
+    fn node(&self, key: &str) -> PyResult<SlotKey> {
+        self.graph
+            .node_index(&key.to_string())
+            .ok_or_else(|| PyKeyError::new_err(key.to_string()))
+    }
+
+    fn key(&self, n: SlotKey) -> String {
+        self.graph.key(n).cloned().unwrap_or_default()
+    }
+
+    fn weight(&self, f: SlotKey, t: SlotKey) -> u64 {
+        self.weights[&(f, t)]
+    }
+
+    fn to_python(&self, path: Option<CostedPath<SlotKey, u64>>) -> PathResult {
+        path.map(|p| {
+            let keys = p.path.into_iter().map(|n| self.key(n)).collect();
+            (keys, p.cost)
+        })
+    }
+}
+
+#[pymethods]
+impl PyGraph {
+    /// Creates an empty graph.
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node, doing nothing if it is already in the graph.
+    fn add_node(&mut self, key: String) {
+        if self.graph.node_index(&key).is_none() {
+            self.graph.insert_node(key, ());
+        }
+    }
+
+    /// Adds the edge `source -> target`, creating the missing nodes, or changes its
+    /// weight if it is already in the graph.
+    #[pyo3(signature = (source, target, weight = 1))]
+    fn add_edge(&mut self, source: String, target: String, weight: u64) {
+        self.add_node(source.clone());
+        self.add_node(target.clone());
+        let (f, t) = (self.node(&source).unwrap(), self.node(&target).unwrap());
+        self.graph.add_edge(f, t);
+        self.weights.insert((f, t), weight);
+    }
+
+    /// Returns whether the node is in the graph.
+    fn has_node(&self, key: &str) -> bool {
+        self.node(key).is_ok()
+    }
+
+    /// Returns whether the edge `source -> target` is in the graph.
+    fn has_edge(&self, source: &str, target: &str) -> bool {
+        match (self.node(source), self.node(target)) {
+            (Ok(f), Ok(t)) => self.graph.has_edge(f, t),
+            _ => false,
+        }
+    }
+
+    /// Returns the keys of the nodes.
+    fn nodes(&self) -> Vec<String> {
+        self.graph.iter_nodes().map(|n| self.key(n)).collect()
+    }
+
+    /// Number of nodes.
+    #[getter]
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Number of edges.
+    #[getter]
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn __len__(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Returns the lightest path from `source` to `target` as a list of keys with its
+    /// cost, or `None` if there is no path. Raises `KeyError` for unknown nodes.
+    fn dijkstra(&self, source: &str, target: &str) -> PyResult<PathResult> {
+        let (f, t) = (self.node(source)?, self.node(target)?);
+        let search = Dijkstra::with_weights(&self.graph, |f, t| self.weight(f, t));
+        Ok(self.to_python(search.run_with_cost(f, t)))
+    }
+
+    /// Like `dijkstra`, guided by `heuristic(node, target)`, which must return an
+    /// integer never greater than the cost of the lightest path from `node` to
+    /// `target`. Errors raised by the heuristic are propagated.
+    fn astar(
+        &self,
+        py: Python<'_>,
+        source: &str,
+        target: &str,
+        heuristic: PyObject,
+    ) -> PyResult<PathResult> {
+        let (f, t) = (self.node(source)?, self.node(target)?);
+        let error = RefCell::new(None);
+        let estimate = |n: SlotKey, goal: SlotKey| {
+            if error.borrow().is_some() {
+                return 0;
+            }
+            let res = heuristic
+                .call1(py, (self.key(n), self.key(goal)))
+                .and_then(|h| h.extract::<u64>(py));
+            res.unwrap_or_else(|e| {
+                *error.borrow_mut() = Some(e);
+                0
+            })
+        };
+
+        let search = AStar::with_weights(&self.graph, |f, t| self.weight(f, t), estimate);
+        let path = search.run_with_cost(f, t);
+        drop(search);
+        match error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(self.to_python(path)),
+        }
+    }
+
+    /// Returns the graph in Graphviz DOT format, labelling the nodes with their keys
+    /// and the edges with their weights.
+    fn to_dot(&self) -> String {
+        let mut out = Vec::new();
+        DotWriter::new(&self.graph)
+            .node_attributes(|&n| vec![("label", self.key(n))])
+            .edge_attributes(|&f, &t| vec![("label", self.weight(f, t).to_string())])
+            .write(&mut out)
+            .expect("writing to memory");
+        String::from_utf8(out).expect("DOT output is UTF-8")
+    }
+}
+
+/// Graph algorithms implemented in Rust.
+#[pymodule]
+fn rust_graph(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyGraph>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::exceptions::PyValueError;
+
+    fn sample() -> PyGraph {
+        let mut g = PyGraph::new();
+        g.add_edge("a".into(), "b".into(), 1);
+        g.add_edge("b".into(), "c".into(), 1);
+        g.add_edge("a".into(), "c".into(), 5);
+        g.add_node("d".into());
+        g
+    }
+
+    #[test]
+    fn test_graph() {
+        let g = sample();
+        assert_eq!((g.node_count(), g.edge_count()), (4, 3));
+        assert!(g.has_edge("a", "b") && !g.has_edge("b", "a") && !g.has_edge("a", "x"));
+        assert_eq!(
+            g.dijkstra("a", "c").unwrap(),
+            Some((vec!["a".into(), "b".into(), "c".into()], 2))
+        );
+        assert_eq!(g.dijkstra("c", "a").unwrap(), None);
+        assert!(g.dijkstra("a", "x").is_err());
+
+        let dot = g.to_dot();
+        assert!(dot.starts_with("digraph {\n") && dot.contains("[label=\"a\"]"));
+        assert!(dot.contains("[label=\"5\"]"));
+    }
+
+    #[test]
+    fn test_astar() {
+        let g = sample();
+        Python::with_gil(|py| {
+            let zero = py.eval(c"lambda n, t: 0", None, None).unwrap().unbind();
+            let path = g.astar(py, "a", "c", zero).unwrap();
+            assert_eq!(path.unwrap().1, 2);
+
+            let failing = py
+                .eval(c"lambda n, t: int('x')", None, None)
+                .unwrap()
+                .unbind();
+            let err = g.astar(py, "a", "c", failing).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
@@ -0,0 +1,122 @@
+use rust_graph_lib::algorithms::Dijkstra;
+use rust_graph_lib::graph::{Graph, GraphRead};
+use rust_graph_lib::impls::adj_list::{AdjListGraph, Index};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/**
+ * Directed graph with integer edge weights, exported to JavaScript as `Graph`.
+ *
+ * Nodes are numbers returned by `addNode`, and edges weigh 1 unless a weight is
+ * given as the third argument of `addEdge`. `shortestPath(from, to)` returns the
+ * nodes of the lightest path as a `Uint32Array`, or `undefined` if there is none.
+ */
+#[wasm_bindgen(js_name = Graph)]
+pub struct JsGraph {
+    graph: AdjListGraph,
+    weights: HashMap<(Index, Index), u32>,
+    // the node with id `i` is at position `i - 1`, ids being numbered from 1
+    nodes: Vec<Index>,
+    ids: HashMap<Index, u32>,
+}
+
+#[wasm_bindgen(js_class = Graph)]
+impl JsGraph {
+    /**
+     * Creates an empty graph.
+     */
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsGraph {
+        JsGraph {
+            graph: AdjListGraph::new(),
+            weights: HashMap::new(),
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /**
+     * Adds a node, returning its id.
+     */
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self) -> u32 {
+        let n = self.graph.add_node();
+        let id = u32::try_from(self.nodes.len() + 1).expect("too many nodes");
+        self.nodes.push(n);
+        self.ids.insert(n, id);
+        id
+    }
+
+    /**
+     * Adds the edge `from -> to`, or changes its weight if it is already in the graph.
+     * Returns `false` if either node is not in the graph.
+     */
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, from: u32, to: u32, weight: Option<u32>) -> bool {
+        let (Some(f), Some(t)) = (self.index(from), self.index(to)) else {
+            return false;
+        };
+        self.graph.add_edge(f, t);
+        self.weights.insert((f, t), weight.unwrap_or(1));
+        true
+    }
+
+    /**
+     * Returns the number of nodes.
+     */
+    #[wasm_bindgen(getter, js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /**
+     * Returns the number of edges.
+     */
+    #[wasm_bindgen(getter, js_name = edgeCount)]
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /**
+     * Returns the ids of the nodes of the lightest path from `from` to `to`, or
+     * `undefined` if there is none.
+     */
+    #[wasm_bindgen(js_name = shortestPath)]
+    pub fn shortest_path(&self, from: u32, to: u32) -> Option<Vec<u32>> {
+        let search = Dijkstra::with_weights(&self.graph, |f, t| self.weights[&(f, t)]);
+        let path = search.run_with_cost(self.index(from)?, self.index(to)?)?;
+        Some(path.path.iter().map(|n| self.ids[n]).collect())
+    }
+
+    fn index(&self, id: u32) -> Option<Index> {
+        let pos = (id as usize).checked_sub(1)?;
+        self.nodes.get(pos).copied()
+    }
+}
+
+impl Default for JsGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_js_graph() {
+        let mut g = JsGraph::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node());
+        assert!(g.add_edge(a, b, None));
+        assert!(g.add_edge(b, c, None));
+        assert!(g.add_edge(a, c, Some(5)));
+        assert!(!g.add_edge(a, c + 1, None));
+        assert_eq!((g.node_count(), g.edge_count()), (3, 3));
+
+        assert_eq!(g.shortest_path(a, c), Some(vec![a, b, c]));
+        g.add_edge(a, c, Some(1));
+        assert_eq!(g.shortest_path(a, c), Some(vec![a, c]));
+        assert_eq!(g.shortest_path(c, a), None);
+    }
+}